@@ -0,0 +1,9 @@
+-- Per-user Gmail sync checkpoint for incremental imports
+CREATE TABLE IF NOT EXISTS email_sync_state (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    history_id VARCHAR(50) NOT NULL,
+    last_sync_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_full_sync_at TIMESTAMPTZ,
+    -- Gmail messages that could not be fetched, retried on the next sync
+    failed_message_ids TEXT[] NOT NULL DEFAULT '{}'
+);
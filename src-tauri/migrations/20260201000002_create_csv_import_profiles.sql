@@ -0,0 +1,27 @@
+-- Saved CSV column mappings for repeated statement imports
+CREATE TABLE IF NOT EXISTS csv_import_profiles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    delimiter VARCHAR(1) NOT NULL DEFAULT ',',
+    has_header BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Columns are header names, or 0-based indexes when there is no header
+    date_column VARCHAR(100) NOT NULL,
+    amount_column VARCHAR(100),
+    debit_column VARCHAR(100),
+    credit_column VARCHAR(100),
+    payee_column VARCHAR(100),
+    description_column VARCHAR(100),
+    date_format VARCHAR(50) NOT NULL DEFAULT '%d/%m/%Y',
+    decimal_separator VARCHAR(1) NOT NULL DEFAULT ',',
+    encoding VARCHAR(50) NOT NULL DEFAULT 'utf-8',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_csv_profile_per_user UNIQUE(user_id, name),
+    -- Either a signed amount column or separate debit/credit columns
+    CONSTRAINT csv_profile_amount_columns CHECK (
+        amount_column IS NOT NULL OR (debit_column IS NOT NULL AND credit_column IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_csv_import_profiles_user_id ON csv_import_profiles(user_id);
@@ -0,0 +1,7 @@
+-- Bank supplied transaction id (OFX FITID, statement references) for deduplication
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS external_id VARCHAR(255);
+
+-- Prevent duplicate statement imports; ids are only unique per source
+CREATE UNIQUE INDEX idx_transactions_external_id
+    ON transactions(user_id, source, external_id)
+    WHERE external_id IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Value date from bank statements (booking date stays in transaction_date)
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS value_date DATE;
@@ -0,0 +1,21 @@
+-- Optional parent for sub-categories, existing categories stay top-level
+ALTER TABLE categories
+    ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES categories(id) ON DELETE SET NULL;
+
+ALTER TABLE categories
+    ADD CONSTRAINT category_not_own_parent CHECK (parent_id IS NULL OR parent_id <> id);
+
+CREATE INDEX IF NOT EXISTS idx_categories_parent_id ON categories(parent_id);
+
+-- Category names are unique among siblings instead of per user, so
+-- "Food:Other" and "Bills:Other" can both exist. Top-level categories have
+-- no parent, which a plain UNIQUE constraint would treat as distinct, hence
+-- two indexes. Existing names were unique per user, which is stricter, so
+-- no duplicates have to be renamed before the indexes are built.
+ALTER TABLE categories DROP CONSTRAINT IF EXISTS unique_category_per_user;
+
+CREATE UNIQUE INDEX IF NOT EXISTS unique_top_level_category
+    ON categories(user_id, name) WHERE parent_id IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS unique_sub_category
+    ON categories(user_id, parent_id, name) WHERE parent_id IS NOT NULL;
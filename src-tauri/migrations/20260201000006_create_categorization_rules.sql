@@ -0,0 +1,26 @@
+-- Per-user rules that pick a category for new transactions
+CREATE TABLE IF NOT EXISTS categorization_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- Which text to match: 'store', 'description' or 'any'
+    match_field VARCHAR(20) NOT NULL DEFAULT 'any',
+    -- How to match: 'contains', 'exact' or 'regex' (case-insensitive)
+    match_type VARCHAR(20) NOT NULL DEFAULT 'contains',
+    -- NULL matches any text (amount/source only rules)
+    pattern TEXT,
+    -- Bounds on the absolute amount, inclusive
+    min_amount DECIMAL(12, 2),
+    max_amount DECIMAL(12, 2),
+    source VARCHAR(20),
+    -- Lower values are evaluated first
+    priority INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT categorization_rule_match_field CHECK (match_field IN ('store', 'description', 'any')),
+    CONSTRAINT categorization_rule_match_type CHECK (match_type IN ('contains', 'exact', 'regex'))
+);
+
+CREATE INDEX idx_categorization_rules_user_priority ON categorization_rules(user_id, priority);
@@ -0,0 +1,21 @@
+-- Token statistics for learned category suggestions (naive Bayes)
+CREATE TABLE IF NOT EXISTS category_token_counts (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    token VARCHAR(100) NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (user_id, category_id, token)
+);
+
+-- Number of training transactions and tokens per category
+CREATE TABLE IF NOT EXISTS category_training_totals (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    documents INTEGER NOT NULL DEFAULT 0,
+    tokens INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (user_id, category_id)
+);
+
+CREATE INDEX idx_category_token_counts_user_token ON category_token_counts(user_id, token);
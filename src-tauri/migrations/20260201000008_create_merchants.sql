@@ -0,0 +1,30 @@
+-- Canonical merchants; free-text store names link to them via normalized keys
+CREATE TABLE IF NOT EXISTS merchants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    -- Case/accent folded, transliterated, without terminal ids
+    normalized_name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_merchant_per_user UNIQUE(user_id, normalized_name)
+);
+
+-- Other spellings of a merchant (e.g. from merged merchants)
+CREATE TABLE IF NOT EXISTS merchant_aliases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    merchant_id UUID NOT NULL REFERENCES merchants(id) ON DELETE CASCADE,
+    alias VARCHAR(255) NOT NULL,
+    normalized_alias VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_merchant_alias_per_user UNIQUE(user_id, normalized_alias)
+);
+
+CREATE INDEX idx_merchant_aliases_merchant_id ON merchant_aliases(merchant_id);
+
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS merchant_id UUID REFERENCES merchants(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_merchant_id ON transactions(merchant_id);
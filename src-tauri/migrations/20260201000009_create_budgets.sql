@@ -0,0 +1,20 @@
+-- Monthly spending limit per category
+CREATE TABLE IF NOT EXISTS budgets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    -- Limit per month, sub-category spending counts towards it
+    amount DECIMAL(12, 2) NOT NULL,
+    -- Carry unused amounts of earlier months into later ones
+    rollover BOOLEAN NOT NULL DEFAULT FALSE,
+    -- First day of the first month the budget applies to
+    start_month DATE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_budget_per_category UNIQUE (user_id, category_id),
+    CONSTRAINT budget_amount_positive CHECK (amount > 0),
+    CONSTRAINT budget_start_month_first_day CHECK (EXTRACT(DAY FROM start_month) = 1)
+);
+
+CREATE INDEX idx_budgets_user ON budgets(user_id);
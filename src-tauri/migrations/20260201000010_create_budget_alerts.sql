@@ -0,0 +1,14 @@
+-- Percentages of a budget that trigger an alert when crossed
+ALTER TABLE budgets ADD COLUMN alert_thresholds INTEGER[] NOT NULL DEFAULT '{80,100}';
+
+-- Alerts already sent, so each threshold fires once per budget and month
+CREATE TABLE IF NOT EXISTS budget_alerts (
+    budget_id UUID NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- First day of the month the alert is for
+    month DATE NOT NULL,
+    threshold INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (budget_id, month, threshold)
+);
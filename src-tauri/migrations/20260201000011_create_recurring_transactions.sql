@@ -0,0 +1,34 @@
+-- Transactions that repeat on a schedule (rent, subscriptions, salary)
+CREATE TABLE IF NOT EXISTS recurring_transactions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID REFERENCES categories(id) ON DELETE SET NULL,
+    amount DECIMAL(12, 2) NOT NULL,
+    store VARCHAR(255),
+    description TEXT,
+    -- 'monthly', 'weekly', 'yearly' or 'last_business_day'
+    frequency VARCHAR(20) NOT NULL,
+    -- Day of the month for monthly schedules, clamped to shorter months
+    day_of_month SMALLINT,
+    -- First possible occurrence; weekly and yearly schedules repeat its weekday/date
+    start_date DATE NOT NULL,
+    -- Last possible occurrence, NULL repeats forever
+    end_date DATE,
+    -- Next occurrence to post
+    next_date DATE NOT NULL,
+    -- Occurrences the user chose not to post
+    skipped_dates DATE[] NOT NULL DEFAULT '{}',
+    is_paused BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Only lists upcoming occurrences without posting them, for charges
+    -- that are imported anyway (confirmed card subscriptions)
+    reminder_only BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT recurring_frequency CHECK (frequency IN ('monthly', 'weekly', 'yearly', 'last_business_day')),
+    CONSTRAINT recurring_day_of_month CHECK (day_of_month BETWEEN 1 AND 31),
+    CONSTRAINT recurring_amount_not_zero CHECK (amount <> 0)
+);
+
+CREATE INDEX idx_recurring_transactions_user ON recurring_transactions(user_id);
+CREATE INDEX idx_recurring_transactions_due ON recurring_transactions(next_date) WHERE NOT is_paused;
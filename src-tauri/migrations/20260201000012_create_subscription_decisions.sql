@@ -0,0 +1,13 @@
+-- Detected subscriptions the user confirmed or dismissed, hidden from detection
+CREATE TABLE IF NOT EXISTS subscription_decisions (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Detection key: 'merchant:<id>' or 'store:<normalized store name>'
+    subscription_key VARCHAR(300) NOT NULL,
+    -- 'confirmed' (turned into a recurring transaction) or 'dismissed'
+    decision VARCHAR(20) NOT NULL,
+    recurring_id UUID REFERENCES recurring_transactions(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, subscription_key),
+    CONSTRAINT subscription_decision_value CHECK (decision IN ('confirmed', 'dismissed'))
+);
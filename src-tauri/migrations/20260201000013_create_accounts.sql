@@ -0,0 +1,39 @@
+-- Accounts/wallets transactions are booked to
+CREATE TABLE IF NOT EXISTS accounts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- 'checking', 'credit_card', 'cash' or 'savings'
+    account_type VARCHAR(20) NOT NULL DEFAULT 'checking',
+    -- Balance before the first recorded transaction
+    opening_balance DECIMAL(12, 2) NOT NULL DEFAULT 0,
+    -- ISO 4217 code
+    currency CHAR(3) NOT NULL DEFAULT 'EUR',
+    -- Account used when a transaction does not name one
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_account_name_per_user UNIQUE (user_id, name),
+    CONSTRAINT account_type_value CHECK (account_type IN ('checking', 'credit_card', 'cash', 'savings'))
+);
+
+CREATE UNIQUE INDEX idx_accounts_one_default ON accounts(user_id) WHERE is_default;
+
+-- Every existing user gets a default account holding all their transactions
+INSERT INTO accounts (user_id, name, account_type, is_default)
+SELECT id, 'Main account', 'checking', TRUE FROM users;
+
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS account_id UUID REFERENCES accounts(id);
+
+UPDATE transactions t
+SET account_id = a.id
+FROM accounts a
+WHERE a.user_id = t.user_id AND a.is_default;
+
+ALTER TABLE transactions ALTER COLUMN account_id SET NOT NULL;
+
+CREATE INDEX idx_transactions_account_date ON transactions(account_id, transaction_date);
+
+-- Account posted occurrences are booked to, NULL means the default account
+ALTER TABLE recurring_transactions
+    ADD COLUMN IF NOT EXISTS account_id UUID REFERENCES accounts(id) ON DELETE SET NULL;
@@ -0,0 +1,5 @@
+-- Both legs of a transfer between accounts share this id; transfers are
+-- neither income nor expense
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS transfer_id UUID;
+
+CREATE INDEX idx_transactions_transfer ON transactions(transfer_id) WHERE transfer_id IS NOT NULL;
@@ -0,0 +1,74 @@
+-- Currency all amounts are summarized in
+ALTER TABLE users ADD COLUMN IF NOT EXISTS base_currency CHAR(3) NOT NULL DEFAULT 'EUR';
+
+-- Exchange rates: 1 unit of `base` is worth `rate` units of `quote`
+CREATE TABLE IF NOT EXISTS fx_rates (
+    base CHAR(3) NOT NULL,
+    quote CHAR(3) NOT NULL,
+    rate_date DATE NOT NULL,
+    rate DECIMAL(18, 8) NOT NULL,
+    -- Provider the rate came from, e.g. 'ecb'
+    source VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (base, quote, rate_date),
+    CONSTRAINT fx_rate_positive CHECK (rate > 0)
+);
+
+CREATE INDEX idx_fx_rates_quote_date ON fx_rates(quote, rate_date);
+
+-- Units of `to_currency` per unit of `from_currency` on a date
+--
+-- Uses the latest rate on or before the date (the nearest later one if
+-- there is none), directly, inverted or crossed through a shared base
+-- currency. NULL when no rates connect the two currencies.
+CREATE OR REPLACE FUNCTION fx_rate(from_currency TEXT, to_currency TEXT, on_date DATE)
+RETURNS DECIMAL
+LANGUAGE sql STABLE
+AS $$
+    WITH latest AS (
+        SELECT DISTINCT ON (base, quote) base::text AS base, quote::text AS quote, rate
+        FROM fx_rates
+        WHERE quote IN (from_currency, to_currency)
+        ORDER BY base, quote, rate_date > on_date, ABS(rate_date - on_date)
+    ),
+    rates AS (
+        SELECT base, quote, rate FROM latest
+        UNION ALL
+        SELECT DISTINCT base::text, base::text, 1::decimal
+        FROM fx_rates
+        WHERE base IN (from_currency, to_currency)
+    )
+    SELECT CASE
+        WHEN from_currency = to_currency THEN 1::decimal
+        ELSE (
+            SELECT ROUND(t.rate / f.rate, 8)
+            FROM rates f
+            JOIN rates t ON t.base = f.base
+            WHERE f.quote = from_currency AND t.quote = to_currency
+            LIMIT 1
+        )
+    END
+$$;
+
+-- `amount` holds the amount converted to the user's base currency, the
+-- amount as charged is kept in `original_amount`
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS currency CHAR(3);
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS original_amount DECIMAL(12, 2);
+-- Base currency units per unit of `currency`; NULL while no rate is known
+-- (`amount` then equals `original_amount`)
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS fx_rate DECIMAL(18, 8);
+
+UPDATE transactions t
+SET currency = a.currency,
+    original_amount = t.amount,
+    fx_rate = CASE WHEN a.currency = u.base_currency THEN 1 END
+FROM accounts a, users u
+WHERE a.id = t.account_id AND u.id = t.user_id;
+
+ALTER TABLE transactions ALTER COLUMN currency SET NOT NULL;
+ALTER TABLE transactions ALTER COLUMN original_amount SET NOT NULL;
+
+-- Currency of recurring amounts, NULL means the account's currency (as for
+-- manually added transactions)
+ALTER TABLE recurring_transactions ADD COLUMN IF NOT EXISTS currency CHAR(3);
@@ -0,0 +1,31 @@
+-- Lines a transaction is split into, each with its own category
+--
+-- Amounts are in the transaction's currency and add up to its
+-- `original_amount` (checked by the application).
+CREATE TABLE IF NOT EXISTS transaction_splits (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    category_id UUID REFERENCES categories(id) ON DELETE SET NULL,
+    amount DECIMAL(12, 2) NOT NULL,
+    note TEXT,
+    -- Order the lines were entered in
+    position SMALLINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_split_position UNIQUE (transaction_id, position)
+);
+
+CREATE INDEX idx_transaction_splits_category ON transaction_splits(category_id);
+
+-- Base currency amount per category: the transaction itself, or its split
+-- lines when it has any
+CREATE OR REPLACE VIEW category_amounts AS
+SELECT t.id AS transaction_id, t.user_id, t.category_id, t.amount,
+       t.transaction_date, t.transfer_id
+FROM transactions t
+WHERE NOT EXISTS (SELECT 1 FROM transaction_splits s WHERE s.transaction_id = t.id)
+UNION ALL
+SELECT t.id, t.user_id, s.category_id, ROUND(s.amount * COALESCE(t.fx_rate, 1), 2),
+       t.transaction_date, t.transfer_id
+FROM transaction_splits s
+JOIN transactions t ON t.id = s.transaction_id;
@@ -0,0 +1,10 @@
+-- Archived transaction ids already restored, so restoring the same backup
+-- again skips them even though they got new ids
+CREATE TABLE IF NOT EXISTS restored_transactions (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Id of the transaction in the backup archive
+    archived_id UUID NOT NULL,
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+
+    PRIMARY KEY (user_id, archived_id)
+);
@@ -72,6 +72,7 @@ const ERROR_HTML: &str = r#"
 "#;
 
 /// OAuth callback result
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct CallbackResult {
     pub code: String,
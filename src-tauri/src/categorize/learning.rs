@@ -0,0 +1,211 @@
+//! Learned category suggestions
+//!
+//! A multinomial naive Bayes classifier over words from `store` and
+//! `description`. Counts are stored per user and updated incrementally as
+//! transactions are categorized; scoring happens in memory.
+
+use super::text;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Prefix for the whole-store token, which rewards exact store matches
+const STORE_TOKEN_PREFIX: &str = "store:";
+/// Token column width in `category_token_counts`
+const MAX_TOKEN_LEN: usize = 100;
+
+/// Tokens used to train and classify a transaction
+pub fn tokenize(store: Option<&str>, description: Option<&str>) -> Vec<String> {
+    let mut tokens: Vec<String> = store
+        .into_iter()
+        .chain(description)
+        .flat_map(text::words)
+        .collect();
+
+    if let Some(store) = store {
+        let whole = text::words(store).collect::<Vec<_>>().join(" ");
+        if !whole.is_empty() {
+            tokens.push(format!("{}{}", STORE_TOKEN_PREFIX, whole));
+        }
+    }
+
+    tokens
+        .into_iter()
+        .filter(|token| token.chars().count() <= MAX_TOKEN_LEN)
+        .collect()
+}
+
+/// Pending count changes, written in one batch by `TrainingRepository::apply`
+#[derive(Debug, Default)]
+pub struct TrainingDelta {
+    pub tokens: HashMap<(Uuid, String), i64>,
+    /// (documents, tokens) per category
+    pub totals: HashMap<Uuid, (i64, i64)>,
+}
+
+impl TrainingDelta {
+    /// Count a categorized transaction
+    pub fn learn(&mut self, category_id: Uuid, store: Option<&str>, description: Option<&str>) {
+        self.add(category_id, store, description, 1);
+    }
+
+    /// Remove a previously counted transaction (re-categorized or deleted)
+    pub fn unlearn(&mut self, category_id: Uuid, store: Option<&str>, description: Option<&str>) {
+        self.add(category_id, store, description, -1);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    fn add(
+        &mut self,
+        category_id: Uuid,
+        store: Option<&str>,
+        description: Option<&str>,
+        sign: i64,
+    ) {
+        let tokens = tokenize(store, description);
+        if tokens.is_empty() {
+            return;
+        }
+
+        let totals = self.totals.entry(category_id).or_default();
+        totals.0 += sign;
+        totals.1 += sign * tokens.len() as i64;
+
+        for token in tokens {
+            *self.tokens.entry((category_id, token)).or_default() += sign;
+        }
+    }
+}
+
+/// Trained statistics for the tokens of one query
+pub struct NaiveBayes {
+    /// (documents, tokens) per category
+    pub totals: HashMap<Uuid, (i64, i64)>,
+    /// Counts for the queried tokens only
+    pub token_counts: HashMap<(Uuid, String), i64>,
+    /// Distinct tokens across all categories (for Laplace smoothing)
+    pub vocabulary_size: i64,
+}
+
+impl NaiveBayes {
+    /// Categories ranked by posterior probability, best first
+    ///
+    /// Returns nothing when none of the tokens were seen in training
+    pub fn rank(&self, tokens: &[String], limit: usize) -> Vec<(Uuid, f64)> {
+        let known: HashSet<&String> = self.token_counts.keys().map(|(_, token)| token).collect();
+        if tokens.iter().all(|token| !known.contains(token)) {
+            return Vec::new();
+        }
+
+        let total_documents: i64 = self.totals.values().map(|(documents, _)| documents).sum();
+        if total_documents <= 0 {
+            return Vec::new();
+        }
+
+        let vocabulary = self.vocabulary_size.max(1) as f64;
+        let mut scores: Vec<(Uuid, f64)> = self
+            .totals
+            .iter()
+            .filter(|(_, (documents, _))| *documents > 0)
+            .map(|(category_id, (documents, category_tokens))| {
+                let prior = (*documents as f64 / total_documents as f64).ln();
+                let likelihood: f64 = tokens
+                    .iter()
+                    .map(|token| {
+                        let count = self
+                            .token_counts
+                            .get(&(*category_id, token.clone()))
+                            .copied()
+                            .unwrap_or(0)
+                            .max(0) as f64;
+                        ((count + 1.0) / (*category_tokens as f64 + vocabulary)).ln()
+                    })
+                    .sum();
+                (*category_id, prior + likelihood)
+            })
+            .collect();
+
+        // Softmax over log scores turns them into confidences summing to 1
+        let max = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = scores.iter().map(|(_, score)| (score - max).exp()).sum();
+        for (_, score) in scores.iter_mut() {
+            *score = (*score - max).exp() / sum;
+        }
+
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores.truncate(limit);
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROCERIES: Uuid = Uuid::from_u128(1);
+    const FUEL: Uuid = Uuid::from_u128(2);
+
+    /// Model trained on `examples`, as `TrainingRepository` would load it
+    fn trained(examples: &[(Uuid, &str)]) -> NaiveBayes {
+        let mut delta = TrainingDelta::default();
+        for (category_id, store) in examples {
+            delta.learn(*category_id, Some(store), None);
+        }
+        let vocabulary: HashSet<&String> = delta.tokens.keys().map(|(_, token)| token).collect();
+
+        NaiveBayes {
+            vocabulary_size: vocabulary.len() as i64,
+            totals: delta.totals,
+            token_counts: delta.tokens,
+        }
+    }
+
+    #[test]
+    fn ranks_the_matching_category_first() {
+        let model = trained(&[
+            (GROCERIES, "AB VASILOPOULOS"),
+            (GROCERIES, "SKLAVENITIS"),
+            (GROCERIES, "AB VASILOPOULOS"),
+            (FUEL, "SHELL"),
+            (FUEL, "AVIN"),
+        ]);
+
+        let ranked = model.rank(&tokenize(Some("AB VASILOPOULOS"), None), 5);
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].0, GROCERIES);
+        assert!(ranked[0].1 > 0.9);
+        let total: f64 = ranked.iter().map(|(_, confidence)| confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_tokens_give_no_suggestions() {
+        let model = trained(&[(GROCERIES, "SKLAVENITIS"), (FUEL, "SHELL")]);
+
+        assert!(model.rank(&tokenize(Some("BOOKSTORE"), None), 5).is_empty());
+    }
+
+    #[test]
+    fn unlearned_categories_drop_out() {
+        let mut model = trained(&[(GROCERIES, "SHELL SHOP"), (FUEL, "SHELL")]);
+        model.totals.insert(GROCERIES, (0, 0));
+
+        let ranked = model.rank(&tokenize(Some("SHELL"), None), 5);
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0, FUEL);
+    }
+
+    #[test]
+    fn limit_truncates_the_ranking() {
+        let model = trained(&[(GROCERIES, "KIOSK"), (FUEL, "KIOSK")]);
+
+        assert_eq!(model.rank(&tokenize(Some("KIOSK"), None), 1).len(), 1);
+    }
+}
@@ -0,0 +1,306 @@
+//! Rule based transaction categorization
+//!
+//! A user's active rules are compiled once into a `RuleEngine` and evaluated
+//! in priority order; the first matching rule picks the category. Text
+//! matching is case-insensitive and amount bounds apply to the absolute
+//! amount so the same rule works for charges and refunds. Store patterns
+//! are also tried against the canonical merchant name, so one rule covers
+//! every spelling of a shop.
+
+pub mod learning;
+pub mod text;
+
+use crate::db::repository::RuleRepository;
+use crate::error::{AppError, Result};
+use crate::models::{CategorizationRule, CreateTransaction};
+use regex::{Regex, RegexBuilder};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Transaction fields rules are evaluated against
+#[derive(Debug, Clone, Copy)]
+pub struct RuleInput<'a> {
+    pub store: Option<&'a str>,
+    /// Canonical name of the merchant the store is linked to
+    pub merchant: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub amount: Decimal,
+    pub source: Option<&'a str>,
+}
+
+impl<'a> From<&'a CreateTransaction> for RuleInput<'a> {
+    fn from(tx: &'a CreateTransaction) -> Self {
+        Self {
+            store: tx.store.as_deref(),
+            merchant: None,
+            description: tx.description.as_deref(),
+            amount: tx.amount,
+            source: Some(tx.source.as_str()),
+        }
+    }
+}
+
+enum Matcher {
+    Any,
+    Contains(String),
+    Exact(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn new(match_type: &str, pattern: Option<&str>) -> Result<Self> {
+        let Some(pattern) = pattern.map(str::trim).filter(|p| !p.is_empty()) else {
+            return Ok(Matcher::Any);
+        };
+
+        match match_type {
+            "contains" => Ok(Matcher::Contains(pattern.to_lowercase())),
+            "exact" => Ok(Matcher::Exact(pattern.to_lowercase())),
+            "regex" => RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map(Matcher::Regex)
+                .map_err(|e| AppError::Validation(format!("Invalid regex '{}': {}", pattern, e))),
+            other => Err(AppError::Validation(format!(
+                "Unknown match type '{}' (expected contains, exact or regex)",
+                other
+            ))),
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Contains(needle) => text.to_lowercase().contains(needle),
+            Matcher::Exact(value) => text.trim().to_lowercase() == *value,
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+struct CompiledRule {
+    rule: CategorizationRule,
+    matcher: Matcher,
+}
+
+impl CompiledRule {
+    fn matches(&self, input: &RuleInput) -> bool {
+        let rule = &self.rule;
+
+        if let Some(source) = &rule.source {
+            if input.source != Some(source.as_str()) {
+                return false;
+            }
+        }
+
+        let amount = input.amount.abs();
+        if rule.min_amount.is_some_and(|min| amount < min)
+            || rule.max_amount.is_some_and(|max| amount > max)
+        {
+            return false;
+        }
+
+        if matches!(self.matcher, Matcher::Any) {
+            return true;
+        }
+
+        let (store, merchant, description) = match rule.match_field.as_str() {
+            "store" => (input.store, input.merchant, None),
+            "description" => (None, None, input.description),
+            _ => (input.store, input.merchant, input.description),
+        };
+
+        [store, merchant, description]
+            .into_iter()
+            .flatten()
+            .any(|text| self.matcher.matches(text))
+    }
+}
+
+/// A user's compiled categorization rules
+pub struct RuleEngine {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleEngine {
+    /// Compile rules (expected in priority order), skipping inactive or invalid ones
+    pub fn new(rules: Vec<CategorizationRule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| rule.is_active)
+            .filter_map(
+                |rule| match Matcher::new(&rule.match_type, rule.pattern.as_deref()) {
+                    Ok(matcher) => Some(CompiledRule { rule, matcher }),
+                    Err(e) => {
+                        tracing::warn!("Skipping categorization rule {}: {}", rule.id, e);
+                        None
+                    }
+                },
+            )
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Load the user's rules from the database
+    pub async fn load(pool: &PgPool, user_id: Uuid) -> Result<Self> {
+        Ok(Self::new(
+            RuleRepository::find_by_user(pool, user_id).await?,
+        ))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// First rule matching the input
+    pub fn find_match(&self, input: RuleInput) -> Option<&CategorizationRule> {
+        self.rules
+            .iter()
+            .find(|compiled| compiled.matches(&input))
+            .map(|compiled| &compiled.rule)
+    }
+
+    /// Fill in `category_id` from the rules if the transaction has none
+    pub fn apply(&self, tx: &mut CreateTransaction, merchant: Option<&str>) {
+        if tx.category_id.is_some() {
+            return;
+        }
+
+        let input = RuleInput {
+            merchant,
+            ..RuleInput::from(&*tx)
+        };
+        tx.category_id = self.find_match(input).map(|rule| rule.category_id);
+    }
+}
+
+/// Check that a rule's match settings are usable before saving it
+pub fn validate_rule(match_field: &str, match_type: &str, pattern: Option<&str>) -> Result<()> {
+    if !matches!(match_field, "store" | "description" | "any") {
+        return Err(AppError::Validation(format!(
+            "Unknown match field '{}' (expected store, description or any)",
+            match_field
+        )));
+    }
+
+    Matcher::new(match_type, pattern).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule(category: u128, match_type: &str, pattern: Option<&str>) -> CategorizationRule {
+        CategorizationRule {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            category_id: Uuid::from_u128(category),
+            name: format!("Rule {}", category),
+            match_field: "any".to_string(),
+            match_type: match_type.to_string(),
+            pattern: pattern.map(str::to_string),
+            min_amount: None,
+            max_amount: None,
+            source: None,
+            priority: 0,
+            is_active: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn input(store: &str, amount: i64) -> RuleInput<'_> {
+        RuleInput {
+            store: Some(store),
+            merchant: None,
+            description: None,
+            amount: Decimal::new(amount, 2),
+            source: Some("manual"),
+        }
+    }
+
+    fn matched(engine: &RuleEngine, input: RuleInput) -> Option<u128> {
+        engine
+            .find_match(input)
+            .map(|rule| rule.category_id.as_u128())
+    }
+
+    #[test]
+    fn text_matching_ignores_case() {
+        let engine = RuleEngine::new(vec![
+            rule(1, "contains", Some("Shell")),
+            rule(2, "regex", Some(r"^ab\s+vasil")),
+            rule(3, "exact", Some("kiosk")),
+        ]);
+
+        assert_eq!(matched(&engine, input("SHELL STATION 42", -5000)), Some(1));
+        assert_eq!(matched(&engine, input("AB VASILOPOULOS", -2345)), Some(2));
+        assert_eq!(matched(&engine, input(" KIOSK ", -150)), Some(3));
+        assert_eq!(matched(&engine, input("KIOSK 2", -150)), None);
+    }
+
+    #[test]
+    fn amount_bounds_are_inclusive_on_the_absolute_amount() {
+        let engine = RuleEngine::new(vec![CategorizationRule {
+            min_amount: Some(Decimal::new(1000, 2)),
+            max_amount: Some(Decimal::new(5000, 2)),
+            ..rule(1, "contains", None)
+        }]);
+
+        assert_eq!(matched(&engine, input("SHOP", -1000)), Some(1));
+        assert_eq!(matched(&engine, input("SHOP", 5000)), Some(1));
+        assert_eq!(matched(&engine, input("SHOP", -999)), None);
+        assert_eq!(matched(&engine, input("SHOP", -5001)), None);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let engine = RuleEngine::new(vec![
+            rule(1, "contains", Some("shell")),
+            rule(2, "contains", Some("shell station")),
+            rule(3, "contains", None),
+        ]);
+
+        assert_eq!(matched(&engine, input("SHELL STATION", -5000)), Some(1));
+        assert_eq!(matched(&engine, input("BAKERY", -120)), Some(3));
+    }
+
+    #[test]
+    fn inactive_and_invalid_rules_are_skipped() {
+        let engine = RuleEngine::new(vec![
+            CategorizationRule {
+                is_active: false,
+                ..rule(1, "contains", Some("shell"))
+            },
+            rule(2, "regex", Some("(shell")),
+            rule(3, "contains", Some("shell")),
+        ]);
+
+        assert_eq!(matched(&engine, input("SHELL", -5000)), Some(3));
+    }
+
+    #[test]
+    fn store_rules_match_the_merchant_name() {
+        let engine = RuleEngine::new(vec![CategorizationRule {
+            match_field: "store".to_string(),
+            ..rule(1, "exact", Some("Vasilopoulos"))
+        }]);
+
+        let with_merchant = RuleInput {
+            merchant: Some("Vasilopoulos"),
+            ..input("AB VASILOPOULOS 0231", -2345)
+        };
+        assert_eq!(matched(&engine, with_merchant), Some(1));
+        assert_eq!(matched(&engine, input("AB VASILOPOULOS 0231", -2345)), None);
+    }
+
+    #[test]
+    fn invalid_match_settings_are_rejected() {
+        assert!(validate_rule("any", "regex", Some("(unclosed")).is_err());
+        assert!(validate_rule("payee", "contains", Some("shell")).is_err());
+        assert!(validate_rule("store", "fuzzy", Some("shell")).is_err());
+        assert!(validate_rule("store", "regex", Some("^shell")).is_ok());
+    }
+}
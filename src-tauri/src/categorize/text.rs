@@ -0,0 +1,171 @@
+//! Text normalization shared by the classifier and merchant matching
+
+/// Lowercase and strip Greek accents/diaeresis, final sigma becomes σ
+pub fn fold(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'ά' => 'α',
+            'έ' => 'ε',
+            'ή' => 'η',
+            'ί' | 'ϊ' | 'ΐ' => 'ι',
+            'ό' => 'ο',
+            'ύ' | 'ϋ' | 'ΰ' => 'υ',
+            'ώ' => 'ω',
+            'ς' => 'σ',
+            other => other,
+        })
+        .collect()
+}
+
+/// Folded alphanumeric words (digits-only words and single letters dropped)
+pub fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1 && !word.chars().all(|c| c.is_ascii_digit()))
+        .map(fold)
+}
+
+/// Matching key for a merchant name
+///
+/// Folds case and accents, transliterates Greek to Latin, joins dotted
+/// abbreviations ("A.B." -> "ab") and drops trailing terminal/store ids, so
+/// "AB VASILOPOULOS 123", "A.B. Vasilopoulos" and "ΑΒ ΒΑΣΙΛΟΠΟΥΛΟΣ" all
+/// become "ab vasilopoulos".
+pub fn merchant_key(name: &str) -> String {
+    let mut words: Vec<String> = name
+        .replace('.', "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            if is_greek_initials(word) {
+                transliterate_initials(word)
+            } else {
+                transliterate(&fold(word))
+            }
+        })
+        .collect();
+
+    // Terminal ids, branch numbers and references ("123", "T0045", "#12")
+    while words.len() > 1
+        && words
+            .last()
+            .is_some_and(|word| word.chars().any(|c| c.is_ascii_digit()))
+    {
+        words.pop();
+    }
+
+    words.join(" ")
+}
+
+/// Display name for a new merchant: the store without trailing ids
+pub fn merchant_display_name(store: &str) -> String {
+    let mut words: Vec<&str> = store.split_whitespace().collect();
+    while words.len() > 1
+        && words
+            .last()
+            .is_some_and(|word| word.chars().any(|c| c.is_ascii_digit()))
+    {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Short all-capital Greek words are initials ("ΑΒ", "Α.Β.")
+fn is_greek_initials(word: &str) -> bool {
+    word.chars().count() <= 3 && word.chars().all(|c| ('Α'..='Ω').contains(&c))
+}
+
+/// Initials letter by letter, so "ΑΒ" becomes "ab" rather than "av"
+fn transliterate_initials(word: &str) -> String {
+    fold(word)
+        .chars()
+        .map(|c| match c {
+            'β' => "b".to_string(),
+            other => transliterate(&other.to_string()),
+        })
+        .collect()
+}
+
+/// Greek to Latin (simplified ELOT 743) on folded text
+fn transliterate(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let next = chars.peek().copied();
+        let digraph = match (c, next) {
+            ('ο', Some('υ')) => Some("ou"),
+            ('α', Some('υ')) => Some("av"),
+            ('ε', Some('υ')) => Some("ev"),
+            ('μ', Some('π')) => Some("mp"),
+            ('ν', Some('τ')) => Some("nt"),
+            ('γ', Some('γ')) => Some("ng"),
+            ('γ', Some('κ')) => Some("gk"),
+            _ => None,
+        };
+        if let Some(latin) = digraph {
+            out.push_str(latin);
+            chars.next();
+            continue;
+        }
+
+        let latin = match c {
+            'α' => "a",
+            'β' => "v",
+            'γ' => "g",
+            'δ' => "d",
+            'ε' => "e",
+            'ζ' => "z",
+            'η' => "i",
+            'θ' => "th",
+            'ι' => "i",
+            'κ' => "k",
+            'λ' => "l",
+            'μ' => "m",
+            'ν' => "n",
+            'ξ' => "x",
+            'ο' => "o",
+            'π' => "p",
+            'ρ' => "r",
+            'σ' => "s",
+            'τ' => "t",
+            'υ' => "y",
+            'φ' => "f",
+            'χ' => "ch",
+            'ψ' => "ps",
+            'ω' => "o",
+            other => {
+                out.push(other);
+                continue;
+            }
+        };
+        out.push_str(latin);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merchant_spellings_share_a_key() {
+        for name in [
+            "AB VASILOPOULOS 123",
+            "A.B. Vasilopoulos",
+            "AB ΒΑΣΙΛΟΠΟΥΛΟΣ",
+            "ΑΒ ΒΑΣΙΛΟΠΟΥΛΟΣ",
+            "Α.Β. Βασιλόπουλος",
+        ] {
+            assert_eq!(merchant_key(name), "ab vasilopoulos", "{}", name);
+        }
+    }
+
+    #[test]
+    fn greek_words_use_digraphs() {
+        assert_eq!(merchant_key("ΕΥΔΑΠ"), "evdap");
+        assert_eq!(merchant_key("ΚΑΦΕ ΜΠΑΡ ΑΥΡΑ"), "kafe mpar avra");
+        assert_eq!(merchant_key("ΟΤΕ T0045"), "ote");
+    }
+}
@@ -1,14 +1,33 @@
 use crate::{
     auth,
     config::Settings,
-    models::{Category, Transaction, User},
-    services::{CategoryService, TransactionService},
+    db::repository::EmailSyncRepository,
+    email::{EmailImporter, GmailClient, ImportSummary},
+    export::{journal::JournalFormat, ExportFormat},
+    fx::EcbFileProvider,
+    import::{ImportPreview, StatementFormat},
+    models::{
+        Account, AccountWithBalance, Budget, BudgetStatus, CategorizationRule, Category,
+        CategoryNode, CategorySuggestion, CategorySummary, CreateAccount, CreateCategorizationRule,
+        CreateCategory, CreateCsvImportProfile, CreateRecurringTransaction, CreateTransaction,
+        CreateTransfer, CsvImportProfile, EmailSyncState, FxRate, Merchant, MerchantSpending,
+        MerchantWithAliases, RateImportSummary, RecurringWithUpcoming, RuleMatch, SetBudget,
+        SplitLine, SubscriptionReport, Transaction, TransactionSplit, TransactionWithBalance,
+        Transfer, UpdateCategory, UpdateTransaction, User, DEFAULT_ALERT_THRESHOLDS,
+    },
+    notifications,
+    services::{
+        AccountService, BackupService, BackupSummary, BudgetService, CategoryService,
+        CurrencyService, ExportService, ImportService, MerchantService, RecurringService,
+        RestoreSummary, RuleService, StatementImportResult, SubscriptionService, SuggestionService,
+        TransactionService, UserService,
+    },
     state::AppState,
 };
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State};
 
 /// Filter for querying transactions
 #[derive(Debug, Deserialize)]
@@ -16,6 +35,7 @@ pub struct TransactionFilter {
     pub year: Option<i32>,
     pub month: Option<u32>,
     pub category_id: Option<String>,
+    pub account_id: Option<String>,
 }
 
 /// Data for creating a new transaction
@@ -27,6 +47,274 @@ pub struct CreateTransactionInput {
     pub category_id: Option<String>,
     pub transaction_date: String,
     pub is_income: bool,
+    /// Defaults to the user's default account
+    pub account_id: Option<String>,
+    /// Defaults to the account's currency
+    pub currency: Option<String>,
+}
+
+/// Data for creating a category
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryInput {
+    pub name: String,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub is_income: bool,
+    pub parent_id: Option<String>,
+}
+
+/// Changes to an existing category
+///
+/// `icon: null` removes the icon, `parent_id: null` makes it top-level
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateCategoryInput {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub icon: Option<Option<String>>,
+    pub is_income: Option<bool>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub parent_id: Option<Option<String>>,
+}
+
+/// Changes to an existing transaction
+///
+/// Omitted fields are left unchanged; `null` clears nullable fields
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateTransactionInput {
+    pub amount: Option<f64>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub store: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub category_id: Option<Option<String>>,
+    pub transaction_date: Option<String>,
+    pub account_id: Option<String>,
+    /// ISO 4217 code the amount is in
+    pub currency: Option<String>,
+}
+
+/// Data for a transfer between two accounts
+#[derive(Debug, Deserialize)]
+pub struct TransferInput {
+    pub from_account_id: String,
+    pub to_account_id: String,
+    /// Amount moved, positive
+    pub amount: f64,
+    pub transaction_date: String,
+    pub description: Option<String>,
+}
+
+/// One line of a split transaction
+#[derive(Debug, Deserialize)]
+pub struct SplitLineInput {
+    pub category_id: Option<String>,
+    /// Signed like the transaction, in its currency
+    pub amount: f64,
+    pub note: Option<String>,
+}
+
+impl SplitLineInput {
+    fn into_line(self) -> Result<SplitLine, String> {
+        Ok(SplitLine {
+            category_id: self
+                .category_id
+                .map(|id| {
+                    uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid category ID: {}", e))
+                })
+                .transpose()?,
+            amount: Decimal::try_from(self.amount).map_err(|e| format!("Invalid amount: {}", e))?,
+            note: self.note,
+        })
+    }
+}
+
+/// Keep an explicit `null` as `Some(None)` instead of collapsing it to `None`
+fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Data for creating or replacing a categorization rule
+#[derive(Debug, Deserialize)]
+pub struct CategorizationRuleInput {
+    pub name: String,
+    pub category_id: String,
+    #[serde(default = "default_match_field")]
+    pub match_field: String,
+    #[serde(default = "default_match_type")]
+    pub match_type: String,
+    pub pattern: Option<String>,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+fn default_match_field() -> String {
+    "any".to_string()
+}
+
+fn default_match_type() -> String {
+    "contains".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl CategorizationRuleInput {
+    fn into_create(self, user_id: uuid::Uuid) -> Result<CreateCategorizationRule, String> {
+        let category_id = uuid::Uuid::parse_str(&self.category_id)
+            .map_err(|e| format!("Invalid category ID: {}", e))?;
+        let amount = |value: Option<f64>| {
+            value
+                .map(|v| Decimal::try_from(v).map_err(|e| format!("Invalid amount: {}", e)))
+                .transpose()
+        };
+
+        Ok(CreateCategorizationRule {
+            user_id,
+            category_id,
+            name: self.name,
+            match_field: self.match_field,
+            match_type: self.match_type,
+            pattern: self.pattern,
+            min_amount: amount(self.min_amount)?,
+            max_amount: amount(self.max_amount)?,
+            source: self.source,
+            priority: self.priority,
+            is_active: self.is_active,
+        })
+    }
+}
+
+/// Monthly budget for a category
+#[derive(Debug, Deserialize)]
+pub struct BudgetInput {
+    pub category_id: String,
+    pub amount: f64,
+    #[serde(default)]
+    pub rollover: bool,
+    /// Any date in the first budgeted month (`YYYY-MM-DD`), defaults to the current month
+    pub start_month: Option<String>,
+    /// Percentages of the budget that trigger an alert, defaults to 80 and 100
+    pub alert_thresholds: Option<Vec<i32>>,
+}
+
+/// Data for creating or replacing a recurring transaction
+#[derive(Debug, Deserialize)]
+pub struct RecurringTransactionInput {
+    pub amount: f64,
+    pub store: Option<String>,
+    pub description: Option<String>,
+    pub category_id: Option<String>,
+    /// `monthly`, `weekly`, `yearly` or `last_business_day`
+    pub frequency: String,
+    /// Day of the month for monthly schedules, defaults to the start date's day
+    pub day_of_month: Option<i16>,
+    /// First possible occurrence (`YYYY-MM-DD`), defaults to today
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// Defaults to the user's default account
+    pub account_id: Option<String>,
+    /// Defaults to the account's currency
+    pub currency: Option<String>,
+    /// List occurrences without posting them
+    #[serde(default)]
+    pub reminder_only: bool,
+}
+
+impl RecurringTransactionInput {
+    fn into_create(self, user_id: uuid::Uuid) -> Result<CreateRecurringTransaction, String> {
+        let category_id = self
+            .category_id
+            .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid category ID: {}", e)))
+            .transpose()?;
+        let account_id = self
+            .account_id
+            .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid account ID: {}", e)))
+            .transpose()?;
+        let amount =
+            Decimal::try_from(self.amount).map_err(|e| format!("Invalid amount: {}", e))?;
+        let date = |value: String| {
+            NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date format: {}", e))
+        };
+
+        Ok(CreateRecurringTransaction {
+            user_id,
+            category_id,
+            amount,
+            store: self.store,
+            description: self.description,
+            frequency: self.frequency,
+            day_of_month: self.day_of_month,
+            start_date: match self.start_date {
+                Some(start_date) => date(start_date)?,
+                None => chrono::Local::now().date_naive(),
+            },
+            end_date: self.end_date.map(date).transpose()?,
+            account_id,
+            currency: self.currency,
+            reminder_only: self.reminder_only,
+        })
+    }
+}
+
+/// Data for creating or replacing an account
+#[derive(Debug, Deserialize)]
+pub struct AccountInput {
+    pub name: String,
+    /// `checking`, `credit_card`, `cash` or `savings`
+    #[serde(default = "default_account_type")]
+    pub account_type: String,
+    #[serde(default)]
+    pub opening_balance: f64,
+    /// Defaults to the user's base currency
+    pub currency: Option<String>,
+}
+
+fn default_account_type() -> String {
+    "checking".to_string()
+}
+
+impl AccountInput {
+    fn into_create(self, user: &User) -> Result<CreateAccount, String> {
+        let opening_balance = Decimal::try_from(self.opening_balance)
+            .map_err(|e| format!("Invalid amount: {}", e))?;
+
+        Ok(CreateAccount {
+            user_id: user.id,
+            name: self.name,
+            account_type: self.account_type,
+            opening_balance,
+            currency: self.currency.unwrap_or_else(|| user.base_currency.clone()),
+        })
+    }
+}
+
+/// Data for saving a CSV column mapping profile
+#[derive(Debug, Deserialize)]
+pub struct CsvProfileInput {
+    pub name: String,
+    pub delimiter: String,
+    pub has_header: bool,
+    pub date_column: String,
+    pub amount_column: Option<String>,
+    pub debit_column: Option<String>,
+    pub credit_column: Option<String>,
+    pub payee_column: Option<String>,
+    pub description_column: Option<String>,
+    pub date_format: String,
+    pub decimal_separator: String,
+    pub encoding: String,
 }
 
 /// Monthly summary response
@@ -36,6 +324,8 @@ pub struct MonthlySummary {
     pub expenses: f64,
     pub balance: f64,
     pub transaction_count: i64,
+    /// The user's base currency, all amounts are converted to it
+    pub currency: String,
 }
 
 /// Get the current logged-in user
@@ -72,6 +362,11 @@ pub async fn login(state: State<'_, AppState>) -> Result<User, String> {
             .map_err(|e| e.to_string())?;
     }
 
+    AccountService::new(state.pool.clone())
+        .ensure_default_account(user.id)
+        .await
+        .map_err(|e| e.to_string())?;
+
     Ok(user)
 }
 
@@ -98,6 +393,162 @@ pub async fn get_categories(state: State<'_, AppState>) -> Result<Vec<Category>,
         .map_err(|e| e.to_string())
 }
 
+/// Get the current user's categories as parent/sub-category trees
+#[tauri::command]
+pub async fn get_category_tree(state: State<'_, AppState>) -> Result<Vec<CategoryNode>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = CategoryService::new(state.pool.clone());
+    service
+        .get_category_tree(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Per-category totals for a month, sub-categories rolled up into parents
+#[tauri::command]
+pub async fn get_category_summary(
+    state: State<'_, AppState>,
+    year: i32,
+    month: u32,
+) -> Result<Vec<CategorySummary>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let filter = crate::models::TransactionFilter {
+        year: Some(year),
+        month: Some(month),
+        ..Default::default()
+    };
+    let (Some(start_date), Some(end_date)) = filter.date_range() else {
+        return Err("Invalid year/month".to_string());
+    };
+
+    let service = CategoryService::new(state.pool.clone());
+    service
+        .get_category_summary(user.id, start_date, end_date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a category for the current user
+#[tauri::command]
+pub async fn create_category(
+    state: State<'_, AppState>,
+    category: CreateCategoryInput,
+) -> Result<Category, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let parent_id = category
+        .parent_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid parent ID: {}", e)))
+        .transpose()?;
+
+    let service = CategoryService::new(state.pool.clone());
+    service
+        .create_category(CreateCategory {
+            user_id: user.id,
+            name: category.name.trim().to_string(),
+            icon: category.icon,
+            is_income: category.is_income,
+            parent_id,
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rename a category or change its icon/income flag
+#[tauri::command]
+pub async fn update_category(
+    state: State<'_, AppState>,
+    category_id: String,
+    changes: UpdateCategoryInput,
+) -> Result<Category, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&category_id).map_err(|e| format!("Invalid category ID: {}", e))?;
+
+    let parent_id = changes
+        .parent_id
+        .map(|id| {
+            id.map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid parent ID: {}", e)))
+                .transpose()
+        })
+        .transpose()?;
+
+    let service = CategoryService::new(state.pool.clone());
+    service
+        .update_category(
+            user.id,
+            id,
+            UpdateCategory {
+                name: changes.name,
+                icon: changes.icon,
+                is_income: changes.is_income,
+                parent_id,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a category, its transactions become uncategorized
+#[tauri::command]
+pub async fn delete_category(
+    state: State<'_, AppState>,
+    category_id: String,
+) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&category_id).map_err(|e| format!("Invalid category ID: {}", e))?;
+
+    let service = CategoryService::new(state.pool.clone());
+    service
+        .delete_category(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merge one category into another, returns the number of transactions moved
+#[tauri::command]
+pub async fn merge_categories(
+    state: State<'_, AppState>,
+    source_category_id: String,
+    target_category_id: String,
+) -> Result<u64, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let source_id = uuid::Uuid::parse_str(&source_category_id)
+        .map_err(|e| format!("Invalid category ID: {}", e))?;
+    let target_id = uuid::Uuid::parse_str(&target_category_id)
+        .map_err(|e| format!("Invalid category ID: {}", e))?;
+
+    let service = CategoryService::new(state.pool.clone());
+    service
+        .merge_categories(user.id, source_id, target_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Get transactions with optional filtering
 #[tauri::command]
 pub async fn get_transactions(
@@ -111,24 +562,40 @@ pub async fn get_transactions(
 
     let service = TransactionService::new(state.pool.clone());
 
-    // If year and month are specified, filter by month
-    if let (Some(year), Some(month)) = (filter.year, filter.month) {
-        service
-            .get_user_transactions_by_month(user.id, year, month)
-            .await
-            .map_err(|e| e.to_string())
-    } else {
-        // Get all transactions (limited)
-        service
-            .get_user_transactions(user.id, 100)
-            .await
-            .map_err(|e| e.to_string())
-    }
+    let category_id = filter
+        .category_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid category ID: {}", e)))
+        .transpose()?;
+    let account_id = filter
+        .account_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid account ID: {}", e)))
+        .transpose()?;
+
+    let filter = crate::models::TransactionFilter {
+        year: filter.year,
+        month: filter.month,
+        category_id,
+        account_id,
+        ..Default::default()
+    };
+    // Date range from year/month, at most 100 (the latest) transactions
+    let (start_date, end_date) = filter.date_range();
+    let filter = crate::models::TransactionFilter {
+        start_date,
+        end_date,
+        ..filter
+    };
+
+    service
+        .get_transactions(user.id, filter)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Add a new transaction
 #[tauri::command]
 pub async fn add_transaction(
+    app: AppHandle,
     state: State<'_, AppState>,
     transaction: CreateTransactionInput,
 ) -> Result<Transaction, String> {
@@ -151,61 +618,1335 @@ pub async fn add_transaction(
         })
         .transpose()?;
 
+    let account_id = transaction
+        .account_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid account ID: {}", e)))
+        .transpose()?;
+
     // Convert amount to Decimal
     let amount = Decimal::try_from(transaction.amount)
         .map_err(|e| format!("Invalid amount: {}", e))?;
 
     let tx = service
-        .create_transaction(
-            user.id,
-            amount,
-            transaction.store,
-            transaction.description,
+        .create_transaction_from_dto(CreateTransaction {
+            user_id: user.id,
             category_id,
-            date,
-            "manual".to_string(),
-        )
+            amount,
+            store: transaction.store,
+            description: transaction.description,
+            source: "manual".to_string(),
+            email_message_id: None,
+            external_id: None,
+            transaction_date: date,
+            value_date: None,
+            merchant_id: None,
+            account_id,
+            transfer_id: None,
+            currency: transaction.currency,
+        })
         .await
         .map_err(|e| e.to_string())?;
 
+    notifications::check_budget_alerts(&app, &state.pool, user.id, &[tx.transaction_date]).await;
+
     Ok(tx)
 }
 
-/// Get monthly summary (income, expenses, balance)
+/// Edit a transaction owned by the current user
 #[tauri::command]
-pub async fn get_monthly_summary(
+pub async fn update_transaction(
     state: State<'_, AppState>,
-    year: i32,
-    month: u32,
-) -> Result<MonthlySummary, String> {
+    transaction_id: String,
+    changes: UpdateTransactionInput,
+) -> Result<Transaction, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&transaction_id)
+        .map_err(|e| format!("Invalid transaction ID: {}", e))?;
+
+    let category_id = changes
+        .category_id
+        .map(|id| {
+            id.map(|id| {
+                uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid category ID: {}", e))
+            })
+            .transpose()
+        })
+        .transpose()?;
+
+    let amount = changes
+        .amount
+        .map(|amount| Decimal::try_from(amount).map_err(|e| format!("Invalid amount: {}", e)))
+        .transpose()?;
+
+    let transaction_date = changes
+        .transaction_date
+        .map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date format: {}", e))
+        })
+        .transpose()?;
+
+    let account_id = changes
+        .account_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid account ID: {}", e)))
+        .transpose()?;
+
+    let service = TransactionService::new(state.pool.clone());
+    service
+        .update_transaction(
+            user.id,
+            id,
+            UpdateTransaction {
+                category_id,
+                amount,
+                store: changes.store,
+                description: changes.description,
+                transaction_date,
+                account_id,
+                currency: changes.currency,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a transaction owned by the current user
+#[tauri::command]
+pub async fn delete_transaction(
+    state: State<'_, AppState>,
+    transaction_id: String,
+) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&transaction_id)
+        .map_err(|e| format!("Invalid transaction ID: {}", e))?;
+
+    let service = TransactionService::new(state.pool.clone());
+    service
+        .delete_transaction(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Move money between two of the current user's accounts
+#[tauri::command]
+pub async fn create_transfer(
+    state: State<'_, AppState>,
+    transfer: TransferInput,
+) -> Result<Transfer, String> {
     let user = state
         .get_user()
         .await
         .ok_or_else(|| "No user logged in".to_string())?;
 
+    let from_account_id = uuid::Uuid::parse_str(&transfer.from_account_id)
+        .map_err(|e| format!("Invalid account ID: {}", e))?;
+    let to_account_id = uuid::Uuid::parse_str(&transfer.to_account_id)
+        .map_err(|e| format!("Invalid account ID: {}", e))?;
+    let transaction_date = NaiveDate::parse_from_str(&transfer.transaction_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format: {}", e))?;
+    let amount =
+        Decimal::try_from(transfer.amount).map_err(|e| format!("Invalid amount: {}", e))?;
+
     let service = TransactionService::new(state.pool.clone());
+    service
+        .create_transfer(CreateTransfer {
+            user_id: user.id,
+            from_account_id,
+            to_account_id,
+            amount,
+            transaction_date,
+            description: transfer.description,
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    let transactions = service
-        .get_user_transactions_by_month(user.id, year, month)
+/// Get both legs of a transfer
+#[tauri::command]
+pub async fn get_transfer(
+    state: State<'_, AppState>,
+    transfer_id: String,
+) -> Result<Transfer, String> {
+    let user = state
+        .get_user()
         .await
-        .map_err(|e| e.to_string())?;
+        .ok_or_else(|| "No user logged in".to_string())?;
 
-    let mut income = Decimal::ZERO;
-    let mut expenses = Decimal::ZERO;
-    let transaction_count = transactions.len() as i64;
+    let id =
+        uuid::Uuid::parse_str(&transfer_id).map_err(|e| format!("Invalid transfer ID: {}", e))?;
 
-    for tx in &transactions {
-        if tx.amount >= Decimal::ZERO {
-            income += tx.amount;
-        } else {
-            expenses += tx.amount.abs();
-        }
-    }
+    let service = TransactionService::new(state.pool.clone());
+    service
+        .get_transfer(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    Ok(MonthlySummary {
-        income: income.to_string().parse().unwrap_or(0.0),
-        expenses: expenses.to_string().parse().unwrap_or(0.0),
-        balance: (income - expenses).to_string().parse().unwrap_or(0.0),
-        transaction_count,
-    })
+/// Delete both legs of a transfer
+#[tauri::command]
+pub async fn delete_transfer(
+    state: State<'_, AppState>,
+    transfer_id: String,
+) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&transfer_id).map_err(|e| format!("Invalid transfer ID: {}", e))?;
+
+    let service = TransactionService::new(state.pool.clone());
+    service
+        .delete_transfer(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the split lines of a transaction, empty when it is not split
+#[tauri::command]
+pub async fn get_transaction_splits(
+    state: State<'_, AppState>,
+    transaction_id: String,
+) -> Result<Vec<TransactionSplit>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&transaction_id)
+        .map_err(|e| format!("Invalid transaction ID: {}", e))?;
+
+    let service = TransactionService::new(state.pool.clone());
+    service
+        .get_splits(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Split a transaction into lines with their own category, an empty list
+/// removes the split
+///
+/// `amount` changes the transaction's amount together with its lines.
+#[tauri::command]
+pub async fn set_transaction_splits(
+    state: State<'_, AppState>,
+    transaction_id: String,
+    amount: Option<f64>,
+    lines: Vec<SplitLineInput>,
+) -> Result<Vec<TransactionSplit>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&transaction_id)
+        .map_err(|e| format!("Invalid transaction ID: {}", e))?;
+    let amount = amount
+        .map(|amount| Decimal::try_from(amount).map_err(|e| format!("Invalid amount: {}", e)))
+        .transpose()?;
+    let lines = lines
+        .into_iter()
+        .map(SplitLineInput::into_line)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let service = TransactionService::new(state.pool.clone());
+    service
+        .set_splits(user.id, id, amount, lines)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get monthly summary (income, expenses, balance)
+#[tauri::command]
+pub async fn get_monthly_summary(
+    state: State<'_, AppState>,
+    year: i32,
+    month: u32,
+) -> Result<MonthlySummary, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let summary = TransactionService::new(state.pool.clone())
+        .get_monthly_summary(user.id, year, month)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(MonthlySummary {
+        income: summary.income.to_string().parse().unwrap_or(0.0),
+        expenses: summary.expenses.to_string().parse().unwrap_or(0.0),
+        balance: (summary.income - summary.expenses)
+            .to_string()
+            .parse()
+            .unwrap_or(0.0),
+        transaction_count: summary.transaction_count,
+        currency: user.base_currency,
+    })
+}
+
+/// Sync card notification emails from Gmail
+///
+/// Only fetches messages added since the last sync unless `full_resync` is set
+/// or the stored checkpoint has expired
+#[tauri::command]
+pub async fn sync_email_transactions(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    full_resync: Option<bool>,
+) -> Result<ImportSummary, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let settings = Settings::load().map_err(|e| e.to_string())?;
+
+    let access_token = auth::get_access_token(&settings)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Gmail access expired, please log in again".to_string())?;
+
+    let client = GmailClient::new(&settings.gmail_api_base_url, &access_token)
+        .map_err(|e| e.to_string())?;
+
+    let importer = EmailImporter::new(state.pool.clone(), settings.email_import_senders);
+    let result = importer
+        .sync(&client, user.id, full_resync.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.imported > 0 {
+        notifications::check_budget_alerts(&app, &state.pool, user.id, &result.months).await;
+    }
+
+    Ok(result)
+}
+
+/// Get the Gmail sync checkpoint for the current user (None if never synced)
+#[tauri::command]
+pub async fn get_sync_status(state: State<'_, AppState>) -> Result<Option<EmailSyncState>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    EmailSyncRepository::find_by_user(&state.pool, user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Import bank notification emails from a local `.mbox` file, `.eml` file
+/// or folder of `.eml` files
+#[tauri::command]
+pub async fn import_email_files(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ImportSummary, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    // Sender list only applies to Gmail searches
+    let importer = EmailImporter::new(state.pool.clone(), Vec::new());
+    let result = importer
+        .import_files(std::path::Path::new(&path), user.id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.imported > 0 {
+        notifications::check_budget_alerts(&app, &state.pool, user.id, &result.months).await;
+    }
+
+    Ok(result)
+}
+
+/// Save (create or replace by name) a CSV column mapping profile
+#[tauri::command]
+pub async fn save_csv_profile(
+    state: State<'_, AppState>,
+    profile: CsvProfileInput,
+) -> Result<CsvImportProfile, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = ImportService::new(state.pool.clone());
+    service
+        .save_csv_profile(CreateCsvImportProfile {
+            user_id: user.id,
+            name: profile.name,
+            delimiter: profile.delimiter,
+            has_header: profile.has_header,
+            date_column: profile.date_column,
+            amount_column: profile.amount_column,
+            debit_column: profile.debit_column,
+            credit_column: profile.credit_column,
+            payee_column: profile.payee_column,
+            description_column: profile.description_column,
+            date_format: profile.date_format,
+            decimal_separator: profile.decimal_separator,
+            encoding: profile.encoding,
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get all saved CSV profiles for the current user
+#[tauri::command]
+pub async fn get_csv_profiles(state: State<'_, AppState>) -> Result<Vec<CsvImportProfile>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = ImportService::new(state.pool.clone());
+    service
+        .get_csv_profiles(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a saved CSV profile
+#[tauri::command]
+pub async fn delete_csv_profile(
+    state: State<'_, AppState>,
+    profile_id: String,
+) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let profile_id =
+        uuid::Uuid::parse_str(&profile_id).map_err(|e| format!("Invalid profile ID: {}", e))?;
+
+    let service = ImportService::new(state.pool.clone());
+    service
+        .delete_csv_profile(profile_id, user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Dry-run a CSV import: parse the file with a profile without saving
+#[tauri::command]
+pub async fn preview_csv_import(
+    state: State<'_, AppState>,
+    path: String,
+    profile_id: String,
+) -> Result<ImportPreview, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let profile_id =
+        uuid::Uuid::parse_str(&profile_id).map_err(|e| format!("Invalid profile ID: {}", e))?;
+
+    let service = ImportService::new(state.pool.clone());
+    service
+        .preview_csv(user.id, std::path::Path::new(&path), profile_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Import a CSV statement with a profile (all rows saved in one transaction)
+#[tauri::command]
+pub async fn import_csv(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    profile_id: String,
+    account_id: Option<String>,
+) -> Result<StatementImportResult, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let profile_id =
+        uuid::Uuid::parse_str(&profile_id).map_err(|e| format!("Invalid profile ID: {}", e))?;
+
+    let account_id = account_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid account ID: {}", e)))
+        .transpose()?;
+
+    let service = ImportService::new(state.pool.clone());
+    let result = service
+        .import_csv(user.id, std::path::Path::new(&path), profile_id, account_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.imported > 0 {
+        notifications::check_budget_alerts(&app, &state.pool, user.id, &result.months).await;
+    }
+
+    Ok(result)
+}
+
+/// Dry-run a statement import (OFX/QFX, camt.052/053, MT940, QIF): parse the
+/// file without saving
+#[tauri::command]
+pub async fn preview_statement_import(
+    state: State<'_, AppState>,
+    path: String,
+    format: StatementFormat,
+) -> Result<ImportPreview, String> {
+    state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = ImportService::new(state.pool.clone());
+    service
+        .preview_statement(std::path::Path::new(&path), format)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Import a statement file (OFX/QFX, camt.052/053, MT940, QIF), skipping
+/// transactions imported before
+#[tauri::command]
+pub async fn import_statement(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    format: StatementFormat,
+    account_id: Option<String>,
+) -> Result<StatementImportResult, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let account_id = account_id
+        .map(|id| uuid::Uuid::parse_str(&id).map_err(|e| format!("Invalid account ID: {}", e)))
+        .transpose()?;
+
+    let service = ImportService::new(state.pool.clone());
+    let result = service
+        .import_statement(user.id, std::path::Path::new(&path), format, account_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.imported > 0 {
+        notifications::check_budget_alerts(&app, &state.pool, user.id, &result.months).await;
+    }
+
+    Ok(result)
+}
+
+/// Export transactions to a QIF file, returns the number of transactions written
+#[tauri::command]
+pub async fn export_qif(
+    state: State<'_, AppState>,
+    path: String,
+    filter: Option<crate::models::TransactionFilter>,
+) -> Result<usize, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = ExportService::new(state.pool.clone());
+    service
+        .export_qif(
+            user.id,
+            std::path::Path::new(&path),
+            &filter.unwrap_or_default(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Export filtered transactions as CSV or XLSX to the chosen path
+#[tauri::command]
+pub async fn export_transactions(
+    state: State<'_, AppState>,
+    path: String,
+    format: ExportFormat,
+    filter: Option<crate::models::TransactionFilter>,
+    locale: Option<String>,
+) -> Result<usize, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = ExportService::new(state.pool.clone());
+    service
+        .export_transactions(
+            user.id,
+            std::path::Path::new(&path),
+            &filter.unwrap_or_default(),
+            format,
+            locale.as_deref(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Export filtered transactions as a Beancount or Ledger journal
+#[tauri::command]
+pub async fn export_journal(
+    state: State<'_, AppState>,
+    path: String,
+    format: JournalFormat,
+    filter: Option<crate::models::TransactionFilter>,
+) -> Result<usize, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = ExportService::new(state.pool.clone());
+    service
+        .export_journal(
+            &user,
+            std::path::Path::new(&path),
+            &filter.unwrap_or_default(),
+            format,
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Write a backup of the current user's data (gzip compressed by default)
+#[tauri::command]
+pub async fn backup_user_data(
+    state: State<'_, AppState>,
+    path: String,
+    compress: Option<bool>,
+) -> Result<BackupSummary, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = BackupService::new(state.pool.clone());
+    service
+        .backup(&user, std::path::Path::new(&path), compress.unwrap_or(true))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a backup archive into the current user's account
+#[tauri::command]
+pub async fn restore_user_data(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<RestoreSummary, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = BackupService::new(state.pool.clone());
+    let summary = service
+        .restore(&user, std::path::Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // The profile may have been restored as well
+    let refreshed = UserService::new(state.pool.clone())
+        .get_user(user.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if refreshed.is_some() {
+        state.set_user(refreshed).await;
+    }
+
+    Ok(summary)
+}
+
+/// Get the current user's categorization rules in evaluation order
+#[tauri::command]
+pub async fn get_categorization_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<CategorizationRule>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = RuleService::new(state.pool.clone());
+    service.get_rules(user.id).await.map_err(|e| e.to_string())
+}
+
+/// Create a categorization rule
+#[tauri::command]
+pub async fn create_categorization_rule(
+    state: State<'_, AppState>,
+    rule: CategorizationRuleInput,
+) -> Result<CategorizationRule, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = RuleService::new(state.pool.clone());
+    service
+        .create_rule(rule.into_create(user.id)?)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace a categorization rule
+#[tauri::command]
+pub async fn update_categorization_rule(
+    state: State<'_, AppState>,
+    rule_id: String,
+    rule: CategorizationRuleInput,
+) -> Result<CategorizationRule, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&rule_id).map_err(|e| format!("Invalid rule ID: {}", e))?;
+
+    let service = RuleService::new(state.pool.clone());
+    service
+        .update_rule(id, rule.into_create(user.id)?)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a categorization rule
+#[tauri::command]
+pub async fn delete_categorization_rule(
+    state: State<'_, AppState>,
+    rule_id: String,
+) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&rule_id).map_err(|e| format!("Invalid rule ID: {}", e))?;
+
+    let service = RuleService::new(state.pool.clone());
+    service
+        .delete_rule(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Show which uncategorized transactions the rules would categorize
+#[tauri::command]
+pub async fn preview_categorization_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<RuleMatch>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = RuleService::new(state.pool.clone());
+    service
+        .preview_rules(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-apply the rules to existing uncategorized transactions
+#[tauri::command]
+pub async fn apply_categorization_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<RuleMatch>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = RuleService::new(state.pool.clone());
+    service
+        .apply_rules(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Suggest up to three categories for a store/description from past choices
+#[tauri::command]
+pub async fn suggest_category(
+    state: State<'_, AppState>,
+    store: Option<String>,
+    description: Option<String>,
+) -> Result<Vec<CategorySuggestion>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = SuggestionService::new(state.pool.clone());
+    service
+        .suggest_category(user.id, store.as_deref(), description.as_deref(), 3)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rebuild category suggestions from the full transaction history
+#[tauri::command]
+pub async fn retrain_category_suggestions(state: State<'_, AppState>) -> Result<usize, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = SuggestionService::new(state.pool.clone());
+    service.retrain(user.id).await.map_err(|e| e.to_string())
+}
+
+/// Get the current user's merchants with their aliases
+#[tauri::command]
+pub async fn get_merchants(state: State<'_, AppState>) -> Result<Vec<MerchantWithAliases>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = MerchantService::new(state.pool.clone());
+    service
+        .get_merchants(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Change a merchant's display name
+#[tauri::command]
+pub async fn rename_merchant(
+    state: State<'_, AppState>,
+    merchant_id: String,
+    name: String,
+) -> Result<Merchant, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&merchant_id).map_err(|e| format!("Invalid merchant ID: {}", e))?;
+
+    let service = MerchantService::new(state.pool.clone());
+    service
+        .rename_merchant(user.id, id, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Add a store name alias to a merchant, returns the number of transactions linked
+#[tauri::command]
+pub async fn add_merchant_alias(
+    state: State<'_, AppState>,
+    merchant_id: String,
+    alias: String,
+) -> Result<u64, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&merchant_id).map_err(|e| format!("Invalid merchant ID: {}", e))?;
+
+    let service = MerchantService::new(state.pool.clone());
+    service
+        .add_alias(user.id, id, &alias)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merge one merchant into another, returns the number of transactions moved
+#[tauri::command]
+pub async fn merge_merchants(
+    state: State<'_, AppState>,
+    source_merchant_id: String,
+    target_merchant_id: String,
+) -> Result<u64, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let source_id = uuid::Uuid::parse_str(&source_merchant_id)
+        .map_err(|e| format!("Invalid merchant ID: {}", e))?;
+    let target_id = uuid::Uuid::parse_str(&target_merchant_id)
+        .map_err(|e| format!("Invalid merchant ID: {}", e))?;
+
+    let service = MerchantService::new(state.pool.clone());
+    service
+        .merge_merchants(user.id, source_id, target_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Per-merchant totals for a month
+#[tauri::command]
+pub async fn get_merchant_spending(
+    state: State<'_, AppState>,
+    year: i32,
+    month: u32,
+) -> Result<Vec<MerchantSpending>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let filter = crate::models::TransactionFilter {
+        year: Some(year),
+        month: Some(month),
+        ..Default::default()
+    };
+    let (Some(start_date), Some(end_date)) = filter.date_range() else {
+        return Err("Invalid year/month".to_string());
+    };
+
+    let service = MerchantService::new(state.pool.clone());
+    service
+        .get_merchant_spending(user.id, start_date, end_date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Link existing transactions to merchants, returns the number linked
+#[tauri::command]
+pub async fn relink_merchants(state: State<'_, AppState>) -> Result<u64, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = MerchantService::new(state.pool.clone());
+    service
+        .relink_transactions(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current user's budgets
+#[tauri::command]
+pub async fn get_budgets(state: State<'_, AppState>) -> Result<Vec<Budget>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = BudgetService::new(state.pool.clone());
+    service
+        .get_budgets(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create or replace the budget of a category
+#[tauri::command]
+pub async fn set_budget(state: State<'_, AppState>, budget: BudgetInput) -> Result<Budget, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let category_id = uuid::Uuid::parse_str(&budget.category_id)
+        .map_err(|e| format!("Invalid category ID: {}", e))?;
+    let amount = Decimal::try_from(budget.amount).map_err(|e| format!("Invalid amount: {}", e))?;
+    let start_month = match budget.start_month {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date format: {}", e))?,
+        None => chrono::Local::now().date_naive(),
+    };
+
+    let service = BudgetService::new(state.pool.clone());
+    service
+        .set_budget(SetBudget {
+            user_id: user.id,
+            category_id,
+            amount,
+            rollover: budget.rollover,
+            start_month,
+            alert_thresholds: budget
+                .alert_thresholds
+                .unwrap_or_else(|| DEFAULT_ALERT_THRESHOLDS.to_vec()),
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a budget
+#[tauri::command]
+pub async fn delete_budget(state: State<'_, AppState>, budget_id: String) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&budget_id).map_err(|e| format!("Invalid budget ID: {}", e))?;
+
+    let service = BudgetService::new(state.pool.clone());
+    service
+        .delete_budget(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Spent, remaining and projected spending per budget for a month
+#[tauri::command]
+pub async fn get_budget_status(
+    state: State<'_, AppState>,
+    year: i32,
+    month: u32,
+) -> Result<Vec<BudgetStatus>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = BudgetService::new(state.pool.clone());
+    service
+        .get_budget_status(user.id, year, month)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current user's recurring transactions with their next occurrences
+#[tauri::command]
+pub async fn get_recurring_transactions(
+    state: State<'_, AppState>,
+) -> Result<Vec<RecurringWithUpcoming>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = RecurringService::new(state.pool.clone());
+    service
+        .get_recurring(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a recurring transaction, occurrences since its start date are posted
+/// on the next run
+#[tauri::command]
+pub async fn create_recurring_transaction(
+    state: State<'_, AppState>,
+    recurring: RecurringTransactionInput,
+) -> Result<RecurringWithUpcoming, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = RecurringService::new(state.pool.clone());
+    service
+        .create_recurring(recurring.into_create(user.id)?)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace a recurring transaction, already posted occurrences are kept
+#[tauri::command]
+pub async fn update_recurring_transaction(
+    state: State<'_, AppState>,
+    recurring_id: String,
+    recurring: RecurringTransactionInput,
+) -> Result<RecurringWithUpcoming, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&recurring_id)
+        .map_err(|e| format!("Invalid recurring transaction ID: {}", e))?;
+
+    let service = RecurringService::new(state.pool.clone());
+    service
+        .update_recurring(id, recurring.into_create(user.id)?)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a recurring transaction, already posted occurrences are kept
+#[tauri::command]
+pub async fn delete_recurring_transaction(
+    state: State<'_, AppState>,
+    recurring_id: String,
+) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&recurring_id)
+        .map_err(|e| format!("Invalid recurring transaction ID: {}", e))?;
+
+    let service = RecurringService::new(state.pool.clone());
+    service
+        .delete_recurring(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Pause or resume a recurring transaction
+#[tauri::command]
+pub async fn set_recurring_paused(
+    state: State<'_, AppState>,
+    recurring_id: String,
+    paused: bool,
+) -> Result<RecurringWithUpcoming, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&recurring_id)
+        .map_err(|e| format!("Invalid recurring transaction ID: {}", e))?;
+
+    let service = RecurringService::new(state.pool.clone());
+    service
+        .set_paused(user.id, id, paused)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Skip an upcoming occurrence (`YYYY-MM-DD`), the next one if no date is given
+#[tauri::command]
+pub async fn skip_recurring_occurrence(
+    state: State<'_, AppState>,
+    recurring_id: String,
+    date: Option<String>,
+) -> Result<RecurringWithUpcoming, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id = uuid::Uuid::parse_str(&recurring_id)
+        .map_err(|e| format!("Invalid recurring transaction ID: {}", e))?;
+    let date = date
+        .map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date format: {}", e))
+        })
+        .transpose()?;
+
+    let service = RecurringService::new(state.pool.clone());
+    service
+        .skip_occurrence(user.id, id, date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Recurring charges found in the current user's history with their total cost
+#[tauri::command]
+pub async fn get_detected_subscriptions(
+    state: State<'_, AppState>,
+) -> Result<SubscriptionReport, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = SubscriptionService::new(state.pool.clone());
+    service
+        .get_detected(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a recurring transaction from a detected subscription
+#[tauri::command]
+pub async fn confirm_subscription(
+    state: State<'_, AppState>,
+    key: String,
+) -> Result<RecurringWithUpcoming, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = SubscriptionService::new(state.pool.clone());
+    service
+        .confirm(user.id, &key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop showing a detected subscription
+#[tauri::command]
+pub async fn dismiss_subscription(state: State<'_, AppState>, key: String) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = SubscriptionService::new(state.pool.clone());
+    service
+        .dismiss(user.id, &key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current user's accounts with their balances
+#[tauri::command]
+pub async fn get_accounts(state: State<'_, AppState>) -> Result<Vec<AccountWithBalance>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = AccountService::new(state.pool.clone());
+    service
+        .get_accounts(user.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create an account for the current user
+#[tauri::command]
+pub async fn create_account(
+    state: State<'_, AppState>,
+    account: AccountInput,
+) -> Result<Account, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = AccountService::new(state.pool.clone());
+    service
+        .create_account(account.into_create(&user)?)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace the name, type, opening balance and currency of an account
+#[tauri::command]
+pub async fn update_account(
+    state: State<'_, AppState>,
+    account_id: String,
+    account: AccountInput,
+) -> Result<Account, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&account_id).map_err(|e| format!("Invalid account ID: {}", e))?;
+
+    let service = AccountService::new(state.pool.clone());
+    service
+        .update_account(id, account.into_create(&user)?)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete an account that has no transactions
+#[tauri::command]
+pub async fn delete_account(state: State<'_, AppState>, account_id: String) -> Result<(), String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&account_id).map_err(|e| format!("Invalid account ID: {}", e))?;
+
+    let service = AccountService::new(state.pool.clone());
+    service
+        .delete_account(user.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// An account's transactions with the running balance after each one,
+/// optionally limited to a year or month
+#[tauri::command]
+pub async fn get_account_register(
+    state: State<'_, AppState>,
+    account_id: String,
+    year: Option<i32>,
+    month: Option<u32>,
+) -> Result<Vec<TransactionWithBalance>, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let id =
+        uuid::Uuid::parse_str(&account_id).map_err(|e| format!("Invalid account ID: {}", e))?;
+    let filter = crate::models::TransactionFilter {
+        year,
+        month,
+        ..Default::default()
+    };
+
+    let service = AccountService::new(state.pool.clone());
+    service
+        .get_register(user.id, id, &filter)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Change the currency the current user's totals are kept in
+#[tauri::command]
+pub async fn set_base_currency(
+    state: State<'_, AppState>,
+    currency: String,
+) -> Result<User, String> {
+    let user = state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = CurrencyService::new(state.pool.clone());
+    let user = service
+        .set_base_currency(user.id, &currency)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    state.set_user(Some(user.clone())).await;
+    Ok(user)
+}
+
+/// Import exchange rates from a downloaded ECB reference rate XML file
+#[tauri::command]
+pub async fn import_ecb_rates(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<RateImportSummary, String> {
+    state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = CurrencyService::new(state.pool.clone());
+    service
+        .import_rates(Box::new(EcbFileProvider::new(path)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Latest exchange rate of every currency pair
+#[tauri::command]
+pub async fn get_fx_rates(state: State<'_, AppState>) -> Result<Vec<FxRate>, String> {
+    state
+        .get_user()
+        .await
+        .ok_or_else(|| "No user logged in".to_string())?;
+
+    let service = CurrencyService::new(state.pool.clone());
+    service.get_latest_rates().await.map_err(|e| e.to_string())
 }
@@ -1,6 +1,16 @@
 use crate::error::{AppError, Result};
 
 const DEFAULT_OAUTH_CALLBACK_PORT: u16 = 8085;
+const DEFAULT_GMAIL_API_BASE_URL: &str = "https://gmail.googleapis.com/gmail/v1";
+
+/// Card and bank notification senders scanned by the email importer
+const DEFAULT_EMAIL_IMPORT_SENDERS: &[&str] = &[
+    "eurobank.gr",
+    "piraeusbank.gr",
+    "alpha.gr",
+    "nbg.gr",
+    "revolut.com",
+];
 
 #[derive(Debug, Clone)]
 pub struct Settings {
@@ -10,6 +20,9 @@ pub struct Settings {
     pub google_client_id: String,
     pub google_client_secret: String,
     pub oauth_callback_port: u16,
+    // Email import settings
+    pub gmail_api_base_url: String,
+    pub email_import_senders: Vec<String>,
 }
 
 impl Settings {
@@ -33,12 +46,34 @@ impl Settings {
             .and_then(|s| s.parse().ok())
             .unwrap_or(DEFAULT_OAUTH_CALLBACK_PORT);
 
+        // Gmail API base URL can be pointed at a local stand-in server
+        let gmail_api_base_url = std::env::var("GMAIL_API_BASE_URL")
+            .unwrap_or_else(|_| DEFAULT_GMAIL_API_BASE_URL.to_string());
+
+        let email_import_senders = std::env::var("EMAIL_IMPORT_SENDERS")
+            .ok()
+            .map(|s| {
+                s.split(',')
+                    .map(|sender| sender.trim().to_string())
+                    .filter(|sender| !sender.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|senders| !senders.is_empty())
+            .unwrap_or_else(|| {
+                DEFAULT_EMAIL_IMPORT_SENDERS
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect()
+            });
+
         Ok(Self {
             database_url,
             log_level,
             google_client_id,
             google_client_secret,
             oauth_callback_port,
+            gmail_api_base_url,
+            email_import_senders,
         })
     }
 
@@ -0,0 +1,218 @@
+use crate::error::Result;
+use crate::models::{
+    Account, AccountWithBalance, CreateAccount, TransactionWithBalance, DEFAULT_ACCOUNT_NAME,
+};
+use chrono::NaiveDate;
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct AccountRepository;
+
+impl AccountRepository {
+    pub async fn create<'e, E>(executor: E, account: CreateAccount) -> Result<Account>
+    where
+        E: PgExecutor<'e>,
+    {
+        let account = sqlx::query_as::<_, Account>(
+            r#"
+            INSERT INTO accounts (user_id, name, account_type, opening_balance, currency)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(account.user_id)
+        .bind(&account.name)
+        .bind(&account.account_type)
+        .bind(account.opening_balance)
+        .bind(&account.currency)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// Create the user's default account, in their base currency, unless
+    /// there is one already
+    pub async fn ensure_default<'e, E>(executor: E, user_id: Uuid) -> Result<()>
+    where
+        E: PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO accounts (user_id, name, account_type, is_default, currency)
+            SELECT $1, $2, 'checking', TRUE, u.base_currency
+            FROM users u
+            WHERE u.id = $1
+              AND NOT EXISTS (SELECT 1 FROM accounts WHERE user_id = $1 AND is_default)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(DEFAULT_ACCOUNT_NAME)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the settings of an account owned by `account.user_id`
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        account: CreateAccount,
+    ) -> Result<Option<Account>> {
+        let account = sqlx::query_as::<_, Account>(
+            r#"
+            UPDATE accounts
+            SET name = $3,
+                account_type = $4,
+                opening_balance = $5,
+                currency = $6
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(account.user_id)
+        .bind(&account.name)
+        .bind(&account.account_type)
+        .bind(account.opening_balance)
+        .bind(&account.currency)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    pub async fn find_by_id<'e, E>(executor: E, id: Uuid) -> Result<Option<Account>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let account = sqlx::query_as::<_, Account>("SELECT * FROM accounts WHERE id = $1")
+            .bind(id)
+            .fetch_optional(executor)
+            .await?;
+
+        Ok(account)
+    }
+
+    /// The account transactions are booked to when none is given
+    pub async fn find_default<'e, E>(executor: E, user_id: Uuid) -> Result<Option<Account>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let account = sqlx::query_as::<_, Account>(
+            "SELECT * FROM accounts WHERE user_id = $1 AND is_default",
+        )
+        .bind(user_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// All of a user's accounts, default account first
+    pub async fn find_by_user<'e, E>(executor: E, user_id: Uuid) -> Result<Vec<Account>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let accounts = sqlx::query_as::<_, Account>(
+            "SELECT * FROM accounts WHERE user_id = $1 ORDER BY is_default DESC, name",
+        )
+        .bind(user_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// All of a user's accounts with their balances, default account first
+    ///
+    /// Balances are in the account's currency; transactions in another
+    /// currency are converted at the rate of their transaction date.
+    pub async fn find_with_balances(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<AccountWithBalance>> {
+        let accounts = sqlx::query_as::<_, AccountWithBalance>(
+            r#"
+            SELECT a.*,
+                   a.opening_balance + COALESCE(SUM(
+                       ROUND(t.original_amount * fx_rate(t.currency, a.currency, t.transaction_date), 2)
+                   ), 0) AS balance,
+                   COUNT(t.id) AS transaction_count
+            FROM accounts a
+            LEFT JOIN transactions t ON t.account_id = a.id
+            WHERE a.user_id = $1
+            GROUP BY a.id
+            ORDER BY a.is_default DESC, a.name
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// An account's transactions in a date range, newest first, each with
+    /// the running balance after it
+    ///
+    /// The balance includes transactions before the range.
+    pub async fn find_register(
+        pool: &PgPool,
+        account_id: Uuid,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<TransactionWithBalance>> {
+        let rows = sqlx::query_as::<_, TransactionWithBalance>(
+            r#"
+            SELECT * FROM (
+                SELECT t.*,
+                       a.opening_balance + SUM(
+                           ROUND(t.original_amount * fx_rate(t.currency, a.currency, t.transaction_date), 2)
+                       ) OVER (
+                           ORDER BY t.transaction_date, t.created_at, t.id
+                       ) AS balance
+                FROM transactions t
+                JOIN accounts a ON t.account_id = a.id
+                WHERE t.account_id = $1
+            ) register
+            WHERE ($2::date IS NULL OR transaction_date >= $2)
+              AND ($3::date IS NULL OR transaction_date <= $3)
+            ORDER BY transaction_date DESC, created_at DESC, id DESC
+            "#,
+        )
+        .bind(account_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Number of transactions booked to an account
+    pub async fn count_transactions(pool: &PgPool, id: Uuid) -> Result<i64> {
+        let count =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM transactions WHERE account_id = $1")
+                .bind(id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(count)
+    }
+
+    /// Delete an account that is not the default, returns whether a row was removed
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM accounts WHERE id = $1 AND user_id = $2 AND NOT is_default")
+                .bind(id)
+                .bind(user_id)
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::Result;
+use crate::models::{Budget, BudgetStatus, SetBudget};
+use chrono::NaiveDate;
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct BudgetRepository;
+
+impl BudgetRepository {
+    /// Create the category's budget or replace its settings
+    pub async fn upsert(pool: &PgPool, budget: SetBudget) -> Result<Budget> {
+        let budget = sqlx::query_as::<_, Budget>(
+            r#"
+            INSERT INTO budgets
+                (user_id, category_id, amount, rollover, start_month, alert_thresholds)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id, category_id) DO UPDATE
+            SET amount = EXCLUDED.amount,
+                rollover = EXCLUDED.rollover,
+                start_month = EXCLUDED.start_month,
+                alert_thresholds = EXCLUDED.alert_thresholds,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(budget.user_id)
+        .bind(budget.category_id)
+        .bind(budget.amount)
+        .bind(budget.rollover)
+        .bind(budget.start_month)
+        .bind(&budget.alert_thresholds)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(budget)
+    }
+
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Budget>> {
+        let budgets = sqlx::query_as::<_, Budget>(
+            r#"
+            SELECT b.* FROM budgets b
+            JOIN categories c ON b.category_id = c.id
+            WHERE b.user_id = $1
+            ORDER BY c.name
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(budgets)
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM budgets WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Move a category's budget (with its alert history) to another category
+    ///
+    /// The target must not have a budget of its own
+    pub async fn reassign_category<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_category_id: Uuid,
+        to_category_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            UPDATE budgets SET category_id = $3, updated_at = NOW()
+            WHERE user_id = $1 AND category_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remember that an alert was sent, false if it already had been
+    pub async fn record_alert(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+        month: NaiveDate,
+        threshold: i32,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO budget_alerts (budget_id, user_id, month, threshold)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .bind(month)
+        .bind(threshold)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Spending against each budget for the month starting at `month_start`
+    ///
+    /// Spending includes sub-categories and counts split transactions by
+    /// their lines. With rollover, the unused part of every earlier month
+    /// since `start_month` is added to the month's amount (overspending is
+    /// not carried). The projection extrapolates spending so far linearly to
+    /// the end of the month, using `today` as the current day; past and
+    /// future months are projected as spent.
+    pub async fn get_status(
+        pool: &PgPool,
+        user_id: Uuid,
+        month_start: NaiveDate,
+        month_end: NaiveDate,
+        today: NaiveDate,
+    ) -> Result<Vec<BudgetStatus>> {
+        let rows = sqlx::query_as::<_, BudgetStatus>(
+            r#"
+            WITH RECURSIVE budget_categories AS (
+                SELECT b.id AS budget_id, b.category_id
+                FROM budgets b
+                WHERE b.user_id = $1 AND b.start_month <= $2
+                UNION ALL
+                SELECT bc.budget_id, c.id
+                FROM categories c
+                JOIN budget_categories bc ON c.parent_id = bc.category_id
+            ),
+            monthly AS (
+                SELECT bc.budget_id,
+                       date_trunc('month', t.transaction_date)::date AS month,
+                       -SUM(t.amount) AS spent
+                FROM budget_categories bc
+                JOIN budgets b ON b.id = bc.budget_id
+                JOIN category_amounts t ON t.category_id = bc.category_id
+                WHERE t.user_id = $1
+                  AND t.transfer_id IS NULL
+                  AND t.transaction_date >= b.start_month
+                  AND t.transaction_date <= $3
+                GROUP BY bc.budget_id, month
+            ),
+            carried AS (
+                SELECT b.id AS budget_id,
+                       SUM(GREATEST(b.amount - COALESCE(m.spent, 0), 0)) AS carried_over
+                FROM budgets b
+                CROSS JOIN LATERAL generate_series(
+                    b.start_month::timestamp,
+                    ($2::date - INTERVAL '1 month')::timestamp,
+                    INTERVAL '1 month'
+                ) AS s(month)
+                LEFT JOIN monthly m ON m.budget_id = b.id AND m.month = s.month::date
+                WHERE b.user_id = $1 AND b.rollover
+                GROUP BY b.id
+            ),
+            status AS (
+                SELECT b.id AS budget_id,
+                       b.category_id,
+                       c.name AS category_name,
+                       b.amount,
+                       b.rollover,
+                       COALESCE(cr.carried_over, 0) AS carried_over,
+                       b.amount + COALESCE(cr.carried_over, 0) AS available,
+                       COALESCE(m.spent, 0) AS spent
+                FROM budgets b
+                JOIN categories c ON b.category_id = c.id
+                LEFT JOIN carried cr ON cr.budget_id = b.id
+                LEFT JOIN monthly m ON m.budget_id = b.id AND m.month = $2
+                WHERE b.user_id = $1 AND b.start_month <= $2
+            ),
+            projected AS (
+                SELECT status.*,
+                       CASE
+                           WHEN $4::date < $2::date OR $4::date >= $3::date THEN spent
+                           ELSE ROUND(spent * ($3::date - $2::date + 1) / ($4::date - $2::date + 1), 2)
+                       END AS projected_spent
+                FROM status
+            )
+            SELECT budget_id,
+                   category_id,
+                   category_name,
+                   amount,
+                   rollover,
+                   carried_over,
+                   available,
+                   spent,
+                   available - spent AS remaining,
+                   CASE WHEN available > 0 THEN ROUND(spent * 100 / available, 1) END AS percent_used,
+                   projected_spent,
+                   spent > available AS over_budget,
+                   spent <= available AND projected_spent > available AS projected_over_budget
+            FROM projected
+            ORDER BY category_name
+            "#,
+        )
+        .bind(user_id)
+        .bind(month_start)
+        .bind(month_end)
+        .bind(today)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
@@ -1,16 +1,21 @@
 use crate::error::Result;
-use crate::models::{Category, CreateCategory};
+use crate::models::{Category, CategoryNode, CreateCategory, UpdateCategory};
+use sqlx::postgres::PgExecutor;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 pub struct CategoryRepository;
 
 impl CategoryRepository {
-    pub async fn create(pool: &PgPool, category: CreateCategory) -> Result<Category> {
+    /// Insert a category (accepts a pool or an open database transaction)
+    pub async fn create<'e, E>(executor: E, category: CreateCategory) -> Result<Category>
+    where
+        E: PgExecutor<'e>,
+    {
         let category = sqlx::query_as::<_, Category>(
             r#"
-            INSERT INTO categories (user_id, name, icon, is_income)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO categories (user_id, name, icon, is_income, parent_id)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING *
             "#,
         )
@@ -18,27 +23,34 @@ impl CategoryRepository {
         .bind(&category.name)
         .bind(&category.icon)
         .bind(category.is_income)
-        .fetch_one(pool)
+        .bind(category.parent_id)
+        .fetch_one(executor)
         .await?;
 
         Ok(category)
     }
 
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Category>> {
+    pub async fn find_by_id<'e, E>(executor: E, id: Uuid) -> Result<Option<Category>>
+    where
+        E: PgExecutor<'e>,
+    {
         let category = sqlx::query_as::<_, Category>("SELECT * FROM categories WHERE id = $1")
             .bind(id)
-            .fetch_optional(pool)
+            .fetch_optional(executor)
             .await?;
 
         Ok(category)
     }
 
-    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Category>> {
+    pub async fn find_by_user<'e, E>(executor: E, user_id: Uuid) -> Result<Vec<Category>>
+    where
+        E: PgExecutor<'e>,
+    {
         let categories = sqlx::query_as::<_, Category>(
             "SELECT * FROM categories WHERE user_id = $1 ORDER BY name",
         )
         .bind(user_id)
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(categories)
@@ -66,12 +78,113 @@ impl CategoryRepository {
         Ok(categories)
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<()> {
-        sqlx::query("DELETE FROM categories WHERE id = $1")
+    /// Apply changes to a category owned by `user_id`
+    pub async fn update<'e, E>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        changes: &UpdateCategory,
+    ) -> Result<Option<Category>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let category = sqlx::query_as::<_, Category>(
+            r#"
+            UPDATE categories
+            SET name = COALESCE($3, name),
+                icon = CASE WHEN $4 THEN $5 ELSE icon END,
+                is_income = COALESCE($6, is_income),
+                parent_id = CASE WHEN $7 THEN $8 ELSE parent_id END
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(&changes.name)
+        .bind(changes.icon.is_some())
+        .bind(changes.icon.clone().flatten())
+        .bind(changes.is_income)
+        .bind(changes.parent_id.is_some())
+        .bind(changes.parent_id.flatten())
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(category)
+    }
+
+    /// Set `is_income` on all sub-categories (at any depth) of a category
+    pub async fn set_income_for_descendants<'e, E>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        is_income: bool,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            WITH RECURSIVE descendants AS (
+                SELECT id FROM categories WHERE parent_id = $1 AND user_id = $2
+                UNION
+                SELECT c.id FROM categories c
+                JOIN descendants d ON c.parent_id = d.id
+            )
+            UPDATE categories SET is_income = $3
+            WHERE id IN (SELECT id FROM descendants)
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(is_income)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Move the direct sub-categories of one category under another
+    pub async fn reparent_children<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_parent_id: Uuid,
+        to_parent_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            "UPDATE categories SET parent_id = $3 WHERE user_id = $1 AND parent_id = $2",
+        )
+        .bind(user_id)
+        .bind(from_parent_id)
+        .bind(to_parent_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// All of a user's categories as trees of sub-categories
+    pub async fn find_tree_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<CategoryNode>> {
+        let categories = Self::find_by_user(pool, user_id).await?;
+        Ok(CategoryNode::build_tree(categories))
+    }
+
+    /// Delete a category owned by `user_id`, returns whether a row was removed
+    ///
+    /// Transactions in the category become uncategorized (`ON DELETE SET NULL`)
+    pub async fn delete<'e, E>(executor: E, id: Uuid, user_id: Uuid) -> Result<bool>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query("DELETE FROM categories WHERE id = $1 AND user_id = $2")
             .bind(id)
-            .execute(pool)
+            .bind(user_id)
+            .execute(executor)
             .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 }
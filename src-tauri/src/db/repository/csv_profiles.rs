@@ -0,0 +1,90 @@
+use crate::error::Result;
+use crate::models::{CreateCsvImportProfile, CsvImportProfile};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct CsvProfileRepository;
+
+impl CsvProfileRepository {
+    /// Create a profile, replacing an existing one with the same name
+    pub async fn upsert(
+        pool: &PgPool,
+        profile: CreateCsvImportProfile,
+    ) -> Result<CsvImportProfile> {
+        let profile = sqlx::query_as::<_, CsvImportProfile>(
+            r#"
+            INSERT INTO csv_import_profiles
+                (user_id, name, delimiter, has_header, date_column, amount_column, debit_column,
+                 credit_column, payee_column, description_column, date_format, decimal_separator, encoding)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            ON CONFLICT (user_id, name) DO UPDATE
+            SET delimiter = EXCLUDED.delimiter,
+                has_header = EXCLUDED.has_header,
+                date_column = EXCLUDED.date_column,
+                amount_column = EXCLUDED.amount_column,
+                debit_column = EXCLUDED.debit_column,
+                credit_column = EXCLUDED.credit_column,
+                payee_column = EXCLUDED.payee_column,
+                description_column = EXCLUDED.description_column,
+                date_format = EXCLUDED.date_format,
+                decimal_separator = EXCLUDED.decimal_separator,
+                encoding = EXCLUDED.encoding
+            RETURNING *
+            "#,
+        )
+        .bind(profile.user_id)
+        .bind(&profile.name)
+        .bind(&profile.delimiter)
+        .bind(profile.has_header)
+        .bind(&profile.date_column)
+        .bind(&profile.amount_column)
+        .bind(&profile.debit_column)
+        .bind(&profile.credit_column)
+        .bind(&profile.payee_column)
+        .bind(&profile.description_column)
+        .bind(&profile.date_format)
+        .bind(&profile.decimal_separator)
+        .bind(&profile.encoding)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(profile)
+    }
+
+    pub async fn find_by_id(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<CsvImportProfile>> {
+        let profile = sqlx::query_as::<_, CsvImportProfile>(
+            "SELECT * FROM csv_import_profiles WHERE id = $1 AND user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(profile)
+    }
+
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<CsvImportProfile>> {
+        let profiles = sqlx::query_as::<_, CsvImportProfile>(
+            "SELECT * FROM csv_import_profiles WHERE user_id = $1 ORDER BY name",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(profiles)
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM csv_import_profiles WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
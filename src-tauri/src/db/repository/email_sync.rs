@@ -0,0 +1,61 @@
+use crate::error::Result;
+use crate::models::EmailSyncState;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct EmailSyncRepository;
+
+impl EmailSyncRepository {
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Option<EmailSyncState>> {
+        let state = sqlx::query_as::<_, EmailSyncState>(
+            "SELECT * FROM email_sync_state WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(state)
+    }
+
+    /// Store a new checkpoint, marking it as a full sync if requested
+    ///
+    /// `failed_message_ids` replaces the messages left to retry.
+    pub async fn upsert(
+        pool: &PgPool,
+        user_id: Uuid,
+        history_id: &str,
+        full_sync: bool,
+        failed_message_ids: &[String],
+    ) -> Result<EmailSyncState> {
+        let state = sqlx::query_as::<_, EmailSyncState>(
+            r#"
+            INSERT INTO email_sync_state
+                (user_id, history_id, last_sync_at, last_full_sync_at, failed_message_ids)
+            VALUES ($1, $2, NOW(), CASE WHEN $3 THEN NOW() END, $4)
+            ON CONFLICT (user_id) DO UPDATE
+            SET history_id = EXCLUDED.history_id,
+                last_sync_at = EXCLUDED.last_sync_at,
+                last_full_sync_at = COALESCE(EXCLUDED.last_full_sync_at, email_sync_state.last_full_sync_at),
+                failed_message_ids = EXCLUDED.failed_message_ids
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(history_id)
+        .bind(full_sync)
+        .bind(failed_message_ids)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(state)
+    }
+
+    pub async fn delete(pool: &PgPool, user_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM email_sync_state WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::error::Result;
+use crate::models::FxRate;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct FxRateRepository;
+
+impl FxRateRepository {
+    /// Insert rates, replacing those for the same pair and date
+    pub async fn upsert_many<'e, E>(executor: E, rates: &[FxRate]) -> Result<()>
+    where
+        E: PgExecutor<'e>,
+    {
+        let bases: Vec<&str> = rates.iter().map(|r| r.base.as_str()).collect();
+        let quotes: Vec<&str> = rates.iter().map(|r| r.quote.as_str()).collect();
+        let dates: Vec<NaiveDate> = rates.iter().map(|r| r.rate_date).collect();
+        let values: Vec<Decimal> = rates.iter().map(|r| r.rate).collect();
+        let sources: Vec<&str> = rates.iter().map(|r| r.source.as_str()).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO fx_rates (base, quote, rate_date, rate, source)
+            SELECT *
+            FROM UNNEST($1::text[], $2::text[], $3::date[], $4::numeric[], $5::text[])
+            ON CONFLICT (base, quote, rate_date) DO UPDATE
+            SET rate = EXCLUDED.rate,
+                source = EXCLUDED.source,
+                created_at = NOW()
+            "#,
+        )
+        .bind(&bases)
+        .bind(&quotes)
+        .bind(&dates)
+        .bind(&values)
+        .bind(&sources)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Latest rate of every currency pair
+    pub async fn find_latest(pool: &PgPool) -> Result<Vec<FxRate>> {
+        let rates = sqlx::query_as::<_, FxRate>(
+            r#"
+            SELECT DISTINCT ON (base, quote) *
+            FROM fx_rates
+            ORDER BY base, quote, rate_date DESC
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rates)
+    }
+
+    /// Units of `to` per unit of `from` on a date, see the `fx_rate` SQL function
+    pub async fn find_rate<'e, E>(
+        executor: E,
+        from: &str,
+        to: &str,
+        date: NaiveDate,
+    ) -> Result<Option<Decimal>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let rate = sqlx::query_scalar::<_, Option<Decimal>>("SELECT fx_rate($1, $2, $3)")
+            .bind(from)
+            .bind(to)
+            .bind(date)
+            .fetch_one(executor)
+            .await?;
+
+        Ok(rate)
+    }
+
+    /// Currency an amount would be booked in (`currency`, else the account's;
+    /// the default account when `account_id` is `None`), the user's base
+    /// currency and the rate between them on a date
+    ///
+    /// `None` when the account does not exist.
+    pub async fn find_booking_rate<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        account_id: Option<Uuid>,
+        currency: Option<&str>,
+        date: NaiveDate,
+    ) -> Result<Option<(String, String, Option<Decimal>)>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let rate = sqlx::query_as::<_, (String, String, Option<Decimal>)>(
+            r#"
+            SELECT cur.currency, u.base_currency::text,
+                   fx_rate(cur.currency, u.base_currency, $4)
+            FROM accounts a
+            JOIN users u ON u.id = a.user_id
+            CROSS JOIN LATERAL (SELECT COALESCE($3, a.currency::text) AS currency) cur
+            WHERE a.user_id = $1
+              AND a.id = COALESCE($2, (SELECT id FROM accounts WHERE user_id = $1 AND is_default))
+            "#,
+        )
+        .bind(user_id)
+        .bind(account_id)
+        .bind(currency)
+        .bind(date)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(rate)
+    }
+
+    /// Currencies of a user's transactions that no rate converts to their
+    /// base currency on the transaction date
+    pub async fn find_unconvertible_currencies<'e, E>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<Vec<String>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let currencies = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT p.currency
+            FROM (
+                SELECT DISTINCT tx.currency::text AS currency, tx.transaction_date,
+                       u.base_currency::text AS base_currency
+                FROM transactions tx
+                JOIN users u ON u.id = tx.user_id
+                WHERE tx.user_id = $1
+            ) p
+            WHERE fx_rate(p.currency, p.base_currency, p.transaction_date) IS NULL
+            ORDER BY p.currency
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(currencies)
+    }
+
+    /// Convert amounts again with the current rates and base currency, for
+    /// one user or everyone; returns the number of transactions changed
+    ///
+    /// Transactions no rate converts are left as they are
+    pub async fn reconvert_transactions<'e, E>(executor: E, user_id: Option<Uuid>) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            UPDATE transactions t
+            SET fx_rate = c.rate,
+                amount = ROUND(t.original_amount * c.rate, 2)
+            FROM (
+                SELECT tx.id, fx_rate(tx.currency, u.base_currency, tx.transaction_date) AS rate
+                FROM transactions tx
+                JOIN users u ON u.id = tx.user_id
+                WHERE $1::uuid IS NULL OR tx.user_id = $1
+            ) c
+            WHERE t.id = c.id AND c.rate IS NOT NULL AND t.fx_rate IS DISTINCT FROM c.rate
+            "#,
+        )
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::categorize::text;
+use crate::error::Result;
+use crate::models::{Merchant, MerchantAlias};
+use sqlx::postgres::PgExecutor;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+pub struct MerchantRepository;
+
+impl MerchantRepository {
+    /// Insert a merchant, returning the existing one if the key is taken
+    pub async fn create<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        name: &str,
+        normalized_name: &str,
+    ) -> Result<Merchant>
+    where
+        E: PgExecutor<'e>,
+    {
+        let merchant = sqlx::query_as::<_, Merchant>(
+            r#"
+            INSERT INTO merchants (user_id, name, normalized_name)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, normalized_name) DO UPDATE SET name = merchants.name
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(normalized_name)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(merchant)
+    }
+
+    /// Find a merchant by normalized name or alias
+    pub async fn find_by_key<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        key: &str,
+    ) -> Result<Option<Merchant>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let merchant = sqlx::query_as::<_, Merchant>(
+            r#"
+            SELECT m.* FROM merchants m
+            WHERE m.user_id = $1 AND m.normalized_name = $2
+            UNION ALL
+            SELECT m.* FROM merchants m
+            JOIN merchant_aliases a ON a.merchant_id = m.id
+            WHERE a.user_id = $1 AND a.normalized_alias = $2
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(merchant)
+    }
+
+    /// Merchant for a store name, created on first sight
+    pub async fn find_or_create(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        store: &str,
+    ) -> Result<Option<Merchant>> {
+        let key = text::merchant_key(store);
+        if key.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some(merchant) = Self::find_by_key(&mut *conn, user_id, &key).await? {
+            return Ok(Some(merchant));
+        }
+
+        let name = text::merchant_display_name(store);
+        Self::create(&mut *conn, user_id, &name, &key)
+            .await
+            .map(Some)
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Merchant>> {
+        let merchant = sqlx::query_as::<_, Merchant>("SELECT * FROM merchants WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(merchant)
+    }
+
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Merchant>> {
+        let merchants = sqlx::query_as::<_, Merchant>(
+            "SELECT * FROM merchants WHERE user_id = $1 ORDER BY name",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(merchants)
+    }
+
+    pub async fn find_aliases_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<MerchantAlias>> {
+        let aliases = sqlx::query_as::<_, MerchantAlias>(
+            "SELECT * FROM merchant_aliases WHERE user_id = $1 ORDER BY alias",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(aliases)
+    }
+
+    pub async fn rename(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Merchant>> {
+        let merchant = sqlx::query_as::<_, Merchant>(
+            "UPDATE merchants SET name = $3 WHERE id = $1 AND user_id = $2 RETURNING *",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(merchant)
+    }
+
+    /// Add an alias, moving it if it already points to another merchant
+    pub async fn add_alias<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        merchant_id: Uuid,
+        alias: &str,
+        normalized_alias: &str,
+    ) -> Result<MerchantAlias>
+    where
+        E: PgExecutor<'e>,
+    {
+        let alias = sqlx::query_as::<_, MerchantAlias>(
+            r#"
+            INSERT INTO merchant_aliases (user_id, merchant_id, alias, normalized_alias)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, normalized_alias)
+            DO UPDATE SET merchant_id = EXCLUDED.merchant_id, alias = EXCLUDED.alias
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(merchant_id)
+        .bind(alias)
+        .bind(normalized_alias)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(alias)
+    }
+
+    pub async fn move_aliases<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_merchant_id: Uuid,
+        to_merchant_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            "UPDATE merchant_aliases SET merchant_id = $3 WHERE user_id = $1 AND merchant_id = $2",
+        )
+        .bind(user_id)
+        .bind(from_merchant_id)
+        .bind(to_merchant_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete<'e, E>(executor: E, id: Uuid, user_id: Uuid) -> Result<bool>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query("DELETE FROM merchants WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(executor)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,7 +1,29 @@
 mod users;
 mod categories;
 mod transactions;
+mod email_sync;
+mod csv_profiles;
+mod rules;
+mod training;
+mod merchants;
+mod budgets;
+mod recurring;
+mod subscriptions;
+mod accounts;
+mod fx_rates;
+mod splits;
 
 pub use users::UserRepository;
 pub use categories::CategoryRepository;
 pub use transactions::{TransactionRepository, MonthlySummary};
+pub use email_sync::EmailSyncRepository;
+pub use csv_profiles::CsvProfileRepository;
+pub use rules::RuleRepository;
+pub use training::TrainingRepository;
+pub use merchants::MerchantRepository;
+pub use budgets::BudgetRepository;
+pub use recurring::RecurringRepository;
+pub use subscriptions::SubscriptionRepository;
+pub use accounts::AccountRepository;
+pub use fx_rates::FxRateRepository;
+pub use splits::TransactionSplitRepository;
@@ -0,0 +1,240 @@
+use crate::error::Result;
+use crate::models::{CreateRecurringTransaction, RecurringTransaction};
+use chrono::NaiveDate;
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct RecurringRepository;
+
+impl RecurringRepository {
+    pub async fn create<'e, E>(
+        executor: E,
+        recurring: CreateRecurringTransaction,
+        next_date: NaiveDate,
+    ) -> Result<RecurringTransaction>
+    where
+        E: PgExecutor<'e>,
+    {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            INSERT INTO recurring_transactions
+                (user_id, category_id, amount, store, description, frequency, day_of_month,
+                 start_date, end_date, next_date, account_id, currency, reminder_only)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING *
+            "#,
+        )
+        .bind(recurring.user_id)
+        .bind(recurring.category_id)
+        .bind(recurring.amount)
+        .bind(&recurring.store)
+        .bind(&recurring.description)
+        .bind(&recurring.frequency)
+        .bind(recurring.day_of_month)
+        .bind(recurring.start_date)
+        .bind(recurring.end_date)
+        .bind(next_date)
+        .bind(recurring.account_id)
+        .bind(&recurring.currency)
+        .bind(recurring.reminder_only)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    /// Replace all fields of an entry owned by `recurring.user_id`
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        recurring: CreateRecurringTransaction,
+        next_date: NaiveDate,
+    ) -> Result<Option<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            UPDATE recurring_transactions
+            SET category_id = $3,
+                amount = $4,
+                store = $5,
+                description = $6,
+                frequency = $7,
+                day_of_month = $8,
+                start_date = $9,
+                end_date = $10,
+                next_date = $11,
+                account_id = $12,
+                currency = $13,
+                reminder_only = $14,
+                updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(recurring.user_id)
+        .bind(recurring.category_id)
+        .bind(recurring.amount)
+        .bind(&recurring.store)
+        .bind(&recurring.description)
+        .bind(&recurring.frequency)
+        .bind(recurring.day_of_month)
+        .bind(recurring.start_date)
+        .bind(recurring.end_date)
+        .bind(next_date)
+        .bind(recurring.account_id)
+        .bind(&recurring.currency)
+        .bind(recurring.reminder_only)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            "SELECT * FROM recurring_transactions WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    /// All of a user's entries, next due first
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            SELECT * FROM recurring_transactions
+            WHERE user_id = $1
+            ORDER BY is_paused, next_date, created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    /// Active entries of every user with an occurrence due on or before `today`
+    pub async fn find_due(pool: &PgPool, today: NaiveDate) -> Result<Vec<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            SELECT * FROM recurring_transactions
+            WHERE NOT is_paused
+              AND next_date <= $1
+              AND (end_date IS NULL OR next_date <= end_date)
+            ORDER BY user_id, next_date
+            "#,
+        )
+        .bind(today)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    pub async fn set_next_date<'e, E>(executor: E, id: Uuid, next_date: NaiveDate) -> Result<()>
+    where
+        E: PgExecutor<'e>,
+    {
+        sqlx::query("UPDATE recurring_transactions SET next_date = $2 WHERE id = $1")
+            .bind(id)
+            .bind(next_date)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_paused(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        is_paused: bool,
+        next_date: NaiveDate,
+    ) -> Result<Option<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            UPDATE recurring_transactions
+            SET is_paused = $3, next_date = $4, updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(is_paused)
+        .bind(next_date)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    /// Mark an occurrence as not to be posted
+    pub async fn add_skipped_date(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            UPDATE recurring_transactions
+            SET skipped_dates = CASE
+                    WHEN $3 = ANY(skipped_dates) THEN skipped_dates
+                    ELSE array_append(skipped_dates, $3)
+                END,
+                updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(date)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    /// Move all of a user's entries from one category to another
+    pub async fn reassign_category<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_category_id: Uuid,
+        to_category_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            UPDATE recurring_transactions SET category_id = $3, updated_at = NOW()
+            WHERE user_id = $1 AND category_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM recurring_transactions WHERE id = $1 AND user_id = $2")
+                .bind(id)
+                .bind(user_id)
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,128 @@
+use crate::error::Result;
+use crate::models::{CategorizationRule, CreateCategorizationRule};
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct RuleRepository;
+
+impl RuleRepository {
+    pub async fn create(
+        pool: &PgPool,
+        rule: CreateCategorizationRule,
+    ) -> Result<CategorizationRule> {
+        let rule = sqlx::query_as::<_, CategorizationRule>(
+            r#"
+            INSERT INTO categorization_rules
+                (user_id, category_id, name, match_field, match_type, pattern, min_amount,
+                 max_amount, source, priority, is_active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(rule.user_id)
+        .bind(rule.category_id)
+        .bind(&rule.name)
+        .bind(&rule.match_field)
+        .bind(&rule.match_type)
+        .bind(&rule.pattern)
+        .bind(rule.min_amount)
+        .bind(rule.max_amount)
+        .bind(&rule.source)
+        .bind(rule.priority)
+        .bind(rule.is_active)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// Replace all fields of a rule owned by `rule.user_id`
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        rule: CreateCategorizationRule,
+    ) -> Result<Option<CategorizationRule>> {
+        let rule = sqlx::query_as::<_, CategorizationRule>(
+            r#"
+            UPDATE categorization_rules
+            SET category_id = $3,
+                name = $4,
+                match_field = $5,
+                match_type = $6,
+                pattern = $7,
+                min_amount = $8,
+                max_amount = $9,
+                source = $10,
+                priority = $11,
+                is_active = $12
+            WHERE id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(rule.user_id)
+        .bind(rule.category_id)
+        .bind(&rule.name)
+        .bind(&rule.match_field)
+        .bind(&rule.match_type)
+        .bind(&rule.pattern)
+        .bind(rule.min_amount)
+        .bind(rule.max_amount)
+        .bind(&rule.source)
+        .bind(rule.priority)
+        .bind(rule.is_active)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// All of a user's rules in evaluation order
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<CategorizationRule>> {
+        let rules = sqlx::query_as::<_, CategorizationRule>(
+            r#"
+            SELECT * FROM categorization_rules
+            WHERE user_id = $1
+            ORDER BY priority, created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM categorization_rules WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Point all of a user's rules for one category at another
+    pub async fn reassign_category<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_category_id: Uuid,
+        to_category_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            "UPDATE categorization_rules SET category_id = $3 WHERE user_id = $1 AND category_id = $2",
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
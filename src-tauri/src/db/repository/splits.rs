@@ -0,0 +1,112 @@
+use crate::error::Result;
+use crate::models::{SplitLine, TransactionSplit};
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct TransactionSplitRepository;
+
+impl TransactionSplitRepository {
+    pub async fn create<'e, E>(
+        executor: E,
+        transaction_id: Uuid,
+        position: i16,
+        line: &SplitLine,
+    ) -> Result<TransactionSplit>
+    where
+        E: PgExecutor<'e>,
+    {
+        let split = sqlx::query_as::<_, TransactionSplit>(
+            r#"
+            INSERT INTO transaction_splits (transaction_id, category_id, amount, note, position)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(line.category_id)
+        .bind(line.amount)
+        .bind(&line.note)
+        .bind(position)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(split)
+    }
+
+    /// A transaction's lines in the order they were entered
+    pub async fn find_by_transaction<'e, E>(
+        executor: E,
+        transaction_id: Uuid,
+    ) -> Result<Vec<TransactionSplit>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let splits = sqlx::query_as::<_, TransactionSplit>(
+            "SELECT * FROM transaction_splits WHERE transaction_id = $1 ORDER BY position",
+        )
+        .bind(transaction_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(splits)
+    }
+
+    /// All lines of a user's transactions (for backups)
+    pub async fn find_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<TransactionSplit>> {
+        let splits = sqlx::query_as::<_, TransactionSplit>(
+            r#"
+            SELECT s.*
+            FROM transaction_splits s
+            JOIN transactions t ON t.id = s.transaction_id
+            WHERE t.user_id = $1
+            ORDER BY s.transaction_id, s.position
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(splits)
+    }
+
+    /// Remove all lines of a transaction, returns the number removed
+    pub async fn delete_by_transaction<'e, E>(executor: E, transaction_id: Uuid) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query("DELETE FROM transaction_splits WHERE transaction_id = $1")
+            .bind(transaction_id)
+            .execute(executor)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Move all of a user's split lines from one category to another
+    pub async fn reassign_category<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_category_id: Uuid,
+        to_category_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            UPDATE transaction_splits s
+            SET category_id = $3
+            FROM transactions t
+            WHERE t.id = s.transaction_id AND t.user_id = $1 AND s.category_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::error::Result;
+use sqlx::postgres::PgExecutor;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct SubscriptionRepository;
+
+impl SubscriptionRepository {
+    /// Keys of detected subscriptions the user already confirmed or dismissed
+    pub async fn find_decided_keys(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>> {
+        let keys = sqlx::query_scalar::<_, String>(
+            "SELECT subscription_key FROM subscription_decisions WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(keys)
+    }
+
+    /// Record a confirmation (with the recurring transaction created) or dismissal
+    pub async fn record_decision<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        key: &str,
+        decision: &str,
+        recurring_id: Option<Uuid>,
+    ) -> Result<()>
+    where
+        E: PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO subscription_decisions (user_id, subscription_key, decision, recurring_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, subscription_key) DO UPDATE
+            SET decision = EXCLUDED.decision,
+                recurring_id = EXCLUDED.recurring_id,
+                created_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(decision)
+        .bind(recurring_id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
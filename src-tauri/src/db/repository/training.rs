@@ -0,0 +1,199 @@
+use crate::categorize::learning::{NaiveBayes, TrainingDelta};
+use crate::error::Result;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct TrainingRepository;
+
+impl TrainingRepository {
+    /// Add (or subtract) counts, dropping rows that reach zero
+    pub async fn apply(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        delta: &TrainingDelta,
+    ) -> Result<()> {
+        if delta.is_empty() {
+            return Ok(());
+        }
+
+        let (categories, tokens, counts): (Vec<Uuid>, Vec<String>, Vec<i64>) = delta
+            .tokens
+            .iter()
+            .filter(|(_, count)| **count != 0)
+            .map(|((category_id, token), count)| (*category_id, token.clone(), *count))
+            .fold(Default::default(), |mut acc, (c, t, n)| {
+                acc.0.push(c);
+                acc.1.push(t);
+                acc.2.push(n);
+                acc
+            });
+
+        sqlx::query(
+            r#"
+            INSERT INTO category_token_counts (user_id, category_id, token, count)
+            SELECT $1, category_id, token, count
+            FROM UNNEST($2::uuid[], $3::text[], $4::bigint[]) AS t(category_id, token, count)
+            WHERE EXISTS (SELECT 1 FROM categories c WHERE c.id = t.category_id)
+            ON CONFLICT (user_id, category_id, token)
+            DO UPDATE SET count = category_token_counts.count + EXCLUDED.count
+            "#,
+        )
+        .bind(user_id)
+        .bind(&categories)
+        .bind(&tokens)
+        .bind(&counts)
+        .execute(&mut *conn)
+        .await?;
+
+        let (categories, documents, token_totals): (Vec<Uuid>, Vec<i64>, Vec<i64>) = delta
+            .totals
+            .iter()
+            .map(|(category_id, (documents, tokens))| (*category_id, *documents, *tokens))
+            .fold(Default::default(), |mut acc, (c, d, t)| {
+                acc.0.push(c);
+                acc.1.push(d);
+                acc.2.push(t);
+                acc
+            });
+
+        sqlx::query(
+            r#"
+            INSERT INTO category_training_totals (user_id, category_id, documents, tokens)
+            SELECT $1, category_id, documents, tokens
+            FROM UNNEST($2::uuid[], $3::bigint[], $4::bigint[]) AS t(category_id, documents, tokens)
+            WHERE EXISTS (SELECT 1 FROM categories c WHERE c.id = t.category_id)
+            ON CONFLICT (user_id, category_id)
+            DO UPDATE SET documents = category_training_totals.documents + EXCLUDED.documents,
+                          tokens = category_training_totals.tokens + EXCLUDED.tokens
+            "#,
+        )
+        .bind(user_id)
+        .bind(&categories)
+        .bind(&documents)
+        .bind(&token_totals)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query("DELETE FROM category_token_counts WHERE user_id = $1 AND count <= 0")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("DELETE FROM category_training_totals WHERE user_id = $1 AND documents <= 0")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Add what was learned for one category to another and forget the first
+    pub async fn merge_category(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        from_category_id: Uuid,
+        to_category_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO category_token_counts (user_id, category_id, token, count)
+            SELECT user_id, $3, token, count
+            FROM category_token_counts
+            WHERE user_id = $1 AND category_id = $2
+            ON CONFLICT (user_id, category_id, token)
+            DO UPDATE SET count = category_token_counts.count + EXCLUDED.count
+            "#,
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO category_training_totals (user_id, category_id, documents, tokens)
+            SELECT user_id, $3, documents, tokens
+            FROM category_training_totals
+            WHERE user_id = $1 AND category_id = $2
+            ON CONFLICT (user_id, category_id)
+            DO UPDATE SET documents = category_training_totals.documents + EXCLUDED.documents,
+                          tokens = category_training_totals.tokens + EXCLUDED.tokens
+            "#,
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query("DELETE FROM category_token_counts WHERE user_id = $1 AND category_id = $2")
+            .bind(user_id)
+            .bind(from_category_id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("DELETE FROM category_training_totals WHERE user_id = $1 AND category_id = $2")
+            .bind(user_id)
+            .bind(from_category_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Forget everything learned for a user
+    pub async fn clear(conn: &mut PgConnection, user_id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM category_token_counts WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("DELETE FROM category_training_totals WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Load the statistics needed to classify the given tokens
+    pub async fn load(pool: &PgPool, user_id: Uuid, tokens: &[String]) -> Result<NaiveBayes> {
+        let totals = sqlx::query_as::<_, (Uuid, i32, i32)>(
+            "SELECT category_id, documents, tokens FROM category_training_totals WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(category_id, documents, tokens)| {
+            (category_id, (documents as i64, tokens as i64))
+        })
+        .collect();
+
+        let token_counts: HashMap<(Uuid, String), i64> = sqlx::query_as::<_, (Uuid, String, i32)>(
+            r#"
+                SELECT category_id, token, count FROM category_token_counts
+                WHERE user_id = $1 AND token = ANY($2)
+                "#,
+        )
+        .bind(user_id)
+        .bind(tokens)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(category_id, token, count)| ((category_id, token), count as i64))
+        .collect();
+
+        let vocabulary_size = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(DISTINCT token) FROM category_token_counts WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(NaiveBayes {
+            totals,
+            token_counts,
+            vocabulary_size,
+        })
+    }
+}
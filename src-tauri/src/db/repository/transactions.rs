@@ -1,6 +1,11 @@
 use crate::error::Result;
-use crate::models::{CreateTransaction, Transaction, TransactionFilter};
+use crate::models::{
+    CreateTransaction, MerchantSpending, Transaction, TransactionFilter, TransactionWithCategory,
+    UpdateTransaction,
+};
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use sqlx::postgres::PgExecutor;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -14,12 +19,32 @@ pub struct MonthlySummary {
 }
 
 impl TransactionRepository {
-    pub async fn create(pool: &PgPool, tx: CreateTransaction) -> Result<Transaction> {
+    /// Insert a transaction (accepts a pool or an open database transaction)
+    ///
+    /// The amount is converted from its currency (the account's, unless
+    /// given) to the user's base currency with the rate for the transaction
+    /// date. Callers check that the rate is known first, see
+    /// `ensure_booking_rate`; without one the insert fails.
+    pub async fn create<'e, E>(executor: E, tx: CreateTransaction) -> Result<Transaction>
+    where
+        E: PgExecutor<'e>,
+    {
         let transaction = sqlx::query_as::<_, Transaction>(
             r#"
             INSERT INTO transactions
-                (user_id, category_id, amount, store, description, source, email_message_id, transaction_date)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                (user_id, category_id, amount, store, description, source, email_message_id,
+                 external_id, transaction_date, value_date, merchant_id, account_id, transfer_id,
+                 currency, original_amount, fx_rate)
+            SELECT $1, $2, ROUND($3 * fx.rate, 2), $4, $5, $6, $7, $8, $9, $10, $11,
+                   a.id, $13, cur.currency, $3, fx.rate
+            FROM accounts a
+            JOIN users u ON u.id = a.user_id
+            CROSS JOIN LATERAL (SELECT COALESCE($14, a.currency::text) AS currency) cur
+            CROSS JOIN LATERAL (
+                SELECT fx_rate(cur.currency, u.base_currency, $9) AS rate
+            ) fx
+            WHERE a.user_id = $1
+              AND a.id = COALESCE($12, (SELECT id FROM accounts WHERE user_id = $1 AND is_default))
             RETURNING *
             "#,
         )
@@ -30,8 +55,14 @@ impl TransactionRepository {
         .bind(&tx.description)
         .bind(&tx.source)
         .bind(&tx.email_message_id)
+        .bind(&tx.external_id)
         .bind(tx.transaction_date)
-        .fetch_one(pool)
+        .bind(tx.value_date)
+        .bind(tx.merchant_id)
+        .bind(tx.account_id)
+        .bind(tx.transfer_id)
+        .bind(&tx.currency)
+        .fetch_one(executor)
         .await?;
 
         Ok(transaction)
@@ -46,13 +77,37 @@ impl TransactionRepository {
         Ok(tx)
     }
 
+    /// Both legs of a transfer owned by `user_id`, outgoing leg first
+    pub async fn find_by_transfer_id<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        transfer_id: Uuid,
+    ) -> Result<Vec<Transaction>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let transactions = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT * FROM transactions
+            WHERE user_id = $1 AND transfer_id = $2
+            ORDER BY amount
+            "#,
+        )
+        .bind(user_id)
+        .bind(transfer_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(transactions)
+    }
+
     pub async fn find_by_user(
         pool: &PgPool,
         user_id: Uuid,
         filter: &TransactionFilter,
     ) -> Result<Vec<Transaction>> {
-        let limit = filter.limit.unwrap_or(100) as i64;
-        let offset = filter.offset.unwrap_or(0) as i64;
+        let limit = filter.limit.unwrap_or(100);
+        let offset = filter.offset.unwrap_or(0);
 
         let transactions = sqlx::query_as::<_, Transaction>(
             r#"
@@ -60,7 +115,11 @@ impl TransactionRepository {
             WHERE user_id = $1
               AND ($2::date IS NULL OR transaction_date >= $2)
               AND ($3::date IS NULL OR transaction_date <= $3)
-              AND ($4::uuid IS NULL OR category_id = $4)
+              AND ($4::uuid IS NULL OR category_id = $4 OR EXISTS (
+                  SELECT 1 FROM transaction_splits s
+                  WHERE s.transaction_id = transactions.id AND s.category_id = $4
+              ))
+              AND ($7::uuid IS NULL OR account_id = $7)
             ORDER BY transaction_date DESC, created_at DESC
             LIMIT $5 OFFSET $6
             "#,
@@ -71,12 +130,50 @@ impl TransactionRepository {
         .bind(filter.category_id)
         .bind(limit)
         .bind(offset)
+        .bind(filter.account_id)
         .fetch_all(pool)
         .await?;
 
         Ok(transactions)
     }
 
+    /// All transactions matching the filter with category names, oldest first (no paging)
+    pub async fn find_for_export(
+        pool: &PgPool,
+        user_id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<Vec<TransactionWithCategory>> {
+        let (start_date, end_date) = filter.date_range();
+
+        let transactions = sqlx::query_as::<_, TransactionWithCategory>(
+            r#"
+            SELECT t.*, c.name AS category_name
+            FROM transactions t
+            LEFT JOIN categories c ON t.category_id = c.id
+            WHERE t.user_id = $1
+              AND ($2::date IS NULL OR t.transaction_date >= $2)
+              AND ($3::date IS NULL OR t.transaction_date <= $3)
+              AND ($4::uuid IS NULL OR t.category_id = $4 OR EXISTS (
+                  SELECT 1 FROM transaction_splits s
+                  WHERE s.transaction_id = t.id AND s.category_id = $4
+              ))
+              AND ($5::uuid IS NULL OR t.account_id = $5)
+            ORDER BY t.transaction_date, t.created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(filter.category_id)
+        .bind(filter.account_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
+    /// Income and expenses in a month by the sign of each amount, split
+    /// transactions counted by their lines (transfers left out)
     pub async fn get_monthly_summary(
         pool: &PgPool,
         user_id: Uuid,
@@ -86,12 +183,12 @@ impl TransactionRepository {
         let row = sqlx::query_as::<_, (Decimal, Decimal, i64)>(
             r#"
             SELECT
-                COALESCE(SUM(CASE WHEN c.is_income THEN t.amount ELSE 0 END), 0) as income,
-                COALESCE(SUM(CASE WHEN NOT c.is_income OR c.is_income IS NULL THEN ABS(t.amount) ELSE 0 END), 0) as expenses,
-                COUNT(*) as transaction_count
-            FROM transactions t
-            LEFT JOIN categories c ON t.category_id = c.id
+                COALESCE(SUM(CASE WHEN t.amount > 0 THEN t.amount ELSE 0 END), 0) as income,
+                COALESCE(SUM(CASE WHEN t.amount < 0 THEN -t.amount ELSE 0 END), 0) as expenses,
+                COUNT(DISTINCT t.transaction_id) as transaction_count
+            FROM category_amounts t
             WHERE t.user_id = $1
+              AND t.transfer_id IS NULL
               AND EXTRACT(YEAR FROM t.transaction_date) = $2
               AND EXTRACT(MONTH FROM t.transaction_date) = $3
             "#,
@@ -109,56 +206,396 @@ impl TransactionRepository {
         })
     }
 
-    pub async fn find_by_email_message_id(
+    /// Per-category sums and counts in a date range, split transactions
+    /// counted by their lines (uncategorized and transfers excluded)
+    pub async fn get_category_totals(
         pool: &PgPool,
+        user_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<(Uuid, Decimal, i64)>> {
+        let rows = sqlx::query_as::<_, (Uuid, Decimal, i64)>(
+            r#"
+            SELECT category_id, COALESCE(SUM(amount), 0), COUNT(*)
+            FROM category_amounts
+            WHERE user_id = $1
+              AND category_id IS NOT NULL
+              AND transfer_id IS NULL
+              AND transaction_date >= $2
+              AND transaction_date <= $3
+            GROUP BY category_id
+            "#,
+        )
+        .bind(user_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn find_by_email_message_id<'e, E>(
+        executor: E,
         email_message_id: &str,
-    ) -> Result<Option<Transaction>> {
+    ) -> Result<Option<Transaction>>
+    where
+        E: PgExecutor<'e>,
+    {
         let tx = sqlx::query_as::<_, Transaction>(
             "SELECT * FROM transactions WHERE email_message_id = $1",
         )
         .bind(email_message_id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(tx)
     }
 
-    pub async fn update(
-        pool: &PgPool,
+    /// Find a transaction by the bank supplied id for a given import source
+    pub async fn find_by_external_id<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        source: &str,
+        external_id: &str,
+    ) -> Result<Option<Transaction>>
+    where
+        E: PgExecutor<'e>,
+    {
+        let tx = sqlx::query_as::<_, Transaction>(
+            "SELECT * FROM transactions WHERE user_id = $1 AND source = $2 AND external_id = $3",
+        )
+        .bind(user_id)
+        .bind(source)
+        .bind(external_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(tx)
+    }
+
+    /// Whether an archived transaction was restored for the user before
+    ///
+    /// Also true when the archive was written from this database and the
+    /// transaction still exists with its original id
+    pub async fn is_restored<'e, E>(executor: E, user_id: Uuid, archived_id: Uuid) -> Result<bool>
+    where
+        E: PgExecutor<'e>,
+    {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM restored_transactions
+                WHERE user_id = $1 AND archived_id = $2
+            ) OR EXISTS (
+                SELECT 1 FROM transactions WHERE user_id = $1 AND id = $2
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(archived_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Remember which transaction an archived transaction was restored as
+    pub async fn record_restored<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        archived_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<()>
+    where
+        E: PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO restored_transactions (user_id, archived_id, transaction_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, archived_id) DO UPDATE SET transaction_id = $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(archived_id)
+        .bind(transaction_id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Apply changes to a transaction owned by `user_id`
+    ///
+    /// The amount is converted again with the rate for the (new) date and
+    /// currency.
+    /// Returns `None` when no such transaction exists for the user
+    pub async fn update<'e, E>(
+        executor: E,
         id: Uuid,
-        category_id: Option<Uuid>,
-        amount: Option<Decimal>,
-        store: Option<String>,
-        description: Option<String>,
-    ) -> Result<Transaction> {
+        user_id: Uuid,
+        changes: &UpdateTransaction,
+    ) -> Result<Option<Transaction>>
+    where
+        E: PgExecutor<'e>,
+    {
         let tx = sqlx::query_as::<_, Transaction>(
             r#"
-            UPDATE transactions
-            SET category_id = COALESCE($2, category_id),
-                amount = COALESCE($3, amount),
-                store = COALESCE($4, store),
-                description = COALESCE($5, description)
-            WHERE id = $1
-            RETURNING *
+            UPDATE transactions t
+            SET category_id = CASE WHEN $3 THEN $4 ELSE t.category_id END,
+                original_amount = COALESCE($5, t.original_amount),
+                currency = COALESCE($12, t.currency),
+                amount = ROUND(
+                    COALESCE($5, t.original_amount)
+                        * fx_rate(COALESCE($12, t.currency), u.base_currency, COALESCE($10, t.transaction_date)),
+                    2
+                ),
+                fx_rate = fx_rate(COALESCE($12, t.currency), u.base_currency, COALESCE($10, t.transaction_date)),
+                store = CASE WHEN $6 THEN $7 ELSE t.store END,
+                description = CASE WHEN $8 THEN $9 ELSE t.description END,
+                transaction_date = COALESCE($10, t.transaction_date),
+                account_id = COALESCE($11, t.account_id)
+            FROM users u
+            WHERE t.id = $1 AND t.user_id = $2 AND u.id = t.user_id
+            RETURNING t.*
             "#,
         )
         .bind(id)
-        .bind(category_id)
-        .bind(amount)
-        .bind(store)
-        .bind(description)
-        .fetch_one(pool)
+        .bind(user_id)
+        .bind(changes.category_id.is_some())
+        .bind(changes.category_id.flatten())
+        .bind(changes.amount)
+        .bind(changes.store.is_some())
+        .bind(changes.store.clone().flatten())
+        .bind(changes.description.is_some())
+        .bind(changes.description.clone().flatten())
+        .bind(changes.transaction_date)
+        .bind(changes.account_id)
+        .bind(&changes.currency)
+        .fetch_optional(executor)
         .await?;
 
         Ok(tx)
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<()> {
-        sqlx::query("DELETE FROM transactions WHERE id = $1")
+    /// All of a user's transactions without a category, newest first
+    /// (transfers and split transactions, whose lines carry the categories,
+    /// are left out)
+    pub async fn find_uncategorized(pool: &PgPool, user_id: Uuid) -> Result<Vec<Transaction>> {
+        let transactions = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT * FROM transactions
+            WHERE user_id = $1
+              AND category_id IS NULL
+              AND transfer_id IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM transaction_splits s WHERE s.transaction_id = transactions.id
+              )
+            ORDER BY transaction_date DESC, created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
+    /// Set the category of an uncategorized transaction owned by `user_id`
+    ///
+    /// Returns false if the transaction was categorized in the meantime
+    pub async fn set_category_if_empty<'e, E>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        category_id: Uuid,
+    ) -> Result<bool>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            r#"
+            UPDATE transactions SET category_id = $3
+            WHERE id = $1 AND user_id = $2 AND category_id IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(category_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Transactions with a store name that are not linked to a merchant yet
+    pub async fn find_without_merchant(pool: &PgPool, user_id: Uuid) -> Result<Vec<Transaction>> {
+        let transactions = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT * FROM transactions
+            WHERE user_id = $1 AND merchant_id IS NULL AND store IS NOT NULL
+            ORDER BY transaction_date, created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
+    pub async fn set_merchant<'e, E>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+        merchant_id: Option<Uuid>,
+    ) -> Result<()>
+    where
+        E: PgExecutor<'e>,
+    {
+        sqlx::query("UPDATE transactions SET merchant_id = $3 WHERE id = $1 AND user_id = $2")
             .bind(id)
-            .execute(pool)
+            .bind(user_id)
+            .bind(merchant_id)
+            .execute(executor)
             .await?;
 
         Ok(())
     }
+
+    /// Move all of a user's transactions from one merchant to another
+    pub async fn reassign_merchant<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_merchant_id: Uuid,
+        to_merchant_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            "UPDATE transactions SET merchant_id = $3 WHERE user_id = $1 AND merchant_id = $2",
+        )
+        .bind(user_id)
+        .bind(from_merchant_id)
+        .bind(to_merchant_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Expenses with a store or merchant since `start_date`, oldest first
+    ///
+    /// Occurrences posted by recurring transactions and transfers are left out.
+    pub async fn find_store_expenses_since(
+        pool: &PgPool,
+        user_id: Uuid,
+        start_date: NaiveDate,
+    ) -> Result<Vec<Transaction>> {
+        let transactions = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT * FROM transactions
+            WHERE user_id = $1
+              AND amount < 0
+              AND transaction_date >= $2
+              AND (merchant_id IS NOT NULL OR store IS NOT NULL)
+              AND source IS DISTINCT FROM 'recurring'
+              AND transfer_id IS NULL
+            ORDER BY transaction_date, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(start_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
+    /// Per-merchant totals in a date range, biggest spending first
+    pub async fn get_merchant_spending(
+        pool: &PgPool,
+        user_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MerchantSpending>> {
+        let rows = sqlx::query_as::<_, MerchantSpending>(
+            r#"
+            SELECT m.id AS merchant_id, m.name, COALESCE(SUM(t.amount), 0) AS total,
+                   COUNT(*) AS transaction_count
+            FROM transactions t
+            JOIN merchants m ON t.merchant_id = m.id
+            WHERE t.user_id = $1
+              AND t.transfer_id IS NULL
+              AND t.transaction_date >= $2
+              AND t.transaction_date <= $3
+            GROUP BY m.id, m.name
+            ORDER BY total
+            "#,
+        )
+        .bind(user_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Move all of a user's transactions from one category to another
+    pub async fn reassign_category<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        from_category_id: Uuid,
+        to_category_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query(
+            "UPDATE transactions SET category_id = $3 WHERE user_id = $1 AND category_id = $2",
+        )
+        .bind(user_id)
+        .bind(from_category_id)
+        .bind(to_category_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete a transaction owned by `user_id`, returns whether a row was removed
+    pub async fn delete<'e, E>(executor: E, id: Uuid, user_id: Uuid) -> Result<bool>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result = sqlx::query("DELETE FROM transactions WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(executor)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete both legs of a transfer owned by `user_id`, returns the number removed
+    pub async fn delete_transfer<'e, E>(
+        executor: E,
+        transfer_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64>
+    where
+        E: PgExecutor<'e>,
+    {
+        let result =
+            sqlx::query("DELETE FROM transactions WHERE transfer_id = $1 AND user_id = $2")
+                .bind(transfer_id)
+                .bind(user_id)
+                .execute(executor)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
 }
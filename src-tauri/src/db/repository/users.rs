@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::models::{CreateUser, UpdateUser, User};
+use sqlx::postgres::PgExecutor;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -83,6 +84,21 @@ impl UserRepository {
         Ok(user)
     }
 
+    pub async fn set_base_currency<'e, E>(executor: E, id: Uuid, currency: &str) -> Result<User>
+    where
+        E: PgExecutor<'e>,
+    {
+        let user = sqlx::query_as::<_, User>(
+            "UPDATE users SET base_currency = $2 WHERE id = $1 RETURNING *",
+        )
+        .bind(id)
+        .bind(currency)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(user)
+    }
+
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<()> {
         sqlx::query("DELETE FROM users WHERE id = $1")
             .bind(id)
@@ -0,0 +1,260 @@
+use crate::error::{AppError, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use reqwest::Client as HttpClient;
+use serde::Deserialize;
+
+/// Reference to a message returned by the list endpoint
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRef {
+    pub id: String,
+    pub thread_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListMessagesResponse {
+    messages: Option<Vec<MessageRef>>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileResponse {
+    history_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryResponse {
+    history: Option<Vec<HistoryRecord>>,
+    next_page_token: Option<String>,
+    history_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryRecord {
+    messages_added: Option<Vec<MessageAdded>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageAdded {
+    message: MessageRef,
+}
+
+/// Messages added to the mailbox since a history checkpoint
+#[derive(Debug, Clone)]
+pub struct HistoryChanges {
+    pub messages: Vec<MessageRef>,
+    /// Checkpoint to use for the next incremental sync
+    pub history_id: String,
+}
+
+/// Full message fetched with `format=raw`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GmailMessage {
+    pub id: String,
+    pub thread_id: Option<String>,
+    pub history_id: Option<String>,
+    pub internal_date: Option<String>,
+    pub snippet: Option<String>,
+    pub raw: Option<String>,
+}
+
+impl GmailMessage {
+    /// Decode the base64url encoded RFC 822 message
+    pub fn raw_bytes(&self) -> Result<Vec<u8>> {
+        let raw = self.raw.as_deref().ok_or_else(|| {
+            AppError::ExternalService(format!("Message {} has no raw content", self.id))
+        })?;
+
+        // Gmail uses URL-safe base64, padding is optional
+        URL_SAFE_NO_PAD
+            .decode(raw.trim_end_matches('='))
+            .map_err(|e| AppError::ExternalService(format!("Invalid message encoding: {}", e)))
+    }
+}
+
+/// Minimal Gmail REST API client (read-only)
+pub struct GmailClient {
+    http_client: HttpClient,
+    base_url: String,
+    access_token: String,
+}
+
+impl GmailClient {
+    /// Create new Gmail client
+    ///
+    /// `base_url` is normally `https://gmail.googleapis.com/gmail/v1` but can
+    /// point to any server that serves the same JSON (e.g. local fixtures)
+    pub fn new(base_url: &str, access_token: &str) -> Result<Self> {
+        let http_client = HttpClient::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| AppError::ExternalService(format!("HTTP client error: {}", e)))?;
+
+        Ok(Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token: access_token.to_string(),
+        })
+    }
+
+    /// List all message ids matching a Gmail search query (follows pagination)
+    pub async fn list_messages(&self, query: &str) -> Result<Vec<MessageRef>> {
+        let url = format!("{}/users/me/messages", self.base_url);
+        let mut messages = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .http_client
+                .get(&url)
+                .bearer_auth(&self.access_token)
+                .query(&[("q", query)]);
+
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token.as_str())]);
+            }
+
+            let page: ListMessagesResponse = Self::parse(Self::execute(request).await?).await?;
+            messages.extend(page.messages.unwrap_or_default());
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Fetch a single message in raw format
+    pub async fn get_message(&self, id: &str) -> Result<GmailMessage> {
+        let url = format!("{}/users/me/messages/{}", self.base_url, id);
+        let request = self
+            .http_client
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .query(&[("format", "raw")]);
+
+        Self::parse(Self::execute(request).await?).await
+    }
+
+    /// Current mailbox history id (starting point for incremental syncs)
+    pub async fn get_history_id(&self) -> Result<String> {
+        let url = format!("{}/users/me/profile", self.base_url);
+        let request = self.http_client.get(&url).bearer_auth(&self.access_token);
+
+        let profile: ProfileResponse = Self::parse(Self::execute(request).await?).await?;
+        Ok(profile.history_id)
+    }
+
+    /// List messages added since `start_history_id` (follows pagination)
+    ///
+    /// Returns `None` if the checkpoint is too old and a full sync is needed
+    pub async fn list_history(&self, start_history_id: &str) -> Result<Option<HistoryChanges>> {
+        let url = format!("{}/users/me/history", self.base_url);
+        let mut messages = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .http_client
+                .get(&url)
+                .bearer_auth(&self.access_token)
+                .query(&[
+                    ("startHistoryId", start_history_id),
+                    ("historyTypes", "messageAdded"),
+                ]);
+
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token.as_str())]);
+            }
+
+            let response = Self::execute(request).await?;
+
+            // Gmail answers 404 when the start history id has expired
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+
+            let page: HistoryResponse = Self::parse(response).await?;
+            let added = page
+                .history
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|record| record.messages_added.unwrap_or_default())
+                .map(|added| added.message);
+            messages.extend(added);
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => {
+                    return Ok(Some(HistoryChanges {
+                        messages,
+                        history_id: page.history_id,
+                    }))
+                }
+            }
+        }
+    }
+
+    async fn execute(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        request
+            .send()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Gmail request failed: {}", e)))
+    }
+
+    async fn parse<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+
+            if status == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(AppError::Auth(format!(
+                    "Gmail API error {}: {}",
+                    status, body
+                )));
+            }
+
+            return Err(AppError::ExternalService(format!(
+                "Gmail API error {}: {}",
+                status, body
+            )));
+        }
+
+        response.json::<T>().await.map_err(|e| {
+            AppError::ExternalService(format!("Failed to parse Gmail response: {}", e))
+        })
+    }
+}
+
+/// Build a Gmail search query that matches any of the given senders
+pub fn build_sender_query(senders: &[String]) -> String {
+    let senders = senders
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    format!("from:({})", senders)
+}
+
+/// Check whether a `From` header belongs to one of the given senders
+///
+/// Senders can be full addresses or domains (`eurobank.gr` also matches
+/// `alerts@info.eurobank.gr`)
+pub fn matches_sender(from: &str, senders: &[String]) -> bool {
+    let from = from.to_lowercase();
+    senders.iter().any(|sender| {
+        let sender = sender.to_lowercase();
+        if sender.contains('@') {
+            from.contains(&sender)
+        } else {
+            from.contains(&format!("@{}", sender)) || from.contains(&format!(".{}", sender))
+        }
+    })
+}
@@ -0,0 +1,595 @@
+use crate::db::repository::EmailSyncRepository;
+use crate::db::DbPool;
+use crate::error::Result;
+use crate::models::CreateTransaction;
+use crate::services::TransactionService;
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::gmail::{build_sender_query, matches_sender, GmailClient, MessageRef};
+use super::offline;
+use super::parsers::{EmailMessage, ParserRegistry};
+
+/// How a sync run fetched its messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncMode {
+    /// Search the whole mailbox by sender
+    Full,
+    /// Only messages added since the stored `historyId`
+    Incremental,
+    /// Local `.eml` files or an mbox file
+    Offline,
+}
+
+/// Outcome of an import run
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSummary {
+    pub mode: SyncMode,
+    /// Messages considered in this run
+    pub fetched: usize,
+    pub imported: usize,
+    /// Messages already imported or not from a configured sender
+    pub skipped: usize,
+    /// Messages from a bank that are not transaction notifications
+    pub ignored: usize,
+    /// Messages that could not be fetched, parsed or saved
+    pub failed: usize,
+    /// First day of every month that received transactions
+    pub months: Vec<NaiveDate>,
+}
+
+impl ImportSummary {
+    fn new(mode: SyncMode) -> Self {
+        Self {
+            mode,
+            fetched: 0,
+            imported: 0,
+            skipped: 0,
+            ignored: 0,
+            failed: 0,
+            months: Vec::new(),
+        }
+    }
+
+    fn add_imported(&mut self, date: NaiveDate) {
+        self.imported += 1;
+        let month = date.with_day(1).unwrap_or(date);
+        if !self.months.contains(&month) {
+            self.months.push(month);
+        }
+    }
+}
+
+/// Imports card notification emails as transactions
+pub struct EmailImporter {
+    pool: DbPool,
+    senders: Vec<String>,
+    parsers: ParserRegistry,
+}
+
+impl EmailImporter {
+    pub fn new(pool: DbPool, senders: Vec<String>) -> Self {
+        Self::with_parsers(pool, senders, ParserRegistry::with_defaults())
+    }
+
+    /// Create an importer with a custom parser registry
+    pub fn with_parsers(pool: DbPool, senders: Vec<String>, parsers: ParserRegistry) -> Self {
+        Self {
+            pool,
+            senders,
+            parsers,
+        }
+    }
+
+    /// Sync new notification emails
+    ///
+    /// Uses the stored history checkpoint when available and falls back to a
+    /// full mailbox scan when there is none, it has expired or `force_full` is set
+    pub async fn sync(
+        &self,
+        client: &GmailClient,
+        user_id: Uuid,
+        force_full: bool,
+    ) -> Result<ImportSummary> {
+        let checkpoint = if force_full {
+            None
+        } else {
+            EmailSyncRepository::find_by_user(&self.pool, user_id).await?
+        };
+
+        if let Some(checkpoint) = checkpoint {
+            match client.list_history(&checkpoint.history_id).await? {
+                Some(changes) => {
+                    // Retry what could not be fetched last time along with the new messages
+                    let mut messages: Vec<MessageRef> = checkpoint
+                        .failed_message_ids
+                        .into_iter()
+                        .map(|id| MessageRef {
+                            id,
+                            thread_id: None,
+                        })
+                        .collect();
+                    for message in changes.messages {
+                        if !messages.iter().any(|m| m.id == message.id) {
+                            messages.push(message);
+                        }
+                    }
+
+                    let mut summary = ImportSummary::new(SyncMode::Incremental);
+                    let failed_ids = self
+                        .import_messages(client, user_id, messages, &mut summary)
+                        .await?;
+
+                    EmailSyncRepository::upsert(
+                        &self.pool,
+                        user_id,
+                        &changes.history_id,
+                        false,
+                        &failed_ids,
+                    )
+                    .await?;
+                    return Ok(summary);
+                }
+                None => {
+                    tracing::info!("Gmail history checkpoint expired, running full sync");
+                }
+            }
+        }
+
+        self.full_sync(client, user_id).await
+    }
+
+    /// Scan the whole mailbox for notification emails and reset the checkpoint
+    pub async fn full_sync(&self, client: &GmailClient, user_id: Uuid) -> Result<ImportSummary> {
+        // Take the checkpoint first so nothing arriving during the scan is missed
+        let history_id = client.get_history_id().await?;
+
+        let query = build_sender_query(&self.senders);
+        let messages = client.list_messages(&query).await?;
+        tracing::info!("Found {} notification emails", messages.len());
+
+        let mut summary = ImportSummary::new(SyncMode::Full);
+        let failed_ids = self
+            .import_messages(client, user_id, messages, &mut summary)
+            .await?;
+
+        EmailSyncRepository::upsert(&self.pool, user_id, &history_id, true, &failed_ids).await?;
+        Ok(summary)
+    }
+
+    /// Import a local `.mbox` file, `.eml` file or folder of `.eml` files
+    ///
+    /// Deduplicates on the `Message-ID` header. No sender filtering is done
+    /// since the user picked the files explicitly.
+    pub async fn import_files(&self, path: &Path, user_id: Uuid) -> Result<ImportSummary> {
+        let service = TransactionService::new(self.pool.clone());
+        let messages = offline::read_messages(path).await?;
+
+        let mut summary = ImportSummary::new(SyncMode::Offline);
+        summary.fetched = messages.len();
+
+        for raw in messages {
+            let email = match EmailMessage::parse(&raw) {
+                Ok(email) => email,
+                Err(e) => {
+                    tracing::warn!("Failed to parse email file: {}", e);
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            let message_id = match email.message_id.clone() {
+                Some(id) => id,
+                None => {
+                    tracing::warn!("Skipping email without Message-ID header");
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            if service.is_email_imported(&message_id).await? {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let parsed = match self.parsers.parse(&email) {
+                Some(p) => p,
+                None => {
+                    tracing::debug!("Message {} is not a transaction notification", message_id);
+                    summary.ignored += 1;
+                    continue;
+                }
+            };
+
+            let tx = CreateTransaction {
+                user_id,
+                category_id: None,
+                amount: parsed.amount,
+                store: parsed.store,
+                description: None,
+                source: "email".to_string(),
+                email_message_id: Some(message_id),
+                external_id: None,
+                transaction_date: parsed.date,
+                value_date: None,
+                merchant_id: None,
+                account_id: None,
+                transfer_id: None,
+                currency: Some(parsed.currency),
+            };
+
+            // A row that cannot be saved (e.g. a currency without exchange
+            // rates) does not stop the rest of the import
+            if let Err(e) = service.create_transaction_from_dto(tx).await {
+                tracing::warn!("Failed to import email file: {}", e);
+                summary.failed += 1;
+                continue;
+            }
+            summary.add_imported(parsed.date);
+        }
+
+        tracing::info!(
+            "Offline email import finished: {} imported, {} skipped, {} ignored, {} failed",
+            summary.imported,
+            summary.skipped,
+            summary.ignored,
+            summary.failed
+        );
+
+        Ok(summary)
+    }
+
+    /// Import fetched messages, returns the ids of those that could not be
+    /// fetched or saved so they can be retried
+    async fn import_messages(
+        &self,
+        client: &GmailClient,
+        user_id: Uuid,
+        messages: Vec<MessageRef>,
+        summary: &mut ImportSummary,
+    ) -> Result<Vec<String>> {
+        let service = TransactionService::new(self.pool.clone());
+        let mut failed_ids = Vec::new();
+        summary.fetched += messages.len();
+
+        for message_ref in messages {
+            let message = match client.get_message(&message_ref.id).await {
+                Ok(m) => m,
+                Err(e) => {
+                    tracing::warn!("Failed to fetch message {}: {}", message_ref.id, e);
+                    summary.failed += 1;
+                    failed_ids.push(message_ref.id);
+                    continue;
+                }
+            };
+
+            let email = match message
+                .raw_bytes()
+                .and_then(|raw| EmailMessage::parse(&raw))
+            {
+                Ok(email) => email,
+                Err(e) => {
+                    tracing::warn!("Failed to parse message {}: {}", message.id, e);
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            // History results are not filtered by sender like the search query
+            let from_sender = email
+                .from
+                .as_deref()
+                .is_some_and(|from| matches_sender(from, &self.senders));
+            if !from_sender {
+                summary.skipped += 1;
+                continue;
+            }
+
+            // Deduplicate on the `Message-ID` header like file imports, so a
+            // notification synced from Gmail and imported from a file is kept once
+            let message_id = email
+                .message_id
+                .clone()
+                .unwrap_or_else(|| message.id.clone());
+            if service.is_email_imported(&message_id).await? {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let parsed = match self.parsers.parse(&email) {
+                Some(p) => p,
+                None => {
+                    tracing::debug!("Message {} is not a transaction notification", message_id);
+                    summary.ignored += 1;
+                    continue;
+                }
+            };
+
+            let tx = CreateTransaction {
+                user_id,
+                category_id: None,
+                amount: parsed.amount,
+                store: parsed.store,
+                description: None,
+                source: "email".to_string(),
+                email_message_id: Some(message_id),
+                external_id: None,
+                transaction_date: parsed.date,
+                value_date: None,
+                merchant_id: None,
+                account_id: None,
+                transfer_id: None,
+                currency: Some(parsed.currency),
+            };
+
+            // Keep going so the checkpoint still moves past the other messages
+            if let Err(e) = service.create_transaction_from_dto(tx).await {
+                tracing::warn!("Failed to import message {}: {}", message.id, e);
+                summary.failed += 1;
+                failed_ids.push(message.id);
+                continue;
+            }
+            summary.add_imported(parsed.date);
+        }
+
+        tracing::info!(
+            "Email import finished: {} imported, {} skipped, {} ignored, {} failed",
+            summary.imported,
+            summary.skipped,
+            summary.ignored,
+            summary.failed
+        );
+
+        Ok(failed_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repository::{FxRateRepository, TransactionRepository};
+    use crate::email::GmailMessage;
+    use crate::models::{FxRate, TransactionFilter};
+    use crate::test_support::create_user;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use warp::{Filter, Reply};
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/gmail");
+
+    /// Serve a fixture file as a Gmail API response, 404 when there is none
+    fn fixture(name: &str) -> warp::reply::Response {
+        match std::fs::read_to_string(format!("{}/{}", FIXTURES, name)) {
+            Ok(body) => {
+                warp::reply::with_header(body, "content-type", "application/json").into_response()
+            }
+            Err(_) => warp::http::StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+
+    /// Start a stand-in Gmail API on a free local port, returns its base URL
+    ///
+    /// While `outage` is set fetching single messages fails.
+    fn start_gmail_server(outage: Arc<AtomicBool>) -> String {
+        let profile = warp::path!("users" / "me" / "profile").map(|| fixture("profile.json"));
+        let list = warp::path!("users" / "me" / "messages")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(
+                |query: HashMap<String, String>| match query.get("pageToken") {
+                    Some(token) => fixture(&format!("messages_{}.json", token)),
+                    None => fixture("messages.json"),
+                },
+            );
+        let get = warp::path!("users" / "me" / "messages" / String).map(move |id: String| {
+            if outage.load(Ordering::SeqCst) {
+                return warp::http::StatusCode::SERVICE_UNAVAILABLE.into_response();
+            }
+            fixture(&format!("messages/{}.json", id))
+        });
+        // Checkpoints without a fixture (older than profile.json) have expired
+        let history = warp::path!("users" / "me" / "history")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(|query: HashMap<String, String>| {
+                match query.get("startHistoryId").map(String::as_str) {
+                    Some("1000") => fixture("history.json"),
+                    Some(id) => fixture(&format!("history_{}.json", id)),
+                    None => warp::http::StatusCode::NOT_FOUND.into_response(),
+                }
+            });
+
+        let routes = warp::get().and(profile.or(list).unify().or(get).unify().or(history).unify());
+        let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        format!("http://{}", addr)
+    }
+
+    async fn amounts(pool: &PgPool, user_id: Uuid) -> Vec<(Option<String>, Decimal)> {
+        let mut amounts: Vec<_> =
+            TransactionRepository::find_by_user(pool, user_id, &TransactionFilter::default())
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|tx| (tx.store, tx.amount))
+                .collect();
+        amounts.sort();
+        amounts
+    }
+
+    fn importer(pool: &PgPool) -> EmailImporter {
+        EmailImporter::new(
+            pool.clone(),
+            vec!["eurobank.gr".to_string(), "revolut.com".to_string()],
+        )
+    }
+
+    #[sqlx::test]
+    async fn full_sync_then_incremental_sync(pool: PgPool) {
+        let user_id = create_user(&pool, "user@example.com").await.id;
+        let client = GmailClient::new(&start_gmail_server(Arc::default()), "test-token").unwrap();
+        let importer = importer(&pool);
+
+        // No checkpoint yet: the whole mailbox is searched, over two pages
+        let summary = importer.sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.mode, SyncMode::Full);
+        assert_eq!(summary.fetched, 3);
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.ignored, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            amounts(&pool, user_id).await,
+            vec![
+                (Some("AB VASILOPOULOS".to_string()), Decimal::new(-2345, 2)),
+                (Some("Tesco".to_string()), Decimal::new(-1250, 2)),
+            ]
+        );
+        let checkpoint = EmailSyncRepository::find_by_user(&pool, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.history_id, "1000");
+
+        // Only messages added since the checkpoint, one not from a bank
+        let summary = importer.sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.mode, SyncMode::Incremental);
+        assert_eq!(summary.fetched, 2);
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(amounts(&pool, user_id).await.len(), 3);
+        let checkpoint = EmailSyncRepository::find_by_user(&pool, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.history_id, "1010");
+    }
+
+    #[sqlx::test]
+    async fn expired_checkpoint_falls_back_to_full_sync(pool: PgPool) {
+        let user_id = create_user(&pool, "user@example.com").await.id;
+        let client = GmailClient::new(&start_gmail_server(Arc::default()), "test-token").unwrap();
+        EmailSyncRepository::upsert(&pool, user_id, "1", true, &[])
+            .await
+            .unwrap();
+
+        let summary = importer(&pool).sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.mode, SyncMode::Full);
+        assert_eq!(summary.imported, 2);
+
+        // Running again finds the same messages already imported
+        let summary = importer(&pool).sync(&client, user_id, true).await.unwrap();
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.skipped, 2);
+    }
+
+    #[sqlx::test]
+    async fn messages_that_failed_to_fetch_are_retried(pool: PgPool) {
+        let user_id = create_user(&pool, "user@example.com").await.id;
+        let outage = Arc::new(AtomicBool::new(true));
+        let client = GmailClient::new(&start_gmail_server(outage.clone()), "test-token").unwrap();
+        let importer = importer(&pool);
+
+        let summary = importer.sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.failed, 3);
+        assert_eq!(summary.imported, 0);
+        let checkpoint = EmailSyncRepository::find_by_user(&pool, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.history_id, "1000");
+        assert_eq!(checkpoint.failed_message_ids.len(), 3);
+
+        // The failed messages are fetched again with the ones added since
+        outage.store(false, Ordering::SeqCst);
+        let summary = importer.sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.mode, SyncMode::Incremental);
+        assert_eq!(summary.fetched, 5);
+        assert_eq!(summary.imported, 3);
+        assert_eq!(summary.ignored, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.failed, 0);
+        let checkpoint = EmailSyncRepository::find_by_user(&pool, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.history_id, "1010");
+        assert!(checkpoint.failed_message_ids.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn messages_without_exchange_rates_are_retried(pool: PgPool) {
+        let user_id = create_user(&pool, "user@example.com").await.id;
+        let client = GmailClient::new(&start_gmail_server(Arc::default()), "test-token").unwrap();
+        let importer = importer(&pool);
+        EmailSyncRepository::upsert(&pool, user_id, "1010", true, &[])
+            .await
+            .unwrap();
+
+        // A USD charge while no rates are imported fails without stopping the sync
+        let summary = importer.sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.mode, SyncMode::Incremental);
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.failed, 1);
+        let checkpoint = EmailSyncRepository::find_by_user(&pool, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.history_id, "1020");
+        assert_eq!(checkpoint.failed_message_ids, vec!["18e2a0c0d1a2b3c6"]);
+
+        FxRateRepository::upsert_many(
+            &pool,
+            &[FxRate {
+                base: "EUR".to_string(),
+                quote: "USD".to_string(),
+                rate_date: NaiveDate::from_ymd_opt(2026, 3, 17).unwrap(),
+                rate: Decimal::new(12, 1),
+                source: "ecb".to_string(),
+            }],
+        )
+        .await
+        .unwrap();
+
+        let summary = importer.sync(&client, user_id, false).await.unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            amounts(&pool, user_id).await,
+            vec![(Some("Amazon".to_string()), Decimal::new(-2500, 2))]
+        );
+        let checkpoint = EmailSyncRepository::find_by_user(&pool, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(checkpoint.failed_message_ids.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn gmail_and_file_imports_share_message_ids(pool: PgPool) {
+        let user_id = create_user(&pool, "user@example.com").await.id;
+        let client = GmailClient::new(&start_gmail_server(Arc::default()), "test-token").unwrap();
+        let importer = importer(&pool);
+        importer.sync(&client, user_id, false).await.unwrap();
+
+        // The Eurobank notification saved from the mail client as a file
+        let message: GmailMessage = serde_json::from_str(
+            &std::fs::read_to_string(format!("{}/messages/18e2a0c0d1a2b3c1.json", FIXTURES))
+                .unwrap(),
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join(format!("{}.eml", user_id));
+        std::fs::write(&path, message.raw_bytes().unwrap()).unwrap();
+
+        let summary = importer.import_files(&path, user_id).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(amounts(&pool, user_id).await.len(), 2);
+    }
+}
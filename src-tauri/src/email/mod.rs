@@ -0,0 +1,15 @@
+//! Email import module for bank and card notifications
+//!
+//! Provides:
+//! - `GmailClient` - Read-only Gmail REST API client
+//! - `EmailImporter` - Turns notification emails (Gmail or local files) into transactions
+//! - `ParserRegistry` - Bank specific notification parsers by sender domain
+
+mod gmail;
+mod importer;
+mod offline;
+pub mod parsers;
+
+pub use gmail::{GmailClient, GmailMessage, HistoryChanges, MessageRef};
+pub use importer::{EmailImporter, ImportSummary, SyncMode};
+pub use parsers::{EmailMessage, ParsedTransaction, ParserRegistry, TransactionParser};
@@ -0,0 +1,74 @@
+use crate::error::{AppError, Result};
+use std::path::Path;
+
+/// Read raw messages from a `.eml` file, a folder of `.eml` files or an mbox file
+pub async fn read_messages(path: &Path) -> Result<Vec<Vec<u8>>> {
+    let metadata = tokio::fs::metadata(path).await?;
+
+    if metadata.is_dir() {
+        let mut messages = Vec::new();
+        let mut entries = tokio::fs::read_dir(path).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let file_path = entry.path();
+            if is_eml(&file_path) && entry.file_type().await?.is_file() {
+                messages.push(tokio::fs::read(&file_path).await?);
+            }
+        }
+
+        return Ok(messages);
+    }
+
+    let content = tokio::fs::read(path).await?;
+
+    if is_eml(path) {
+        Ok(vec![content])
+    } else {
+        split_mbox(&content)
+    }
+}
+
+fn is_eml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("eml"))
+}
+
+/// Split an mbox file into raw messages
+///
+/// Messages start with a `From ` separator line; `>From ` quoting in the
+/// body is undone (mboxrd)
+fn split_mbox(content: &[u8]) -> Result<Vec<Vec<u8>>> {
+    if !content.starts_with(b"From ") {
+        return Err(AppError::Validation(
+            "Not an mbox file (missing 'From ' separator)".to_string(),
+        ));
+    }
+
+    let mut messages = Vec::new();
+    let mut current: Option<Vec<u8>> = None;
+
+    for line in content.split_inclusive(|&b| b == b'\n') {
+        if line.starts_with(b"From ") {
+            if let Some(message) = current.take() {
+                messages.push(message);
+            }
+            current = Some(Vec::new());
+            continue;
+        }
+
+        if let Some(message) = current.as_mut() {
+            let unquoted = match line.iter().position(|&b| b != b'>') {
+                Some(pos) if pos > 0 && line[pos..].starts_with(b"From ") => &line[1..],
+                _ => line,
+            };
+            message.extend_from_slice(unquoted);
+        }
+    }
+
+    if let Some(message) = current {
+        messages.push(message);
+    }
+
+    Ok(messages)
+}
@@ -0,0 +1,87 @@
+//! Notification templates for Greek banks
+//!
+//! Patterns are written against the plain-text part of the card alert
+//! emails. Amounts use the Greek `1.234,56` format except for Revolut.
+
+use super::{TemplateParser, TransactionParser};
+
+const GREEK_DATE_FORMATS: &[&str] = &["%d/%m/%Y", "%d-%m-%Y", "%d.%m.%Y", "%d/%m/%y"];
+
+/// All built-in bank parsers
+pub fn default_parsers() -> Vec<Box<dyn TransactionParser>> {
+    vec![
+        Box::new(eurobank()),
+        Box::new(piraeus()),
+        Box::new(alpha()),
+        Box::new(nbg()),
+        Box::new(revolut()),
+    ]
+}
+
+/// "...συναλλαγή ποσού 23,45 EUR στο κατάστημα AB VASILOPOULOS στις 12/03/2026..."
+fn eurobank() -> TemplateParser {
+    TemplateParser::new(
+        "Eurobank",
+        &["eurobank.gr"],
+        r"(?i)ποσού\s+(?P<amount>\d[\d.,]*)\s*(?P<currency>EUR|USD|GBP|€)",
+        r"(?i)κατάστημα\s+(?P<store>.+?)\s+(?:στις|την)\s",
+    )
+    .with_date(
+        r"(?:στις|την)\s+(?P<date>\d{2}/\d{2}/\d{4})",
+        GREEK_DATE_FORMATS,
+    )
+    .with_refund(r"(?i)επιστροφή|πίστωση")
+}
+
+/// "Ποσό: 23,45 EUR\nΈμπορος: AB VASILOPOULOS\nΗμερομηνία: 12/03/2026 14:32"
+fn piraeus() -> TemplateParser {
+    TemplateParser::new(
+        "Piraeus Bank",
+        &["piraeusbank.gr", "winbank.gr"],
+        r"(?i)ποσό\s*:\s*(?P<amount>\d[\d.,]*)\s*(?P<currency>EUR|USD|GBP|€)",
+        r"(?im)έμπορος\s*:\s*(?P<store>[^\r\n]+)",
+    )
+    .with_date(
+        r"(?i)ημερομηνία\s*:\s*(?P<date>\d{2}/\d{2}/\d{4})",
+        GREEK_DATE_FORMATS,
+    )
+    .with_refund(r"(?i)επιστροφή|αντιλογισμός")
+}
+
+/// "Χρέωση κάρτας: 23,45€\nΕπιχείρηση: AB VASILOPOULOS\nΗμ/νία: 12/03/2026"
+fn alpha() -> TemplateParser {
+    TemplateParser::new(
+        "Alpha Bank",
+        &["alpha.gr", "alphabank.gr"],
+        r"(?i)(?:χρέωση|πίστωση)\s+κάρτας\s*:\s*(?P<amount>\d[\d.,]*)\s*(?P<currency>EUR|USD|GBP|€)",
+        r"(?im)επιχείρηση\s*:\s*(?P<store>[^\r\n]+)",
+    )
+    .with_date(r"(?i)ημ/νία\s*:\s*(?P<date>\d{2}/\d{2}/\d{4})", GREEK_DATE_FORMATS)
+    .with_refund(r"(?i)πίστωση\s+κάρτας")
+}
+
+/// "Συναλλαγή ποσού EUR 23,45 στο AB VASILOPOULOS την 12/03/2026"
+fn nbg() -> TemplateParser {
+    TemplateParser::new(
+        "National Bank of Greece",
+        &["nbg.gr"],
+        r"(?i)ποσού\s+(?P<currency>EUR|USD|GBP|€)\s*(?P<amount>\d[\d.,]*\d)",
+        r"(?i)\s(?:στο|στην|στον)\s+(?P<store>.+?)\s+την\s",
+    )
+    .with_date(
+        r"(?i)την\s+(?P<date>\d{2}[./-]\d{2}[./-]\d{4})",
+        GREEK_DATE_FORMATS,
+    )
+    .with_refund(r"(?i)επιστροφή")
+}
+
+/// "You spent €23.45 at Tesco" / "You received a refund of £5.00 from Amazon"
+fn revolut() -> TemplateParser {
+    TemplateParser::new(
+        "Revolut",
+        &["revolut.com"],
+        r"(?i)(?:spent|paid|refund of|payment of)\s+(?P<currency>€|\$|£|EUR|USD|GBP)\s*(?P<amount>\d[\d,]*(?:\.\d{1,2})?)",
+        r"(?i)\b(?:at|to|from)\s+(?P<store>[^\r\n.!]+)",
+    )
+    .with_refund(r"(?i)refund")
+}
@@ -0,0 +1,52 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::{normalize_currency, parse_amount, EmailMessage, ParsedTransaction, TransactionParser};
+
+/// Best-effort parser used for senders without a dedicated template
+///
+/// Looks for the first amount followed or preceded by a currency marker
+/// and a merchant introduced by "at"/"στο"/"σε"
+pub struct GenericParser;
+
+impl TransactionParser for GenericParser {
+    fn name(&self) -> &str {
+        "generic"
+    }
+
+    fn sender_domains(&self) -> &[&'static str] {
+        &[]
+    }
+
+    fn parse(&self, email: &EmailMessage) -> Option<ParsedTransaction> {
+        static AMOUNT_RE: OnceLock<Regex> = OnceLock::new();
+        static STORE_RE: OnceLock<Regex> = OnceLock::new();
+
+        let amount_re = AMOUNT_RE.get_or_init(|| {
+            Regex::new(r"(?i)(?:(EUR|USD|GBP|€|\$|£)\s*(\d[\d.,]*\d))|(?:(\d[\d.,]*\d)\s*(EUR|USD|GBP|€|\$|£))")
+                .unwrap()
+        });
+        let store_re = STORE_RE.get_or_init(|| {
+            Regex::new(r"(?i)\b(?:at|στο|στα|σε)\s+([^\r\n,.]+?)(?:\s+(?:on|στις|με)\b|[\r\n,.]|$)")
+                .unwrap()
+        });
+
+        let caps = amount_re.captures(&email.body)?;
+        let raw_amount = caps.get(2).or_else(|| caps.get(3))?.as_str();
+        let currency = caps.get(1).or_else(|| caps.get(4))?.as_str();
+        let amount = parse_amount(raw_amount)?;
+
+        let store = store_re
+            .captures(&email.body)
+            .map(|c| c[1].trim().to_string())
+            .filter(|s| !s.is_empty());
+
+        Some(ParsedTransaction {
+            // Card notifications report charges, store them as expenses
+            amount: -amount.abs(),
+            store,
+            date: email.date?,
+            currency: normalize_currency(currency),
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use crate::error::{AppError, Result};
+use chrono::{DateTime, NaiveDate};
+use mailparse::{MailHeaderMap, ParsedMail};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Parsed email with the parts the parsers care about
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub message_id: Option<String>,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub body: String,
+}
+
+impl EmailMessage {
+    /// Parse a raw RFC 822 message
+    pub fn parse(raw: &[u8]) -> Result<Self> {
+        let mail = mailparse::parse_mail(raw)
+            .map_err(|e| AppError::Validation(format!("Invalid email message: {}", e)))?;
+
+        let headers = mail.get_headers();
+        let date = headers
+            .get_first_value("Date")
+            .and_then(|d| mailparse::dateparse(&d).ok())
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .map(|dt| dt.date_naive());
+
+        Ok(Self {
+            message_id: headers.get_first_value("Message-ID"),
+            from: headers.get_first_value("From"),
+            subject: headers.get_first_value("Subject"),
+            date,
+            body: extract_text(&mail),
+        })
+    }
+
+    /// Domain of the sender address (e.g. `eurobank.gr`), lowercased
+    pub fn sender_domain(&self) -> Option<String> {
+        let from = self.from.as_deref()?;
+
+        // "Bank <alerts@bank.gr>" or plain "alerts@bank.gr"
+        let address = match (from.rfind('<'), from.rfind('>')) {
+            (Some(start), Some(end)) if start < end => &from[start + 1..end],
+            _ => from,
+        };
+
+        address
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim().to_lowercase())
+            .filter(|domain| !domain.is_empty())
+    }
+}
+
+/// Extract readable text from a (possibly multipart) message
+///
+/// Prefers `text/plain` parts and falls back to `text/html` with tags removed
+fn extract_text(mail: &ParsedMail) -> String {
+    if let Some(text) = find_part(mail, "text/plain") {
+        return text;
+    }
+
+    find_part(mail, "text/html")
+        .map(|html| strip_html(&html))
+        .unwrap_or_default()
+}
+
+fn find_part(mail: &ParsedMail, mimetype: &str) -> Option<String> {
+    if mail.subparts.is_empty() {
+        if mail.ctype.mimetype.eq_ignore_ascii_case(mimetype) {
+            return mail.get_body().ok();
+        }
+        return None;
+    }
+
+    mail.subparts
+        .iter()
+        .find_map(|part| find_part(part, mimetype))
+}
+
+fn strip_html(html: &str) -> String {
+    static TAG_RE: OnceLock<Regex> = OnceLock::new();
+    let tag_re = TAG_RE.get_or_init(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+
+    tag_re
+        .replace_all(html, "\n")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&euro;", "€")
+}
@@ -0,0 +1,287 @@
+//! Bank notification parsers
+//!
+//! Each bank formats its card alerts differently, so parsing is done by
+//! `TransactionParser` implementations looked up by sender domain in a
+//! `ParserRegistry`. Unknown senders fall back to `GenericParser`.
+
+mod banks;
+mod generic;
+mod message;
+mod template;
+
+pub use generic::GenericParser;
+pub use message::EmailMessage;
+pub use template::TemplateParser;
+
+use crate::error::Result;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Transaction details extracted from a notification email
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedTransaction {
+    /// Signed amount (negative for card charges, positive for refunds)
+    pub amount: Decimal,
+    pub store: Option<String>,
+    pub date: NaiveDate,
+    pub currency: String,
+}
+
+/// Parser for one bank's notification format
+pub trait TransactionParser: Send + Sync {
+    /// Human readable parser name (used in logs)
+    fn name(&self) -> &str;
+
+    /// Sender domains handled by this parser
+    fn sender_domains(&self) -> &[&'static str];
+
+    /// Extract a transaction from an already parsed message
+    ///
+    /// Returns `None` if the message is not a transaction notification
+    fn parse(&self, email: &EmailMessage) -> Option<ParsedTransaction>;
+
+    /// Extract a transaction from a raw MIME message
+    fn parse_raw(&self, raw: &[u8]) -> Result<Option<ParsedTransaction>> {
+        let email = EmailMessage::parse(raw)?;
+        Ok(self.parse(&email))
+    }
+}
+
+/// Parsers keyed by sender domain
+pub struct ParserRegistry {
+    parsers: Vec<Box<dyn TransactionParser>>,
+    by_domain: HashMap<String, usize>,
+    fallback: GenericParser,
+}
+
+impl ParserRegistry {
+    /// Create a registry without bank parsers (only the generic fallback)
+    pub fn empty() -> Self {
+        Self {
+            parsers: Vec::new(),
+            by_domain: HashMap::new(),
+            fallback: GenericParser,
+        }
+    }
+
+    /// Create a registry with all built-in Greek bank parsers
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::empty();
+        for parser in banks::default_parsers() {
+            registry.register(parser);
+        }
+        registry
+    }
+
+    /// Register a parser for all its sender domains
+    ///
+    /// Later registrations override earlier ones for the same domain
+    pub fn register(&mut self, parser: Box<dyn TransactionParser>) {
+        let index = self.parsers.len();
+        for domain in parser.sender_domains() {
+            self.by_domain.insert(domain.to_lowercase(), index);
+        }
+        self.parsers.push(parser);
+    }
+
+    /// Find the parser for a sender domain
+    ///
+    /// Subdomains match their parent (`alerts.eurobank.gr` -> `eurobank.gr`)
+    pub fn find_for_domain(&self, domain: &str) -> Option<&dyn TransactionParser> {
+        let mut candidate = domain.to_lowercase();
+        loop {
+            if let Some(&index) = self.by_domain.get(&candidate) {
+                return Some(self.parsers[index].as_ref());
+            }
+
+            match candidate.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => candidate = parent.to_string(),
+                _ => return None,
+            }
+        }
+    }
+
+    /// Parse a message with the parser registered for its sender
+    pub fn parse(&self, email: &EmailMessage) -> Option<ParsedTransaction> {
+        let parser = email
+            .sender_domain()
+            .and_then(|domain| self.find_for_domain(&domain))
+            .unwrap_or(&self.fallback);
+
+        tracing::debug!("Parsing message with {} parser", parser.name());
+        parser.parse(email)
+    }
+
+    /// Parse a raw MIME message with the parser registered for its sender
+    pub fn parse_raw(&self, raw: &[u8]) -> Result<Option<ParsedTransaction>> {
+        let email = EmailMessage::parse(raw)?;
+        Ok(self.parse(&email))
+    }
+}
+
+impl Default for ParserRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Parse an amount written either as `1.234,56` or `1,234.56`
+pub fn parse_amount(raw: &str) -> Option<Decimal> {
+    let raw = raw.trim();
+    let last_sep = raw.rfind([',', '.']);
+
+    let normalized = match last_sep {
+        // Separator followed by 1-2 digits is the decimal point
+        Some(pos) if raw.len() - pos - 1 <= 2 => {
+            let (int_part, frac_part) = raw.split_at(pos);
+            let int_part: String = int_part.chars().filter(|c| c.is_ascii_digit()).collect();
+            format!("{}.{}", int_part, &frac_part[1..])
+        }
+        // Otherwise all separators are thousands separators
+        _ => raw.chars().filter(|c| c.is_ascii_digit()).collect(),
+    };
+
+    Decimal::from_str(&normalized).ok()
+}
+
+/// Map a currency symbol or code to an ISO 4217 code
+pub fn normalize_currency(raw: &str) -> String {
+    match raw.trim() {
+        "€" => "EUR".to_string(),
+        "$" => "USD".to_string(),
+        "£" => "GBP".to_string(),
+        code => code.to_uppercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse an anonymized notification from `tests/fixtures/eml`
+    fn parse_fixture(name: &str) -> Option<ParsedTransaction> {
+        let path = format!(
+            "{}/tests/fixtures/eml/{}.eml",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        let raw = std::fs::read(&path).unwrap();
+        ParserRegistry::default().parse_raw(&raw).unwrap()
+    }
+
+    fn expected(
+        amount: i64,
+        store: &str,
+        date: (i32, u32, u32),
+        currency: &str,
+    ) -> ParsedTransaction {
+        ParsedTransaction {
+            amount: Decimal::new(amount, 2),
+            store: Some(store.to_string()),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            currency: currency.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_eurobank_notification() {
+        assert_eq!(
+            parse_fixture("eurobank"),
+            Some(expected(-2345, "AB VASILOPOULOS", (2026, 3, 12), "EUR"))
+        );
+    }
+
+    #[test]
+    fn parses_piraeus_quoted_printable_notification() {
+        assert_eq!(
+            parse_fixture("piraeus"),
+            Some(expected(-123456, "PLAISIO COMPUTERS", (2026, 2, 5), "EUR"))
+        );
+    }
+
+    #[test]
+    fn parses_alpha_html_notification() {
+        assert_eq!(
+            parse_fixture("alpha"),
+            Some(expected(-890, "COSMOS SPORT", (2026, 2, 7), "EUR"))
+        );
+    }
+
+    #[test]
+    fn parses_nbg_notification() {
+        assert_eq!(
+            parse_fixture("nbg"),
+            Some(expected(-4500, "SHELL ΓΛΥΦΑΔΑΣ", (2026, 2, 10), "EUR"))
+        );
+    }
+
+    #[test]
+    fn parses_revolut_charge_and_refund() {
+        assert_eq!(
+            parse_fixture("revolut"),
+            Some(expected(-1820, "Pret A Manger", (2026, 2, 18), "GBP"))
+        );
+        assert_eq!(
+            parse_fixture("revolut_refund"),
+            Some(expected(999, "Spotify", (2026, 2, 20), "EUR"))
+        );
+    }
+
+    #[test]
+    fn unknown_sender_uses_generic_parser() {
+        assert_eq!(
+            parse_fixture("generic"),
+            Some(expected(-1500, "KIOSK ONE", (2026, 2, 22), "EUR"))
+        );
+    }
+
+    #[test]
+    fn message_without_amount_is_not_a_notification() {
+        let raw = b"From: Eurobank <news@eurobank.gr>\r\n\
+            Subject: News\r\n\
+            Date: Sat, 14 Mar 2026 10:00:00 +0200\r\n\
+            Content-Type: text/plain; charset=utf-8\r\n\r\n\
+            New products are available in e-banking.\r\n";
+
+        assert_eq!(ParserRegistry::default().parse_raw(raw).unwrap(), None);
+    }
+
+    #[test]
+    fn subdomains_use_the_parent_domain_parser() {
+        let registry = ParserRegistry::default();
+
+        assert_eq!(
+            registry
+                .find_for_domain("info.eurobank.gr")
+                .map(|p| p.name()),
+            Some("Eurobank")
+        );
+        assert_eq!(
+            registry.find_for_domain("WINBANK.GR").map(|p| p.name()),
+            Some("Piraeus Bank")
+        );
+        assert!(registry.find_for_domain("example.com").is_none());
+        assert!(ParserRegistry::empty()
+            .find_for_domain("eurobank.gr")
+            .is_none());
+    }
+
+    #[test]
+    fn parses_both_amount_formats() {
+        assert_eq!(parse_amount("1.234,56"), Some(Decimal::new(123456, 2)));
+        assert_eq!(parse_amount("1,234.56"), Some(Decimal::new(123456, 2)));
+        assert_eq!(parse_amount("23,45"), Some(Decimal::new(2345, 2)));
+        assert_eq!(parse_amount("12.5"), Some(Decimal::new(125, 1)));
+        assert_eq!(parse_amount("1.234"), Some(Decimal::new(1234, 0)));
+        assert_eq!(parse_amount("abc"), None);
+    }
+
+    #[test]
+    fn normalizes_currency_symbols() {
+        assert_eq!(normalize_currency("€"), "EUR");
+        assert_eq!(normalize_currency(" £ "), "GBP");
+        assert_eq!(normalize_currency("usd"), "USD");
+    }
+}
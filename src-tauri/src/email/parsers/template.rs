@@ -0,0 +1,123 @@
+use chrono::NaiveDate;
+use regex::Regex;
+
+use super::{normalize_currency, parse_amount, EmailMessage, ParsedTransaction, TransactionParser};
+
+/// Regex driven parser for banks with a fixed notification template
+///
+/// The amount pattern must have an `amount` group and may have a `currency`
+/// group, the store pattern a `store` group and the date pattern a `date` group.
+pub struct TemplateParser {
+    name: &'static str,
+    domains: &'static [&'static str],
+    amount: Regex,
+    store: Regex,
+    date: Option<(Regex, &'static [&'static str])>,
+    refund: Option<Regex>,
+    default_currency: &'static str,
+}
+
+impl TemplateParser {
+    pub fn new(
+        name: &'static str,
+        domains: &'static [&'static str],
+        amount_pattern: &str,
+        store_pattern: &str,
+    ) -> Self {
+        Self {
+            name,
+            domains,
+            amount: Regex::new(amount_pattern).expect("invalid amount pattern"),
+            store: Regex::new(store_pattern).expect("invalid store pattern"),
+            date: None,
+            refund: None,
+            default_currency: "EUR",
+        }
+    }
+
+    /// Read the transaction date from the body instead of the `Date` header
+    pub fn with_date(mut self, pattern: &str, formats: &'static [&'static str]) -> Self {
+        self.date = Some((Regex::new(pattern).expect("invalid date pattern"), formats));
+        self
+    }
+
+    /// Messages matching this pattern are refunds (stored as positive amounts)
+    pub fn with_refund(mut self, pattern: &str) -> Self {
+        self.refund = Some(Regex::new(pattern).expect("invalid refund pattern"));
+        self
+    }
+
+    pub fn with_default_currency(mut self, currency: &'static str) -> Self {
+        self.default_currency = currency;
+        self
+    }
+
+    fn parse_date(&self, body: &str) -> Option<NaiveDate> {
+        let (pattern, formats) = self.date.as_ref()?;
+        let raw = pattern
+            .captures(body)?
+            .name("date")?
+            .as_str()
+            .trim()
+            .to_string();
+
+        formats
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&raw, format).ok())
+    }
+}
+
+impl TransactionParser for TemplateParser {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn sender_domains(&self) -> &[&'static str] {
+        self.domains
+    }
+
+    fn parse(&self, email: &EmailMessage) -> Option<ParsedTransaction> {
+        let body = email.body.as_str();
+
+        let caps = self.amount.captures(body)?;
+        let amount = parse_amount(caps.name("amount")?.as_str())?;
+        let currency = caps
+            .name("currency")
+            .map(|c| normalize_currency(c.as_str()))
+            .unwrap_or_else(|| self.default_currency.to_string());
+
+        let store = self
+            .store
+            .captures(body)
+            .and_then(|c| c.name("store").map(|s| clean_store(s.as_str())))
+            .filter(|s| !s.is_empty());
+
+        let date = self.parse_date(body).or(email.date)?;
+
+        let is_refund = self
+            .refund
+            .as_ref()
+            .map(|r| r.is_match(body) || email.subject.as_deref().is_some_and(|s| r.is_match(s)))
+            .unwrap_or(false);
+
+        Some(ParsedTransaction {
+            amount: if is_refund {
+                amount.abs()
+            } else {
+                -amount.abs()
+            },
+            store,
+            date,
+            currency,
+        })
+    }
+}
+
+/// Collapse whitespace and trim trailing punctuation from a merchant name
+fn clean_store(raw: &str) -> String {
+    raw.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', ',', ';'])
+        .to_string()
+}
@@ -19,9 +19,18 @@ pub enum AppError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Validation error: {0}")]
     Validation(String),
 
     #[error("External service error: {0}")]
     ExternalService(String),
+
+    #[error("Export error: {0}")]
+    Export(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
@@ -0,0 +1,78 @@
+use crate::error::{AppError, Result};
+use crate::models::TransactionWithCategory;
+
+use super::{escape_formula, LocaleFormat, COLUMNS};
+
+/// Render transactions as CSV
+///
+/// Text columns are escaped so spreadsheet apps never run them as formulas
+pub fn write(rows: &[TransactionWithCategory], locale: &LocaleFormat) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    if locale.byte_order_mark {
+        buffer.extend_from_slice("\u{feff}".as_bytes());
+    }
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(locale.csv_delimiter)
+        .from_writer(buffer);
+
+    writer.write_record(COLUMNS).map_err(csv_error)?;
+
+    for row in rows {
+        let tx = &row.transaction;
+        writer
+            .write_record([
+                locale.format_date(tx.transaction_date),
+                locale.format_amount(tx.amount),
+                text(row.category_name.as_deref()),
+                text(tx.store.as_deref()),
+                text(tx.description.as_deref()),
+                text(tx.source.as_deref()),
+            ])
+            .map_err(csv_error)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| AppError::Export(format!("Failed to write CSV: {}", e)))
+}
+
+fn text(value: Option<&str>) -> String {
+    value.map(escape_formula).unwrap_or_default()
+}
+
+fn csv_error(e: ::csv::Error) -> AppError {
+    AppError::Export(format!("Failed to write CSV: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::exported_transaction;
+
+    #[test]
+    fn escapes_formula_like_text() {
+        let rows = [
+            exported_transaction(
+                "2026-03-01",
+                "-12.50",
+                Some("=HYPERLINK(\"http://example.com\")"),
+                Some("@SUM(A1)"),
+                None,
+            ),
+            exported_transaction("2026-03-02", "-3.10", Some("BAKERY"), Some("- bread"), None),
+        ];
+
+        let output = write(&rows, &LocaleFormat::for_locale(None)).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                "Date,Amount,Category,Store,Description,Source",
+                "2026-03-01,-12.50,,\"'=HYPERLINK(\"\"http://example.com\"\")\",'@SUM(A1),manual",
+                "2026-03-02,-3.10,,BAKERY,'- bread,manual",
+            ]
+        );
+    }
+}
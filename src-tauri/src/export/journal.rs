@@ -0,0 +1,482 @@
+//! Plain-text accounting journals (Beancount and Ledger-cli)
+//!
+//! Every category becomes an `Expenses:` or `Income:` account and each
+//! transaction is balanced against the account it was booked to
+//! (`Assets:Main-Account`, or `Liabilities:` for credit cards). Split
+//! transactions post one line per split. Both legs of a transfer between
+//! accounts go through `Assets:Transfers`, which nets to zero. Amounts are
+//! in the user's base currency.
+
+use crate::models::{Account, Category, TransactionSplit, TransactionWithCategory, User};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use uuid::Uuid;
+
+use super::base_split_amounts;
+
+/// Used for transactions whose account is not part of the export
+const ASSET_ACCOUNT: &str = "Assets:Bank";
+const TRANSFER_ACCOUNT: &str = "Assets:Transfers";
+
+/// Journal dialect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalFormat {
+    Beancount,
+    Ledger,
+}
+
+/// Render a user's transactions as a journal
+///
+/// `splits` holds the split lines by transaction id
+pub fn write(
+    format: JournalFormat,
+    user: &User,
+    transactions: &[TransactionWithCategory],
+    categories: &[Category],
+    bank_accounts: &[Account],
+    splits: &HashMap<Uuid, Vec<TransactionSplit>>,
+) -> String {
+    let by_id: HashMap<Uuid, &Category> = categories.iter().map(|c| (c.id, c)).collect();
+    let accounts_by_category: HashMap<Uuid, String> = categories
+        .iter()
+        .map(|c| {
+            (
+                c.id,
+                category_account(&category_path(c, &by_id), c.is_income),
+            )
+        })
+        .collect();
+    let accounts_by_bank_account = bank_account_names(bank_accounts);
+    let category_leg = |category_id: Option<Uuid>, amount: Decimal, note: Option<&str>| Leg {
+        account: category_id
+            .and_then(|id| accounts_by_category.get(&id).cloned())
+            .unwrap_or_else(|| category_account(&["Uncategorized"], amount.is_sign_positive())),
+        amount,
+        note: note.map(single_line),
+    };
+
+    let postings: Vec<Posting> = transactions
+        .iter()
+        .map(|row| {
+            let tx = &row.transaction;
+            let balancing = accounts_by_bank_account
+                .get(&tx.account_id)
+                .cloned()
+                .unwrap_or_else(|| ASSET_ACCOUNT.to_string());
+            if tx.transfer_id.is_some() {
+                let leg = Leg {
+                    account: TRANSFER_ACCOUNT.to_string(),
+                    amount: tx.amount,
+                    note: None,
+                };
+                return (row, vec![leg], balancing);
+            }
+            let legs = match splits.get(&tx.id).filter(|lines| !lines.is_empty()) {
+                Some(lines) => lines
+                    .iter()
+                    .zip(base_split_amounts(tx, lines))
+                    .map(|(line, amount)| {
+                        category_leg(line.category_id, amount, line.note.as_deref())
+                    })
+                    .collect(),
+                None => vec![category_leg(tx.category_id, tx.amount, None)],
+            };
+            (row, legs, balancing)
+        })
+        .collect();
+
+    // Accounts are opened on the first transaction date so the journal
+    // balances regardless of the exported date range
+    let open_date = transactions
+        .iter()
+        .map(|row| row.transaction.transaction_date)
+        .min()
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+    let mut accounts: BTreeSet<&str> = accounts_by_category.values().map(String::as_str).collect();
+    accounts.extend(accounts_by_bank_account.values().map(String::as_str));
+    for (_, legs, balancing) in &postings {
+        accounts.extend(legs.iter().map(|leg| leg.account.as_str()));
+        accounts.insert(balancing);
+    }
+
+    let currency = user.base_currency.as_str();
+    let mut out = String::new();
+    match format {
+        JournalFormat::Beancount => {
+            let _ = writeln!(out, "option \"title\" {}", quote(&user.full_name()));
+            let _ = writeln!(out, "option \"operating_currency\" \"{}\"", currency);
+            out.push('\n');
+            for account in &accounts {
+                let _ = writeln!(
+                    out,
+                    "{} open {} {}",
+                    beancount_date(open_date),
+                    account,
+                    currency
+                );
+            }
+        }
+        JournalFormat::Ledger => {
+            let _ = writeln!(out, "; {} <{}>", user.full_name(), user.email);
+            out.push('\n');
+            let _ = writeln!(out, "commodity {}", currency);
+            for account in &accounts {
+                let _ = writeln!(out, "account {}", account);
+            }
+        }
+    }
+
+    for posting in &postings {
+        out.push('\n');
+        match format {
+            JournalFormat::Beancount => write_beancount(&mut out, posting, currency),
+            JournalFormat::Ledger => write_ledger(&mut out, posting, currency),
+        }
+    }
+
+    out
+}
+
+/// Category side of a transaction (one per split line), with the
+/// transaction's sign
+struct Leg {
+    account: String,
+    amount: Decimal,
+    note: Option<String>,
+}
+
+/// A transaction with its category legs and the account balancing it
+type Posting<'a> = (&'a TransactionWithCategory, Vec<Leg>, String);
+
+fn write_beancount(out: &mut String, posting: &Posting, currency: &str) {
+    let (row, legs, balancing) = posting;
+    let tx = &row.transaction;
+    let payee = tx.store.as_deref().map(single_line).unwrap_or_default();
+    let narration = tx
+        .description
+        .as_deref()
+        .map(single_line)
+        .unwrap_or_default();
+
+    let _ = writeln!(
+        out,
+        "{} * {} {}",
+        beancount_date(tx.transaction_date),
+        quote(&payee),
+        quote(&narration)
+    );
+    if let Some(source) = &tx.source {
+        let _ = writeln!(out, "  source: {}", quote(source));
+    }
+    if let Some(message_id) = &tx.email_message_id {
+        let _ = writeln!(out, "  email_message_id: {}", quote(message_id));
+    }
+    for leg in legs {
+        let _ = writeln!(out, "  {}  {} {}", leg.account, -leg.amount, currency);
+        if let Some(note) = &leg.note {
+            let _ = writeln!(out, "    note: {}", quote(note));
+        }
+    }
+    let _ = writeln!(out, "  {}  {} {}", balancing, tx.amount, currency);
+}
+
+fn write_ledger(out: &mut String, posting: &Posting, currency: &str) {
+    let (row, legs, balancing) = posting;
+    let tx = &row.transaction;
+    // Ledger needs a payee, manual entries often only have a description
+    let (payee, comment) = match (&tx.store, &tx.description) {
+        (Some(store), description) => (single_line(store), description.as_deref()),
+        (None, Some(description)) => (single_line(description), None),
+        (None, None) if tx.transfer_id.is_some() => ("Transfer".to_string(), None),
+        (None, None) => ("Unknown".to_string(), None),
+    };
+
+    let _ = writeln!(
+        out,
+        "{} * {}",
+        tx.transaction_date.format("%Y/%m/%d"),
+        payee
+    );
+    if let Some(description) = comment {
+        let _ = writeln!(out, "    ; {}", single_line(description));
+    }
+    if let Some(source) = &tx.source {
+        let _ = writeln!(out, "    ; source: {}", single_line(source));
+    }
+    if let Some(message_id) = &tx.email_message_id {
+        let _ = writeln!(out, "    ; email_message_id: {}", single_line(message_id));
+    }
+    for leg in legs {
+        let _ = match &leg.note {
+            Some(note) => writeln!(
+                out,
+                "    {}  {} {}  ; {}",
+                leg.account, -leg.amount, currency, note
+            ),
+            None => writeln!(out, "    {}  {} {}", leg.account, -leg.amount, currency),
+        };
+    }
+    let _ = writeln!(out, "    {}  {} {}", balancing, tx.amount, currency);
+}
+
+/// Journal account per bank account, `Liabilities:` for credit cards
+///
+/// Names that clash after cleaning get a numeric suffix
+fn bank_account_names(accounts: &[Account]) -> HashMap<Uuid, String> {
+    let mut used = BTreeSet::new();
+    let mut names = HashMap::new();
+
+    for account in accounts {
+        let root = match account.account_type.as_str() {
+            "credit_card" => "Liabilities",
+            _ => "Assets",
+        };
+        let base = account_name(root, &[&account.name]);
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while name == TRANSFER_ACCOUNT || !used.insert(name.clone()) {
+            suffix += 1;
+            name = format!("{}-{}", base, suffix);
+        }
+        names.insert(account.id, name);
+    }
+
+    names
+}
+
+/// Category names from the top-level ancestor down to the category
+fn category_path<'a>(category: &'a Category, by_id: &HashMap<Uuid, &'a Category>) -> Vec<&'a str> {
+    let mut path = vec![category.name.as_str()];
+    let mut seen = BTreeSet::from([category.id]);
+    let mut parent_id = category.parent_id;
+
+    while let Some(parent) = parent_id.and_then(|id| by_id.get(&id)) {
+        if !seen.insert(parent.id) {
+            break;
+        }
+        path.push(parent.name.as_str());
+        parent_id = parent.parent_id;
+    }
+
+    path.reverse();
+    path
+}
+
+/// `Expenses:Bills:Mobile-Phone` from ["Bills", "Mobile phone"]
+fn category_account(path: &[&str], is_income: bool) -> String {
+    let root = if is_income { "Income" } else { "Expenses" };
+    account_name(root, path)
+}
+
+/// Journal account name below `root`
+///
+/// Components must start with an uppercase letter or digit and may only
+/// contain letters, digits and dashes
+fn account_name(root: &str, path: &[&str]) -> String {
+    let mut account = root.to_string();
+
+    for name in path {
+        let component = name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                let first = chars.next().unwrap_or_default();
+                first.to_uppercase().chain(chars).collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("-");
+
+        let component = match component.chars().next() {
+            Some(c) if c.is_uppercase() || c.is_ascii_digit() => component,
+            Some(_) => format!("X-{}", component),
+            None => "Uncategorized".to_string(),
+        };
+
+        account.push(':');
+        account.push_str(&component);
+    }
+
+    account
+}
+
+fn beancount_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::exported_transaction;
+    use chrono::{TimeZone, Utc};
+
+    fn uuid(n: u128) -> Uuid {
+        Uuid::from_u128(n)
+    }
+
+    fn user() -> User {
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        User {
+            id: uuid(1),
+            google_id: "google-1".to_string(),
+            email: "maria@example.com".to_string(),
+            f_name: "Maria".to_string(),
+            l_name: "Papadopoulou".to_string(),
+            photo_url: None,
+            base_currency: "EUR".to_string(),
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    fn category(id: u128, name: &str, is_income: bool, parent: Option<u128>) -> Category {
+        Category {
+            id: uuid(id),
+            user_id: uuid(1),
+            name: name.to_string(),
+            icon: None,
+            is_income,
+            parent_id: parent.map(uuid),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn account(id: u128, name: &str, account_type: &str) -> Account {
+        Account {
+            id: uuid(id),
+            user_id: uuid(1),
+            name: name.to_string(),
+            account_type: account_type.to_string(),
+            opening_balance: Default::default(),
+            currency: "EUR".to_string(),
+            is_default: id == 20,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn split(
+        transaction_id: u128,
+        category_id: Option<u128>,
+        amount: &str,
+        note: Option<&str>,
+    ) -> TransactionSplit {
+        TransactionSplit {
+            id: Uuid::new_v4(),
+            transaction_id: uuid(transaction_id),
+            category_id: category_id.map(uuid),
+            amount: amount.parse().unwrap(),
+            note: note.map(str::to_string),
+            position: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn transaction(
+        date: &str,
+        amount: &str,
+        store: Option<&str>,
+        description: Option<&str>,
+        category_id: Option<u128>,
+        account_id: u128,
+    ) -> TransactionWithCategory {
+        let mut row = exported_transaction(date, amount, store, description, None);
+        row.transaction.category_id = category_id.map(uuid);
+        row.transaction.account_id = uuid(account_id);
+        row
+    }
+
+    fn export(format: JournalFormat) -> String {
+        let categories = [
+            category(10, "Groceries", false, None),
+            category(11, "Bills", false, None),
+            category(12, "Mobile phone", false, Some(11)),
+            category(13, "Salary", true, None),
+        ];
+        let accounts = [
+            account(20, "Main account", "checking"),
+            account(21, "Visa Gold", "credit_card"),
+        ];
+
+        let mut payment = transaction("2026-03-05", "-200.00", None, None, None, 20);
+        let mut settlement = transaction("2026-03-05", "200.00", None, None, None, 21);
+        payment.transaction.transfer_id = Some(uuid(30));
+        settlement.transaction.transfer_id = Some(uuid(30));
+
+        let mut hypermarket =
+            transaction("2026-03-06", "-60.00", Some("HYPERMARKET"), None, None, 20);
+        hypermarket.transaction.id = uuid(40);
+        let hypermarket_lines = vec![
+            split(40, Some(10), "-45.00", None),
+            split(40, None, "-15.00", Some("Detergent")),
+        ];
+
+        let transactions = [
+            transaction(
+                "2026-03-01",
+                "1850.00",
+                Some("ACME LTD"),
+                Some("March salary"),
+                Some(13),
+                20,
+            ),
+            transaction(
+                "2026-03-02",
+                "-45.20",
+                Some("AB \"Vasilopoulos\""),
+                None,
+                Some(10),
+                20,
+            ),
+            transaction(
+                "2026-03-03",
+                "-25.00",
+                None,
+                Some("Cosmote bill\nMarch"),
+                Some(12),
+                21,
+            ),
+            transaction("2026-03-04", "-3.50", Some("KIOSK"), None, None, 20),
+            payment,
+            settlement,
+            hypermarket,
+        ];
+
+        write(
+            format,
+            &user(),
+            &transactions,
+            &categories,
+            &accounts,
+            &HashMap::from([(uuid(40), hypermarket_lines)]),
+        )
+    }
+
+    #[test]
+    fn beancount_matches_golden_file() {
+        assert_eq!(
+            export(JournalFormat::Beancount),
+            include_str!("../../tests/fixtures/journal/export.beancount")
+        );
+    }
+
+    #[test]
+    fn ledger_matches_golden_file() {
+        assert_eq!(
+            export(JournalFormat::Ledger),
+            include_str!("../../tests/fixtures/journal/export.ledger")
+        );
+    }
+}
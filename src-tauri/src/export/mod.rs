@@ -0,0 +1,130 @@
+//! Transaction exporters
+//!
+//! Writers turn stored transactions (with their categories) into file
+//! formats other tools can read. File handling is done by `ExportService`.
+
+pub mod csv;
+pub mod journal;
+pub mod qif;
+pub mod xlsx;
+
+use crate::models::{Transaction, TransactionSplit};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// Spreadsheet export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+/// Column headers shared by the spreadsheet writers
+pub const COLUMNS: [&str; 6] = [
+    "Date",
+    "Amount",
+    "Category",
+    "Store",
+    "Description",
+    "Source",
+];
+
+/// Whether spreadsheet apps would evaluate a text cell as a formula
+///
+/// Payees and descriptions come from bank files and emails, so a value
+/// like `=HYPERLINK(...)` must never reach a spreadsheet as a formula.
+pub fn is_formula_like(text: &str) -> bool {
+    text.starts_with(['=', '+', '-', '@', '\t', '\r'])
+}
+
+/// Prefix formula-like text with `'` so spreadsheet apps show it as text
+pub fn escape_formula(text: &str) -> String {
+    if is_formula_like(text) {
+        format!("'{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// A split transaction's line amounts in the base currency, in line order
+///
+/// Lines are converted with the transaction's rate; the last one takes the
+/// rounding difference so they add up to the transaction amount.
+pub fn base_split_amounts(tx: &Transaction, splits: &[TransactionSplit]) -> Vec<Decimal> {
+    let rate = tx.fx_rate.unwrap_or(Decimal::ONE);
+    let mut amounts: Vec<Decimal> = splits
+        .iter()
+        .map(|split| (split.amount * rate).round_dp(2))
+        .collect();
+    let total: Decimal = amounts.iter().sum();
+    if let Some(last) = amounts.last_mut() {
+        *last += tx.amount - total;
+    }
+
+    amounts
+}
+
+/// Number/date formatting for text exports
+///
+/// Without a locale the output is machine friendly (ISO dates, `.` decimals).
+/// Amounts are never rounded or grouped, only the decimal separator changes.
+#[derive(Debug, Clone)]
+pub struct LocaleFormat {
+    pub decimal_separator: char,
+    pub csv_delimiter: u8,
+    pub date_format: &'static str,
+    /// Prepend a UTF-8 BOM so spreadsheet apps detect the encoding
+    pub byte_order_mark: bool,
+}
+
+impl LocaleFormat {
+    /// Formatting for a BCP 47 locale such as `el-GR` or `en-US`
+    pub fn for_locale(locale: Option<&str>) -> Self {
+        let Some(locale) = locale else {
+            return Self {
+                decimal_separator: '.',
+                csv_delimiter: b',',
+                date_format: "%Y-%m-%d",
+                byte_order_mark: false,
+            };
+        };
+
+        let normalized = locale.replace('_', "-").to_lowercase();
+        let language = normalized.split('-').next().unwrap_or_default();
+
+        match language {
+            // Comma decimals use ';' as the list separator in spreadsheet apps
+            "el" | "de" | "fr" | "es" | "it" | "nl" | "pt" => Self {
+                decimal_separator: ',',
+                csv_delimiter: b';',
+                date_format: "%d/%m/%Y",
+                byte_order_mark: true,
+            },
+            _ => Self {
+                decimal_separator: '.',
+                csv_delimiter: b',',
+                date_format: if normalized == "en-us" {
+                    "%m/%d/%Y"
+                } else {
+                    "%d/%m/%Y"
+                },
+                byte_order_mark: true,
+            },
+        }
+    }
+
+    pub fn format_amount(&self, amount: Decimal) -> String {
+        let text = amount.to_string();
+        if self.decimal_separator == '.' {
+            text
+        } else {
+            text.replace('.', &self.decimal_separator.to_string())
+        }
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(self.date_format).to_string()
+    }
+}
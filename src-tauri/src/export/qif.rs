@@ -0,0 +1,137 @@
+//! QIF (Quicken Interchange Format) writer
+//!
+//! Writes a `!Type:Cat` category list followed by a single `!Type:Bank`
+//! section, in the same layout `import::qif` reads back. Split transactions
+//! get one `S`/`E`/`$` group per line.
+
+use crate::models::{Category, TransactionSplit, TransactionWithCategory};
+use std::collections::HashMap;
+use std::fmt::Write;
+use uuid::Uuid;
+
+use super::base_split_amounts;
+
+/// Render transactions as a QIF document
+///
+/// `splits` holds the split lines by transaction id
+pub fn write(
+    transactions: &[TransactionWithCategory],
+    categories: &[Category],
+    splits: &HashMap<Uuid, Vec<TransactionSplit>>,
+) -> String {
+    let names: HashMap<Uuid, &str> = categories.iter().map(|c| (c.id, c.name.as_str())).collect();
+    let mut out = String::new();
+
+    if !categories.is_empty() {
+        out.push_str("!Type:Cat\n");
+        for category in categories {
+            let _ = writeln!(out, "N{}", clean(&category.name));
+            out.push_str(if category.is_income { "I\n" } else { "E\n" });
+            out.push_str("^\n");
+        }
+    }
+
+    out.push_str("!Type:Bank\n");
+    for row in transactions {
+        let tx = &row.transaction;
+        let _ = writeln!(out, "D{}", tx.transaction_date.format("%m/%d/%Y"));
+        let _ = writeln!(out, "T{}", tx.amount);
+
+        if let Some(store) = &tx.store {
+            let _ = writeln!(out, "P{}", clean(store));
+        }
+        if let Some(description) = &tx.description {
+            let _ = writeln!(out, "M{}", clean(description));
+        }
+
+        match splits.get(&tx.id).filter(|lines| !lines.is_empty()) {
+            Some(lines) => {
+                for (line, amount) in lines.iter().zip(base_split_amounts(tx, lines)) {
+                    let category = line.category_id.and_then(|id| names.get(&id));
+                    let _ = writeln!(out, "S{}", category.map(|c| clean(c)).unwrap_or_default());
+                    if let Some(note) = &line.note {
+                        let _ = writeln!(out, "E{}", clean(note));
+                    }
+                    let _ = writeln!(out, "${}", amount);
+                }
+            }
+            None => {
+                if let Some(category) = &row.category_name {
+                    let _ = writeln!(out, "L{}", clean(category));
+                }
+            }
+        }
+
+        out.push_str("^\n");
+    }
+
+    out
+}
+
+/// QIF is line based, keep every value on a single line
+fn clean(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::exported_transaction;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    fn category(id: u128, name: &str) -> Category {
+        Category {
+            id: Uuid::from_u128(id),
+            user_id: Uuid::nil(),
+            name: name.to_string(),
+            icon: None,
+            is_income: false,
+            parent_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn split(category_id: Option<u128>, amount: &str, note: Option<&str>) -> TransactionSplit {
+        TransactionSplit {
+            id: Uuid::nil(),
+            transaction_id: Uuid::from_u128(1),
+            category_id: category_id.map(Uuid::from_u128),
+            amount: amount.parse().unwrap(),
+            note: note.map(str::to_string),
+            position: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn split_lines_are_read_back_by_the_importer() {
+        let mut row = exported_transaction("2026-03-05", "-60.00", Some("HYPERMARKET"), None, None);
+        row.transaction.id = Uuid::from_u128(1);
+        let categories = [category(10, "Groceries"), category(11, "Household")];
+        let splits = HashMap::from([(
+            Uuid::from_u128(1),
+            vec![
+                split(Some(10), "-45.00", None),
+                split(Some(11), "-15.00", Some("Detergent")),
+            ],
+        )]);
+
+        let output = write(&[row], &categories, &splits);
+        let preview = crate::import::qif::parse(output.as_bytes()).unwrap();
+
+        assert_eq!(preview.rows.len(), 1);
+        let lines: Vec<(Decimal, Option<&str>, Option<&str>)> = preview.rows[0]
+            .splits
+            .iter()
+            .map(|s| (s.amount, s.category.as_deref(), s.note.as_deref()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (Decimal::new(-4500, 2), Some("Groceries"), None),
+                (Decimal::new(-1500, 2), Some("Household"), Some("Detergent")),
+            ]
+        );
+    }
+}
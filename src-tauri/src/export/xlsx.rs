@@ -0,0 +1,131 @@
+use crate::error::{AppError, Result};
+use crate::models::TransactionWithCategory;
+use chrono::Datelike;
+use rust_decimal::Decimal;
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
+
+use super::{is_formula_like, COLUMNS};
+
+/// Significant digits Excel keeps for a number
+const EXCEL_DIGITS: u32 = 15;
+
+/// Render transactions as an XLSX workbook
+///
+/// Dates and amounts are written as native cells so the spreadsheet app
+/// formats them for the user's locale
+pub fn write(rows: &[TransactionWithCategory]) -> Result<Vec<u8>> {
+    let amounts = rows
+        .iter()
+        .map(|row| excel_number(row.transaction.amount))
+        .collect::<Result<Vec<f64>>>()?;
+
+    build(rows, &amounts).map_err(|e| AppError::Export(format!("Failed to write XLSX: {}", e)))
+}
+
+/// Convert an amount to the double Excel stores
+///
+/// Excel shows numbers with 15 significant digits, so an amount with at
+/// most 15 digits reads back unchanged. Longer amounts are rejected rather
+/// than silently rounded.
+fn excel_number(amount: Decimal) -> Result<f64> {
+    let digits = amount
+        .normalize()
+        .mantissa()
+        .unsigned_abs()
+        .to_string()
+        .len() as u32;
+    if digits > EXCEL_DIGITS {
+        return Err(AppError::Export(format!(
+            "Amount {} has more digits than a spreadsheet can hold",
+            amount
+        )));
+    }
+
+    amount
+        .to_string()
+        .parse()
+        .map_err(|_| AppError::Export(format!("Amount {} is not a valid number", amount)))
+}
+
+fn build(
+    rows: &[TransactionWithCategory],
+    amounts: &[f64],
+) -> std::result::Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+    // Text that looks like a formula stays text (shown without the quote)
+    let text_format = Format::new().set_quote_prefix();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    let amount_format = Format::new().set_num_format("#,##0.00");
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Transactions")?;
+
+    for (col, title) in COLUMNS.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *title, &header_format)?;
+    }
+
+    for (index, (row, amount)) in rows.iter().zip(amounts).enumerate() {
+        let tx = &row.transaction;
+        let r = index as u32 + 1;
+
+        let date = ExcelDateTime::from_ymd(
+            tx.transaction_date.year() as u16,
+            tx.transaction_date.month() as u8,
+            tx.transaction_date.day() as u8,
+        )?;
+        worksheet.write_datetime_with_format(r, 0, &date, &date_format)?;
+
+        worksheet.write_number_with_format(r, 1, *amount, &amount_format)?;
+
+        let text_columns = [
+            (2, row.category_name.as_deref()),
+            (3, tx.store.as_deref()),
+            (4, tx.description.as_deref()),
+            (5, tx.source.as_deref()),
+        ];
+        for (col, value) in text_columns {
+            match value {
+                Some(value) if is_formula_like(value) => {
+                    worksheet.write_string_with_format(r, col, value, &text_format)?;
+                }
+                Some(value) => {
+                    worksheet.write_string(r, col, value)?;
+                }
+                None => {}
+            }
+        }
+    }
+
+    worksheet.set_column_width(0, 12)?;
+    worksheet.set_column_width(1, 12)?;
+    worksheet.set_column_width(2, 18)?;
+    worksheet.set_column_width(3, 30)?;
+    worksheet.set_column_width(4, 40)?;
+    worksheet.autofilter(0, 0, rows.len() as u32, (COLUMNS.len() - 1) as u16)?;
+
+    workbook.save_to_buffer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_read_back_unchanged() {
+        for raw in ["-12.30", "0.10", "9999999999.99", "1234567890123.45"] {
+            let amount: Decimal = raw.parse().unwrap();
+            let number = excel_number(amount).unwrap();
+
+            let shown: Decimal = format!("{:.2}", number).parse().unwrap();
+            assert_eq!(shown, amount);
+        }
+    }
+
+    #[test]
+    fn rejects_amounts_excel_would_round() {
+        let amount: Decimal = "12345678901234.567".parse().unwrap();
+
+        assert!(matches!(excel_number(amount), Err(AppError::Export(_))));
+    }
+}
@@ -0,0 +1,157 @@
+//! European Central Bank euro reference rates
+//!
+//! Reads `eurofxref-daily.xml`, `eurofxref-hist-90d.xml` or
+//! `eurofxref-hist.xml` as published at
+//! <https://www.ecb.europa.eu/stats/eurofxref/>:
+//!
+//! ```xml
+//! <Cube>
+//!   <Cube time="2024-01-05">
+//!     <Cube currency="USD" rate="1.0921"/>
+//!   </Cube>
+//! </Cube>
+//! ```
+
+use super::RateProvider;
+use crate::error::{AppError, Result};
+use crate::models::FxRate;
+use chrono::NaiveDate;
+use roxmltree::Document;
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Value stored in `fx_rates.source`
+pub const SOURCE: &str = "ecb";
+/// ECB rates are quoted against the euro
+const BASE: &str = "EUR";
+
+/// Rates from a downloaded ECB XML file
+pub struct EcbFileProvider {
+    path: PathBuf,
+}
+
+impl EcbFileProvider {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl RateProvider for EcbFileProvider {
+    fn name(&self) -> &str {
+        SOURCE
+    }
+
+    fn fetch(&self) -> Result<Vec<FxRate>> {
+        parse(&std::fs::read(&self.path)?)
+    }
+}
+
+/// Parse an ECB reference rate file
+pub fn parse(content: &[u8]) -> Result<Vec<FxRate>> {
+    let text = String::from_utf8_lossy(content);
+    let text = text.trim_start_matches('\u{feff}');
+
+    let doc = Document::parse(text)
+        .map_err(|e| AppError::Validation(format!("Invalid ECB rates XML: {}", e)))?;
+
+    let mut rates = Vec::new();
+    let days = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("Cube") && n.has_attribute("time"));
+    for day in days {
+        let raw_date = day.attribute("time").unwrap_or_default();
+        let rate_date = NaiveDate::parse_from_str(raw_date, "%Y-%m-%d")
+            .map_err(|_| AppError::Validation(format!("Invalid ECB date '{}'", raw_date)))?;
+
+        for cube in day.children().filter(|n| n.has_tag_name("Cube")) {
+            let (Some(currency), Some(raw_rate)) =
+                (cube.attribute("currency"), cube.attribute("rate"))
+            else {
+                continue;
+            };
+            let rate = Decimal::from_str(raw_rate.trim())
+                .ok()
+                .filter(|rate| *rate > Decimal::ZERO)
+                .ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "Invalid ECB rate '{}' for {} on {}",
+                        raw_rate, currency, rate_date
+                    ))
+                })?;
+
+            rates.push(FxRate {
+                base: BASE.to_string(),
+                quote: currency.trim().to_uppercase(),
+                rate_date,
+                rate,
+                source: SOURCE.to_string(),
+            });
+        }
+    }
+
+    if rates.is_empty() {
+        return Err(AppError::Validation(
+            "No exchange rates found, expected an ECB eurofxref XML file".to_string(),
+        ));
+    }
+
+    Ok(rates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rates_per_day() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+  <Cube>
+    <Cube time="2026-03-03">
+      <Cube currency="USD" rate="1.0850"/>
+      <Cube currency="GBP" rate="0.8412"/>
+    </Cube>
+    <Cube time="2026-03-02">
+      <Cube currency="USD" rate="1.0821"/>
+    </Cube>
+  </Cube>
+</gesmes:Envelope>"#;
+
+        let rates: Vec<(String, String, Decimal)> = parse(xml.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.quote, r.rate_date.to_string(), r.rate))
+            .collect();
+
+        assert_eq!(
+            rates,
+            vec![
+                (
+                    "USD".to_string(),
+                    "2026-03-03".to_string(),
+                    Decimal::new(10850, 4)
+                ),
+                (
+                    "GBP".to_string(),
+                    "2026-03-03".to_string(),
+                    Decimal::new(8412, 4)
+                ),
+                (
+                    "USD".to_string(),
+                    "2026-03-02".to_string(),
+                    Decimal::new(10821, 4)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_files_without_rates_and_invalid_rates() {
+        assert!(parse(b"<Cube></Cube>").is_err());
+        assert!(parse(
+            br#"<Cube><Cube time="2026-03-03"><Cube currency="USD" rate="-1"/></Cube></Cube>"#
+        )
+        .is_err());
+    }
+}
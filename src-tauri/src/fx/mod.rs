@@ -0,0 +1,57 @@
+//! Exchange rates
+//!
+//! Rates are loaded into `fx_rates` by a `RateProvider`; the default one
+//! reads the European Central Bank's reference rate files, which can be
+//! downloaded once and imported offline. Conversion itself happens in the
+//! database (see the `fx_rate` SQL function) when transactions are saved.
+
+pub mod ecb;
+
+use crate::error::{AppError, Result};
+use crate::models::FxRate;
+
+pub use ecb::EcbFileProvider;
+
+/// Source of exchange rates
+pub trait RateProvider: Send + Sync {
+    /// Provider name, stored with every rate
+    fn name(&self) -> &str;
+
+    /// Load the rates the provider has available
+    fn fetch(&self) -> Result<Vec<FxRate>>;
+}
+
+/// Validate an ISO 4217 currency code, returned uppercased
+pub fn normalize_code(code: &str) -> Result<String> {
+    let code = code.trim();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(AppError::Validation(format!(
+            "Invalid currency code '{}'",
+            code
+        )));
+    }
+
+    Ok(code.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_codes_to_uppercase() {
+        assert_eq!(normalize_code(" eur ").unwrap(), "EUR");
+        assert_eq!(normalize_code("Usd").unwrap(), "USD");
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        for code in ["", "EU", "EURO", "E1R", "€UR", "ευρ", "aß"] {
+            assert!(
+                matches!(normalize_code(code), Err(AppError::Validation(_))),
+                "{}",
+                code
+            );
+        }
+    }
+}
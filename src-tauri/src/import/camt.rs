@@ -0,0 +1,235 @@
+//! ISO 20022 camt.053 (statement) / camt.052 (intraday report) parser
+//!
+//! One row is produced per `Ntry`. Elements are matched by local name only
+//! so all message versions (`camt.053.001.02` ... `.08`) are handled the same way.
+
+use crate::error::{AppError, Result};
+use chrono::NaiveDate;
+use roxmltree::{Document, Node};
+
+use super::{non_empty, parse_decimal, ImportPreview, RowError, StatementRow};
+
+/// Parse a camt.052 or camt.053 XML file
+pub fn parse(content: &[u8]) -> Result<ImportPreview> {
+    let text = String::from_utf8_lossy(content);
+    // roxmltree rejects a leading byte order mark
+    let text = text.trim_start_matches('\u{feff}');
+
+    let doc = Document::parse(text)
+        .map_err(|e| AppError::Validation(format!("Invalid camt XML: {}", e)))?;
+
+    let is_camt = doc
+        .descendants()
+        .any(|n| n.has_tag_name("BkToCstmrStmt") || n.has_tag_name("BkToCstmrAcctRpt"));
+    if !is_camt {
+        return Err(AppError::Validation(
+            "Not a camt.052/camt.053 file".to_string(),
+        ));
+    }
+
+    let mut preview = ImportPreview::default();
+
+    let entries = doc.descendants().filter(|n| n.has_tag_name("Ntry"));
+    for (index, entry) in entries.enumerate() {
+        let record = index + 1;
+
+        // Intraday reports can contain pending entries, only import booked ones
+        if !is_booked(entry) {
+            continue;
+        }
+
+        match parse_entry(entry, record) {
+            Ok(row) => preview.rows.push(row),
+            Err(message) => preview.errors.push(RowError {
+                line: record,
+                message,
+            }),
+        }
+    }
+
+    Ok(preview)
+}
+
+fn parse_entry(entry: Node, record: usize) -> std::result::Result<StatementRow, String> {
+    let raw_amount = path_text(entry, &["Amt"]).ok_or("Missing Amt")?;
+    let amount =
+        parse_decimal(&raw_amount, '.').ok_or_else(|| format!("Invalid Amt '{}'", raw_amount))?;
+
+    let is_debit = match path_text(entry, &["CdtDbtInd"]).as_deref() {
+        Some("DBIT") => true,
+        Some("CRDT") => false,
+        other => return Err(format!("Invalid CdtDbtInd {:?}", other)),
+    };
+    let amount = if is_debit {
+        -amount.abs()
+    } else {
+        amount.abs()
+    };
+
+    let transaction_date = parse_date(entry, "BookgDt")
+        .or_else(|| parse_date(entry, "ValDt"))
+        .ok_or("Missing BookgDt")?;
+    let value_date = parse_date(entry, "ValDt");
+
+    let details = path(entry, &["NtryDtls", "TxDtls"]);
+
+    // The counterparty is the creditor for payments and the debtor for receipts
+    let party = if is_debit { "Cdtr" } else { "Dbtr" };
+    let counterparty = details.and_then(|d| {
+        path_text(d, &["RltdPties", party, "Nm"])
+            .or_else(|| path_text(d, &["RltdPties", party, "Pty", "Nm"]))
+    });
+
+    let remittance = details
+        .and_then(|d| path(d, &["RmtInf"]))
+        .map(|rmt| {
+            rmt.children()
+                .filter(|n| n.has_tag_name("Ustrd"))
+                .filter_map(|n| n.text())
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .and_then(|s| non_empty(&s))
+        .or_else(|| path_text(entry, &["AddtlNtryInf"]));
+
+    let bank_reference = path_text(entry, &["AcctSvcrRef"])
+        .or_else(|| details.and_then(|d| path_text(d, &["Refs", "AcctSvcrRef"])))
+        .or_else(|| details.and_then(|d| path_text(d, &["Refs", "TxId"])))
+        .or_else(|| path_text(entry, &["NtryRef"]));
+
+    Ok(StatementRow {
+        line: record,
+        transaction_date,
+        value_date,
+        amount,
+        store: counterparty,
+        description: remittance,
+        external_id: bank_reference,
+        category: None,
+        splits: Vec::new(),
+    })
+}
+
+fn is_booked(entry: Node) -> bool {
+    // camt.053.001.02 uses <Sts>BOOK</Sts>, later versions <Sts><Cd>BOOK</Cd></Sts>
+    match path_text(entry, &["Sts"]).or_else(|| path_text(entry, &["Sts", "Cd"])) {
+        Some(status) => status == "BOOK",
+        None => true,
+    }
+}
+
+/// `<BookgDt><Dt>` or `<BookgDt><DtTm>`
+fn parse_date(entry: Node, element: &str) -> Option<NaiveDate> {
+    let raw =
+        path_text(entry, &[element, "Dt"]).or_else(|| path_text(entry, &[element, "DtTm"]))?;
+    NaiveDate::parse_from_str(raw.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Follow a chain of child elements by local name
+fn path<'a, 'input>(node: Node<'a, 'input>, names: &[&str]) -> Option<Node<'a, 'input>> {
+    names.iter().try_fold(node, |current, name| {
+        current.children().find(|n| n.has_tag_name(*name))
+    })
+}
+
+/// Trimmed, non-empty text of a child element chain
+fn path_text(node: Node, names: &[&str]) -> Option<String> {
+    path(node, names)?.text().and_then(non_empty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    const STATEMENT: &str = include_str!("../../tests/fixtures/camt/statement_v02.xml");
+    const REPORT: &str = include_str!("../../tests/fixtures/camt/report_v08.xml");
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn reads_every_entry_of_every_statement() {
+        let preview = parse(STATEMENT.as_bytes()).unwrap();
+
+        assert!(preview.errors.is_empty());
+        let rows: Vec<(Decimal, Option<&str>, Option<&str>)> = preview
+            .rows
+            .iter()
+            .map(|row| (row.amount, row.store.as_deref(), row.external_id.as_deref()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    Decimal::new(-4520, 2),
+                    Some("SUPERMARKET"),
+                    Some("REF-1001")
+                ),
+                (
+                    Decimal::new(150000, 2),
+                    Some("EMPLOYER SA"),
+                    Some("TX-1002")
+                ),
+                (Decimal::new(-2000, 2), None, Some("NTRY-2001")),
+            ]
+        );
+        assert_eq!(
+            preview.rows[0].description.as_deref(),
+            Some("Card payment 0231")
+        );
+        assert_eq!(preview.rows[1].description.as_deref(), Some("Salary March"));
+    }
+
+    #[test]
+    fn booking_date_is_the_transaction_date() {
+        let preview = parse(STATEMENT.as_bytes()).unwrap();
+
+        let dates: Vec<(NaiveDate, Option<NaiveDate>)> = preview
+            .rows
+            .iter()
+            .map(|row| (row.transaction_date, row.value_date))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                (date("2026-03-02"), Some(date("2026-03-01"))),
+                (date("2026-03-05"), Some(date("2026-03-05"))),
+                // Without a booking date the value date is used
+                (date("2026-03-07"), Some(date("2026-03-07"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefixed_reports_skip_pending_entries() {
+        let preview = parse(REPORT.as_bytes()).unwrap();
+
+        assert!(preview.errors.is_empty());
+        assert_eq!(preview.rows.len(), 1);
+        let row = &preview.rows[0];
+        assert_eq!(row.amount, Decimal::new(-1250, 2));
+        assert_eq!(row.transaction_date, date("2026-03-09"));
+        assert_eq!(row.value_date, None);
+        assert_eq!(row.store.as_deref(), Some("CAFE"));
+    }
+
+    #[test]
+    fn reports_entries_without_a_credit_debit_indicator() {
+        let file = STATEMENT.replacen("<CdtDbtInd>DBIT</CdtDbtInd>", "", 1);
+
+        let preview = parse(file.as_bytes()).unwrap();
+
+        assert_eq!(preview.rows.len(), 2);
+        assert_eq!(preview.errors.len(), 1);
+        assert_eq!(preview.errors[0].line, 1);
+    }
+
+    #[test]
+    fn rejects_other_xml() {
+        assert!(parse(b"<Document><Other/></Document>").is_err());
+        assert!(parse(b"not xml").is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use crate::error::{AppError, Result};
+use crate::models::CsvImportProfile;
+use chrono::NaiveDate;
+use encoding_rs::Encoding;
+use uuid::Uuid;
+
+use super::{non_empty, parse_decimal, ContentIds, ImportPreview, RowError, StatementRow};
+
+/// Resolved column indexes for a profile
+struct ColumnMap {
+    date: usize,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+    payee: Option<usize>,
+    description: Option<usize>,
+}
+
+/// Parse a CSV statement using a mapping profile
+///
+/// `account_id` scopes the derived ids, so the same row exported for two
+/// accounts is two transactions
+pub fn parse(
+    content: &[u8],
+    profile: &CsvImportProfile,
+    account_id: Option<Uuid>,
+) -> Result<ImportPreview> {
+    let text = decode(content, &profile.encoding)?;
+    let delimiter = single_byte(&profile.delimiter, "delimiter")?;
+    let decimal_separator = profile.decimal_separator.chars().next().unwrap_or(',');
+
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let mut records = reader.records();
+
+    let headers: Vec<String> = if profile.has_header {
+        match records.next() {
+            Some(header) => header
+                .map_err(|e| AppError::Validation(format!("Invalid CSV header: {}", e)))?
+                .iter()
+                .map(|h| h.trim().to_string())
+                .collect(),
+            None => return Ok(ImportPreview::default()),
+        }
+    } else {
+        Vec::new()
+    };
+
+    let columns = ColumnMap {
+        date: resolve_column(&headers, &profile.date_column)?,
+        amount: resolve_optional(&headers, &profile.amount_column)?,
+        debit: resolve_optional(&headers, &profile.debit_column)?,
+        credit: resolve_optional(&headers, &profile.credit_column)?,
+        payee: resolve_optional(&headers, &profile.payee_column)?,
+        description: resolve_optional(&headers, &profile.description_column)?,
+    };
+
+    let mut preview = ImportPreview::default();
+    let mut ids = ContentIds::default();
+
+    let account = account_id.map(|id| id.to_string()).unwrap_or_default();
+
+    // The reader skips empty lines and counts them wrong, so line numbers
+    // come from byte offsets (which point before any skipped empty lines).
+    // Records come in order, so newlines are counted from the previous one.
+    let bytes = text.as_bytes();
+    let (mut counted, mut newlines) = (0, 0);
+    let mut line_at = |position: Option<&::csv::Position>| {
+        let mut start = position.map_or(0, |p| p.byte() as usize);
+        while bytes.get(start).is_some_and(|b| matches!(b, b'\r' | b'\n')) {
+            start += 1;
+        }
+        if start < counted {
+            (counted, newlines) = (0, 0);
+        }
+        newlines += bytes[counted..start]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        counted = start;
+        newlines + 1
+    };
+
+    for record in records {
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                preview.errors.push(RowError {
+                    line: line_at(e.position()),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = line_at(record.position());
+
+        // Skip blank lines (common at the end of bank exports)
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+
+        match parse_record(&record, &columns, profile, decimal_separator, line) {
+            Ok(mut row) => {
+                // CSV exports carry no transaction id, derive one for
+                // re-imports (independent of the profile, which may be recreated)
+                row.external_id = Some(ids.next(&[
+                    &account,
+                    &row.transaction_date.to_string(),
+                    &row.amount.normalize().to_string(),
+                    row.store.as_deref().unwrap_or_default(),
+                    row.description.as_deref().unwrap_or_default(),
+                ]));
+                preview.rows.push(row);
+            }
+            Err(message) => preview.errors.push(RowError { line, message }),
+        }
+    }
+
+    Ok(preview)
+}
+
+fn parse_record(
+    record: &::csv::StringRecord,
+    columns: &ColumnMap,
+    profile: &CsvImportProfile,
+    decimal_separator: char,
+    line: usize,
+) -> std::result::Result<StatementRow, String> {
+    let field = |index: Option<usize>| index.and_then(|i| record.get(i)).and_then(non_empty);
+
+    let raw_date = field(Some(columns.date)).ok_or("Missing date")?;
+    let transaction_date = NaiveDate::parse_from_str(&raw_date, &profile.date_format)
+        .map_err(|e| format!("Invalid date '{}': {}", raw_date, e))?;
+
+    let amount = match columns.amount {
+        Some(_) => {
+            let raw = field(columns.amount).ok_or("Missing amount")?;
+            parse_decimal(&raw, decimal_separator)
+                .ok_or_else(|| format!("Invalid amount '{}'", raw))?
+        }
+        None => {
+            let debit = field(columns.debit)
+                .map(|raw| {
+                    parse_decimal(&raw, decimal_separator)
+                        .ok_or_else(|| format!("Invalid debit '{}'", raw))
+                })
+                .transpose()?;
+            let credit = field(columns.credit)
+                .map(|raw| {
+                    parse_decimal(&raw, decimal_separator)
+                        .ok_or_else(|| format!("Invalid credit '{}'", raw))
+                })
+                .transpose()?;
+
+            match (debit, credit) {
+                (Some(d), _) if !d.is_zero() => -d.abs(),
+                (_, Some(c)) if !c.is_zero() => c.abs(),
+                _ => return Err("Missing debit/credit amount".to_string()),
+            }
+        }
+    };
+
+    Ok(StatementRow {
+        line,
+        transaction_date,
+        value_date: None,
+        amount,
+        store: field(columns.payee),
+        description: field(columns.description),
+        external_id: None,
+        category: None,
+        splits: Vec::new(),
+    })
+}
+
+/// Decode file content with the profile's encoding (e.g. `windows-1253`)
+fn decode(content: &[u8], label: &str) -> Result<String> {
+    let encoding = Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| AppError::Validation(format!("Unknown encoding '{}'", label)))?;
+
+    let (text, _, had_errors) = encoding.decode(content);
+    if had_errors {
+        tracing::warn!(
+            "CSV file contains characters invalid for {}",
+            encoding.name()
+        );
+    }
+
+    Ok(text.into_owned())
+}
+
+fn single_byte(value: &str, field: &str) -> Result<u8> {
+    match value.as_bytes() {
+        [b] => Ok(*b),
+        _ => Err(AppError::Validation(format!(
+            "CSV {} must be a single character",
+            field
+        ))),
+    }
+}
+
+/// Find a column by header name (case-insensitive) or 0-based index
+fn resolve_column(headers: &[String], column: &str) -> Result<usize> {
+    let column = column.trim();
+    let wanted = column.to_lowercase();
+
+    if let Some(index) = headers.iter().position(|h| h.to_lowercase() == wanted) {
+        return Ok(index);
+    }
+
+    column
+        .parse::<usize>()
+        .map_err(|_| AppError::Validation(format!("Column '{}' not found in CSV header", column)))
+}
+
+fn resolve_optional(headers: &[String], column: &Option<String>) -> Result<Option<usize>> {
+    column
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| resolve_column(headers, c))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn profile() -> CsvImportProfile {
+        CsvImportProfile {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Bank".to_string(),
+            delimiter: ";".to_string(),
+            has_header: true,
+            date_column: "Date".to_string(),
+            amount_column: None,
+            debit_column: Some("Debit".to_string()),
+            credit_column: Some("Credit".to_string()),
+            payee_column: Some("Payee".to_string()),
+            description_column: None,
+            date_format: "%d/%m/%Y".to_string(),
+            decimal_separator: ",".to_string(),
+            encoding: "utf-8".to_string(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    const STATEMENT: &str = "Date;Payee;Debit;Credit\n\
+        01/03/2026;COFFEE ISLAND;2,50;\n\
+        01/03/2026;COFFEE ISLAND;2,50;\n\
+        02/03/2026;SALARY;;1.850,00\n\
+        \n\
+        03/03/2026;BROKEN;abc;\n";
+
+    #[test]
+    fn parses_debit_and_credit_columns() {
+        let preview = parse(STATEMENT.as_bytes(), &profile(), None).unwrap();
+
+        let amounts: Vec<Decimal> = preview.rows.iter().map(|row| row.amount).collect();
+        assert_eq!(
+            amounts,
+            vec![
+                Decimal::new(-250, 2),
+                Decimal::new(-250, 2),
+                Decimal::new(185000, 2)
+            ]
+        );
+        assert_eq!(preview.rows[2].store.as_deref(), Some("SALARY"));
+        assert_eq!(preview.errors.len(), 1);
+        assert_eq!(preview.errors[0].line, 6);
+    }
+
+    #[test]
+    fn identical_rows_get_distinct_stable_ids() {
+        let account_id = Some(Uuid::new_v4());
+        let first = parse(STATEMENT.as_bytes(), &profile(), account_id).unwrap();
+        // A recreated profile reads the same rows
+        let again = parse(STATEMENT.as_bytes(), &profile(), account_id).unwrap();
+
+        let ids: Vec<_> = first
+            .rows
+            .iter()
+            .map(|row| row.external_id.clone())
+            .collect();
+        assert!(ids.iter().all(Option::is_some));
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(
+            ids,
+            again
+                .rows
+                .iter()
+                .map(|row| row.external_id.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ids_differ_between_accounts() {
+        let ids = |account_id| {
+            parse(STATEMENT.as_bytes(), &profile(), Some(account_id))
+                .unwrap()
+                .rows
+                .into_iter()
+                .map(|row| row.external_id)
+                .collect::<Vec<_>>()
+        };
+
+        let first = ids(Uuid::new_v4());
+        let second = ids(Uuid::new_v4());
+        assert!(first.iter().zip(&second).all(|(a, b)| a != b));
+    }
+}
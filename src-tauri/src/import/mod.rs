@@ -0,0 +1,182 @@
+//! Statement file importers
+//!
+//! Each format turns a bank export into `StatementRow`s. Rows are shown to
+//! the user as an `ImportPreview` and persisted by `ImportService`.
+
+pub mod camt;
+pub mod csv;
+pub mod mt940;
+pub mod ofx;
+pub mod qif;
+
+use crate::error::Result;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::CreateTransaction;
+
+/// Statement formats that need no mapping profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementFormat {
+    /// OFX 1.x/2.x and QFX
+    Ofx,
+    /// ISO 20022 camt.053 statements and camt.052 reports
+    Camt,
+    /// SWIFT MT940 statements
+    Mt940,
+    /// Quicken Interchange Format (bank, credit card and cash accounts)
+    Qif,
+}
+
+impl StatementFormat {
+    /// Value stored in `transactions.source`
+    pub fn source(&self) -> &'static str {
+        match self {
+            StatementFormat::Ofx => "ofx",
+            StatementFormat::Camt => "camt",
+            StatementFormat::Mt940 => "mt940",
+            StatementFormat::Qif => "qif",
+        }
+    }
+
+    pub fn parse(&self, content: &[u8]) -> Result<ImportPreview> {
+        match self {
+            StatementFormat::Ofx => ofx::parse(content),
+            StatementFormat::Camt => camt::parse(content),
+            StatementFormat::Mt940 => mt940::parse(content),
+            StatementFormat::Qif => qif::parse(content),
+        }
+    }
+}
+
+/// One transaction read from a statement file
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementRow {
+    /// Line (or record) number in the source file, 1-based
+    pub line: usize,
+    pub transaction_date: NaiveDate,
+    pub value_date: Option<NaiveDate>,
+    /// Signed amount (negative for debits)
+    pub amount: Decimal,
+    pub store: Option<String>,
+    pub description: Option<String>,
+    /// Bank supplied (or derived from the row) id used to skip rows imported before
+    pub external_id: Option<String>,
+    /// Category name from the file (created on import if unknown)
+    pub category: Option<String>,
+    /// Split lines, adding up to `amount` (empty when not split)
+    pub splits: Vec<StatementSplit>,
+}
+
+/// One line of a split row read from a statement file
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementSplit {
+    pub amount: Decimal,
+    /// Category name from the file (created on import if unknown)
+    pub category: Option<String>,
+    pub note: Option<String>,
+}
+
+impl StatementRow {
+    pub fn into_create_transaction(
+        self,
+        user_id: Uuid,
+        category_id: Option<Uuid>,
+        account_id: Option<Uuid>,
+        source: &str,
+    ) -> CreateTransaction {
+        CreateTransaction {
+            user_id,
+            category_id,
+            amount: self.amount,
+            store: self.store,
+            description: self.description,
+            source: source.to_string(),
+            email_message_id: None,
+            external_id: self.external_id,
+            transaction_date: self.transaction_date,
+            value_date: self.value_date,
+            merchant_id: None,
+            account_id,
+            transfer_id: None,
+            currency: None,
+        }
+    }
+}
+
+/// A record that could not be read
+#[derive(Debug, Clone, Serialize)]
+pub struct RowError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Dry-run result of parsing a statement file
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportPreview {
+    pub rows: Vec<StatementRow>,
+    pub errors: Vec<RowError>,
+}
+
+/// Stable ids for rows the bank supplies no id for
+///
+/// The id is a hash of the row's fields and its occurrence among identical
+/// rows of the same file, so re-importing an overlapping export skips the
+/// rows seen before while repeated identical charges in one file are kept.
+#[derive(Debug, Default)]
+pub struct ContentIds {
+    occurrences: HashMap<String, usize>,
+}
+
+impl ContentIds {
+    pub fn next(&mut self, fields: &[&str]) -> String {
+        let key = fields.join("\u{1f}");
+        let occurrence = self.occurrences.entry(key.clone()).or_insert(0);
+        *occurrence += 1;
+
+        let digest = Sha256::digest(format!("{}\u{1f}{}", key, occurrence));
+        format!("{:x}", digest)
+    }
+}
+
+/// Parse a decimal with the given decimal separator (`1.234,56` with `,`)
+///
+/// Currency symbols and spaces are ignored, a trailing minus or wrapping
+/// parentheses mark a negative amount
+pub fn parse_decimal(raw: &str, decimal_separator: char) -> Option<Decimal> {
+    let raw = raw.trim();
+    let negative =
+        raw.starts_with('-') || raw.ends_with('-') || (raw.starts_with('(') && raw.ends_with(')'));
+
+    let normalized: String = raw
+        .chars()
+        .filter_map(|c| match c {
+            '0'..='9' => Some(c),
+            c if c == decimal_separator => Some('.'),
+            _ => None,
+        })
+        .collect();
+
+    if normalized.is_empty() {
+        return None;
+    }
+
+    let value = Decimal::from_str(&normalized).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// Trim a text field, treating blank values as missing
+pub fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
@@ -0,0 +1,307 @@
+//! SWIFT MT940 statement parser
+//!
+//! Each `:61:` statement line becomes a row, with the following `:86:`
+//! field as remittance information. Structured `:86:` content using `?NN`
+//! subfields (common with German and Austrian banks) is split into
+//! counterparty name and remittance text.
+
+use crate::error::{AppError, Result};
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::{non_empty, parse_decimal, ContentIds, ImportPreview, RowError, StatementRow};
+
+/// A `:NN:` field with its (possibly multi-line) content
+struct Field {
+    tag: String,
+    content: String,
+}
+
+/// Parse an MT940 file (one or more statements)
+pub fn parse(content: &[u8]) -> Result<ImportPreview> {
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text.to_string(),
+        // Older exports are Latin-1/Windows-1252
+        Err(_) => encoding_rs::WINDOWS_1252.decode(content).0.into_owned(),
+    };
+
+    let fields = split_fields(&text);
+    if !fields.iter().any(|f| f.tag == "20") {
+        return Err(AppError::Validation(
+            "Not an MT940 file (missing :20: field)".to_string(),
+        ));
+    }
+
+    let mut preview = ImportPreview::default();
+    let mut record = 0;
+    let mut account = "";
+    let mut ids = ContentIds::default();
+    let mut fields = fields.iter().peekable();
+
+    while let Some(field) = fields.next() {
+        if field.tag == "25" {
+            account = field.content.trim();
+        }
+        if field.tag != "61" {
+            continue;
+        }
+        record += 1;
+
+        // Information to account owner belongs to the preceding :61:
+        let info = match fields.peek() {
+            Some(next) if next.tag == "86" => fields.next().map(|f| f.content.as_str()),
+            _ => None,
+        };
+
+        match parse_statement_line(&field.content, info, record, account, &mut ids) {
+            Ok(row) => preview.rows.push(row),
+            Err(message) => preview.errors.push(RowError {
+                line: record,
+                message,
+            }),
+        }
+    }
+
+    Ok(preview)
+}
+
+/// Parse a `:61:` line with its `:86:` information
+///
+/// Rows are identified by the bank reference. Customer references repeat
+/// (e.g. every month for a standing order), so without a bank reference
+/// the id is derived from the account, date, amount and customer reference.
+fn parse_statement_line(
+    line: &str,
+    info: Option<&str>,
+    record: usize,
+    account: &str,
+    ids: &mut ContentIds,
+) -> std::result::Result<StatementRow, String> {
+    static LINE_RE: OnceLock<Regex> = OnceLock::new();
+    let line_re = LINE_RE.get_or_init(|| {
+        Regex::new(
+            r"^(?P<value>\d{6})(?P<entry>\d{4})?(?P<mark>R?[CD])[A-Z]?(?P<amount>\d+,\d{0,2})[NSF][A-Z0-9]{3}(?P<customer>[^/\r\n]*)(?://(?P<bank>[^\r\n]*))?",
+        )
+        .unwrap()
+    });
+
+    let caps = line_re
+        .captures(line)
+        .ok_or_else(|| format!("Invalid :61: line '{}'", line.lines().next().unwrap_or("")))?;
+
+    let value_date = NaiveDate::parse_from_str(&caps["value"], "%y%m%d")
+        .map_err(|e| format!("Invalid value date: {}", e))?;
+
+    // Entry date only has month/day, take the year from the value date
+    let transaction_date = match caps.name("entry") {
+        Some(entry) => entry_date(value_date, entry.as_str())
+            .ok_or_else(|| format!("Invalid entry date '{}'", entry.as_str()))?,
+        None => value_date,
+    };
+
+    let amount = parse_decimal(&caps["amount"], ',')
+        .ok_or_else(|| format!("Invalid amount '{}'", &caps["amount"]))?;
+
+    // RC/RD are reversals of a credit/debit
+    let amount = match &caps["mark"] {
+        "C" | "RD" => amount.abs(),
+        _ => -amount.abs(),
+    };
+
+    let external_id = caps
+        .name("bank")
+        .and_then(|m| non_empty(m.as_str()))
+        .unwrap_or_else(|| {
+            ids.next(&[
+                account,
+                &transaction_date.to_string(),
+                &amount.normalize().to_string(),
+                caps["customer"].trim(),
+            ])
+        });
+
+    let (counterparty, remittance) = info.map(parse_information).unwrap_or((None, None));
+
+    Ok(StatementRow {
+        line: record,
+        transaction_date,
+        value_date: Some(value_date),
+        amount,
+        store: counterparty,
+        description: remittance,
+        external_id: Some(external_id),
+        category: None,
+        splits: Vec::new(),
+    })
+}
+
+/// Split the text into `:NN:` fields, joining continuation lines
+fn split_fields(text: &str) -> Vec<Field> {
+    static TAG_RE: OnceLock<Regex> = OnceLock::new();
+    let tag_re = TAG_RE.get_or_init(|| Regex::new(r"^:(\d{2}[A-Z]?):(.*)$").unwrap());
+
+    let mut fields: Vec<Field> = Vec::new();
+
+    for line in text.lines() {
+        // SWIFT block envelope ({1:...}{4: ... -})
+        let line = line.trim_end().trim_start_matches("{4:");
+        if line.is_empty() || line == "-" || line.starts_with("-}") || line.starts_with('{') {
+            continue;
+        }
+
+        if let Some(caps) = tag_re.captures(line) {
+            fields.push(Field {
+                tag: caps[1].to_string(),
+                content: caps[2].to_string(),
+            });
+        } else if let Some(field) = fields.last_mut() {
+            field.content.push('\n');
+            field.content.push_str(line);
+        }
+    }
+
+    fields
+}
+
+/// Extract (counterparty, remittance) from a `:86:` field
+fn parse_information(info: &str) -> (Option<String>, Option<String>) {
+    // Subfields can be wrapped across lines anywhere
+    let joined = info.replace(['\r', '\n'], "");
+
+    if !joined.contains("?20") && !joined.contains("?32") {
+        let text = info.split_whitespace().collect::<Vec<_>>().join(" ");
+        return (None, non_empty(&text));
+    }
+
+    let mut name = String::new();
+    let mut remittance = Vec::new();
+
+    for part in joined.split('?').skip(1) {
+        let code = part.get(..2).unwrap_or_default();
+        let value = part.get(2..).unwrap_or_default();
+        match code {
+            "20" | "21" | "22" | "23" | "24" | "25" | "26" | "27" | "28" | "29" | "60" | "61"
+            | "62" | "63" => remittance.push(value.trim().to_string()),
+            "32" | "33" => name.push_str(value),
+            _ => {}
+        }
+    }
+
+    (non_empty(&name), non_empty(&remittance.join(" ")))
+}
+
+/// Resolve an `MMDD` entry date near the value date (handles year boundaries)
+fn entry_date(value_date: NaiveDate, mmdd: &str) -> Option<NaiveDate> {
+    let month: u32 = mmdd.get(..2)?.parse().ok()?;
+    let day: u32 = mmdd.get(2..4)?.parse().ok()?;
+
+    let year = match (value_date.month(), month) {
+        (1, 12) => value_date.year() - 1,
+        (12, 1) => value_date.year() + 1,
+        _ => value_date.year(),
+    };
+
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    const STATEMENT: &str = ":20:STARTUMS\n\
+        :25:GR1601101250000000012300695\n\
+        :28C:00001/001\n\
+        :60F:C260301EUR1000,00\n\
+        :61:2603020302DR45,00NMSCRENT-2026//8310220001\n\
+        :86:?20Rent March?32Landlord\n\
+        :61:2603050305DR12,50NTRFSTANDING-ORDER\n\
+        :86:Gym membership\n\
+        :61:2603050305DR12,50NTRFSTANDING-ORDER\n\
+        :86:Gym membership\n\
+        :61:2603100310CR100,00NTRFNONREF\n\
+        :86:Refund\n\
+        :62F:C260310EUR1030,00\n\
+        -";
+
+    #[test]
+    fn parses_statement_lines_with_information() {
+        let preview = parse(STATEMENT.as_bytes()).unwrap();
+
+        assert!(preview.errors.is_empty());
+        let amounts: Vec<Decimal> = preview.rows.iter().map(|row| row.amount).collect();
+        assert_eq!(
+            amounts,
+            vec![
+                Decimal::new(-4500, 2),
+                Decimal::new(-1250, 2),
+                Decimal::new(-1250, 2),
+                Decimal::new(10000, 2),
+            ]
+        );
+        assert_eq!(preview.rows[0].store.as_deref(), Some("Landlord"));
+        assert_eq!(preview.rows[0].description.as_deref(), Some("Rent March"));
+        assert_eq!(
+            preview.rows[1].description.as_deref(),
+            Some("Gym membership")
+        );
+    }
+
+    #[test]
+    fn entry_date_is_the_transaction_date() {
+        // Value date 30 Dec 2025, booked on 2 Jan 2026
+        let statement = ":20:STARTUMS\n:25:GR1601101250000000012300695\n\
+            :61:2512300102DR9,90NMSCNONREF\n:86:Streaming\n-";
+
+        let preview = parse(statement.as_bytes()).unwrap();
+
+        let row = &preview.rows[0];
+        assert_eq!(row.value_date, NaiveDate::from_ymd_opt(2025, 12, 30));
+        assert_eq!(
+            Some(row.transaction_date),
+            NaiveDate::from_ymd_opt(2026, 1, 2)
+        );
+        assert_eq!(row.amount, Decimal::new(-990, 2));
+    }
+
+    #[test]
+    fn bank_reference_is_the_external_id() {
+        let preview = parse(STATEMENT.as_bytes()).unwrap();
+
+        assert_eq!(preview.rows[0].external_id.as_deref(), Some("8310220001"));
+    }
+
+    #[test]
+    fn repeated_customer_references_get_distinct_stable_ids() {
+        let first = parse(STATEMENT.as_bytes()).unwrap();
+        let second = parse(STATEMENT.as_bytes()).unwrap();
+
+        let ids: Vec<_> = first
+            .rows
+            .iter()
+            .map(|row| row.external_id.clone())
+            .collect();
+        assert!(ids[1].is_some());
+        assert_ne!(ids[1], ids[2]);
+        assert_ne!(ids[1].as_deref(), Some("STANDING-ORDER"));
+        assert_ne!(ids[3].as_deref(), Some("NONREF"));
+
+        let again: Vec<_> = second
+            .rows
+            .iter()
+            .map(|row| row.external_id.clone())
+            .collect();
+        assert_eq!(ids, again);
+    }
+
+    #[test]
+    fn same_customer_reference_on_another_account_is_a_different_row() {
+        let other = STATEMENT.replace("GR1601101250000000012300695", "GR9601101250000000099999999");
+
+        let first = parse(STATEMENT.as_bytes()).unwrap();
+        let second = parse(other.as_bytes()).unwrap();
+
+        assert_ne!(first.rows[1].external_id, second.rows[1].external_id);
+    }
+}
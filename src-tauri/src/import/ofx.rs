@@ -0,0 +1,302 @@
+//! OFX / QFX statement parser
+//!
+//! Handles both OFX 1.x (SGML, leaf elements without closing tags) and
+//! OFX 2.x (XML). Only `STMTTRN` records and the account they belong to
+//! are read. FITIDs are only unique per account, so row ids are prefixed
+//! with the statement's bank and account id.
+
+use crate::error::{AppError, Result};
+use chrono::NaiveDate;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::{non_empty, parse_decimal, ImportPreview, RowError, StatementRow};
+
+/// Parse an OFX/QFX file
+pub fn parse(content: &[u8]) -> Result<ImportPreview> {
+    static TRANSACTION_RE: OnceLock<Regex> = OnceLock::new();
+    static ACCOUNT_RE: OnceLock<Regex> = OnceLock::new();
+    let transaction_re =
+        TRANSACTION_RE.get_or_init(|| Regex::new(r"(?is)<STMTTRN>(.*?)</STMTTRN>").unwrap());
+    let account_re = ACCOUNT_RE.get_or_init(|| {
+        Regex::new(r"(?is)<(?:BANK|CC)ACCTFROM>(.*?)</(?:BANK|CC)ACCTFROM>").unwrap()
+    });
+
+    let text = decode(content);
+
+    if !text.to_uppercase().contains("<OFX>") {
+        return Err(AppError::Validation(
+            "Not an OFX file (missing <OFX> element)".to_string(),
+        ));
+    }
+
+    // Each statement names its account before listing its transactions
+    let accounts: Vec<(usize, String)> = account_re
+        .captures_iter(&text)
+        .filter_map(|caps| {
+            let start = caps.get(0)?.start();
+            Some((start, account_key(&leaf_values(&caps[1]))?))
+        })
+        .collect();
+
+    let mut preview = ImportPreview::default();
+
+    for (index, caps) in transaction_re.captures_iter(&text).enumerate() {
+        let record = index + 1;
+        let fields = leaf_values(&caps[1]);
+        let start = caps.get(0).map_or(0, |m| m.start());
+        let account = accounts
+            .iter()
+            .rev()
+            .find(|(position, _)| *position < start)
+            .map(|(_, key)| key.as_str());
+
+        match parse_transaction(&fields, account, record) {
+            Ok(row) => preview.rows.push(row),
+            Err(message) => preview.errors.push(RowError {
+                line: record,
+                message,
+            }),
+        }
+    }
+
+    Ok(preview)
+}
+
+fn parse_transaction(
+    fields: &HashMap<String, String>,
+    account: Option<&str>,
+    record: usize,
+) -> std::result::Result<StatementRow, String> {
+    let raw_date = fields.get("DTPOSTED").ok_or("Missing DTPOSTED")?;
+    let transaction_date =
+        parse_ofx_date(raw_date).ok_or_else(|| format!("Invalid DTPOSTED '{}'", raw_date))?;
+
+    let raw_amount = fields.get("TRNAMT").ok_or("Missing TRNAMT")?;
+    // OFX mandates '.', but some European exports use ','
+    let decimal_separator = if raw_amount.contains('.') { '.' } else { ',' };
+    let amount = parse_decimal(raw_amount, decimal_separator)
+        .ok_or_else(|| format!("Invalid TRNAMT '{}'", raw_amount))?;
+
+    let name = fields.get("NAME").or_else(|| fields.get("PAYEE")).cloned();
+    let memo = fields.get("MEMO").cloned();
+    let external_id = fields.get("FITID").map(|fitid| match account {
+        Some(account) => format!("{}:{}", account, fitid),
+        None => fitid.clone(),
+    });
+
+    Ok(StatementRow {
+        line: record,
+        transaction_date,
+        value_date: None,
+        amount,
+        store: name.or_else(|| memo.clone()),
+        description: memo,
+        external_id,
+        category: None,
+        splits: Vec::new(),
+    })
+}
+
+/// `BANKID/ACCTID` of a bank account, `ACCTID` of a credit card
+fn account_key(fields: &HashMap<String, String>) -> Option<String> {
+    let account_id = fields.get("ACCTID")?;
+    Some(match fields.get("BANKID") {
+        Some(bank_id) => format!("{}/{}", bank_id, account_id),
+        None => account_id.clone(),
+    })
+}
+
+/// Collect `<TAG>value` leaf elements of a record (SGML or XML style)
+fn leaf_values(record: &str) -> HashMap<String, String> {
+    static LEAF_RE: OnceLock<Regex> = OnceLock::new();
+    let leaf_re = LEAF_RE.get_or_init(|| Regex::new(r"<([A-Za-z0-9.]+)>([^<\r\n]*)").unwrap());
+
+    leaf_re
+        .captures_iter(record)
+        .filter_map(|caps| {
+            let value = non_empty(&unescape(&caps[2]))?;
+            // First value wins (e.g. NAME inside a nested PAYEE aggregate)
+            Some((caps[1].to_uppercase(), value))
+        })
+        .fold(HashMap::new(), |mut fields, (tag, value)| {
+            fields.entry(tag).or_insert(value);
+            fields
+        })
+}
+
+/// Parse `YYYYMMDD[HHMMSS[.XXX]][[gmt offset:tz name]]`, keeping only the date
+fn parse_ofx_date(raw: &str) -> Option<NaiveDate> {
+    let digits = raw.get(..8)?;
+    NaiveDate::parse_from_str(digits, "%Y%m%d").ok()
+}
+
+/// Decode using the SGML header charset (1.x) or UTF-8
+fn decode(content: &[u8]) -> String {
+    let head = String::from_utf8_lossy(&content[..content.len().min(512)]);
+
+    if head.contains("CHARSET:1252") {
+        let (text, _, _) = encoding_rs::WINDOWS_1252.decode(content);
+        return text.into_owned();
+    }
+
+    if head.contains("CHARSET:1253") {
+        let (text, _, _) = encoding_rs::WINDOWS_1253.decode(content);
+        return text.into_owned();
+    }
+
+    String::from_utf8_lossy(content).into_owned()
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    const SGML: &str = "OFXHEADER:100\r\n\
+        DATA:OFXSGML\r\n\
+        VERSION:102\r\n\
+        ENCODING:USASCII\r\n\
+        CHARSET:1252\r\n\
+        \r\n\
+        <OFX>\r\n\
+        <BANKMSGSRSV1><STMTTRNRS><STMTRS>\r\n\
+        <CURDEF>EUR\r\n\
+        <BANKACCTFROM><BANKID>0260<ACCTID>1234567890<ACCTTYPE>CHECKING</BANKACCTFROM>\r\n\
+        <BANKTRANLIST>\r\n\
+        <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20260303120000[+2:EET]<TRNAMT>-45.20<FITID>1001<NAME>SUPERMARKET<MEMO>Card payment</STMTTRN>\r\n\
+        <STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20260305<TRNAMT>1500,00<FITID>1002<NAME>SALARY &amp; BONUS</STMTTRN>\r\n\
+        </BANKTRANLIST>\r\n\
+        </STMTRS></STMTTRNRS></BANKMSGSRSV1>\r\n\
+        </OFX>\r\n";
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<?OFX OFXHEADER="200" VERSION="220"?>
+<OFX>
+  <CREDITCARDMSGSRSV1>
+    <CCSTMTTRNRS>
+      <CCSTMTRS>
+        <CURDEF>EUR</CURDEF>
+        <CCACCTFROM><ACCTID>4111XXXX1111</ACCTID></CCACCTFROM>
+        <BANKTRANLIST>
+          <STMTTRN>
+            <TRNTYPE>DEBIT</TRNTYPE>
+            <DTPOSTED>20260310</DTPOSTED>
+            <TRNAMT>-12.50</TRNAMT>
+            <FITID>1001</FITID>
+            <NAME>CAFE</NAME>
+          </STMTTRN>
+          <STMTTRN>
+            <TRNTYPE>DEBIT</TRNTYPE>
+            <DTPOSTED>20260311</DTPOSTED>
+            <TRNAMT>-3.00</TRNAMT>
+            <MEMO>Bakery</MEMO>
+          </STMTTRN>
+        </BANKTRANLIST>
+      </CCSTMTRS>
+    </CCSTMTTRNRS>
+  </CREDITCARDMSGSRSV1>
+</OFX>
+"#;
+
+    #[test]
+    fn reads_sgml_statements() {
+        let preview = parse(SGML.as_bytes()).unwrap();
+
+        assert!(preview.errors.is_empty());
+        assert_eq!(preview.rows.len(), 2);
+        let debit = &preview.rows[0];
+        assert_eq!(
+            debit.transaction_date,
+            NaiveDate::from_ymd_opt(2026, 3, 3).unwrap()
+        );
+        assert_eq!(debit.amount, Decimal::new(-4520, 2));
+        assert_eq!(debit.store.as_deref(), Some("SUPERMARKET"));
+        assert_eq!(debit.description.as_deref(), Some("Card payment"));
+        assert_eq!(debit.external_id.as_deref(), Some("0260/1234567890:1001"));
+
+        let credit = &preview.rows[1];
+        assert_eq!(credit.amount, Decimal::new(150000, 2));
+        assert_eq!(credit.store.as_deref(), Some("SALARY & BONUS"));
+    }
+
+    #[test]
+    fn reads_xml_statements() {
+        let preview = parse(XML.as_bytes()).unwrap();
+
+        assert!(preview.errors.is_empty());
+        let rows: Vec<(Decimal, Option<&str>, Option<&str>)> = preview
+            .rows
+            .iter()
+            .map(|row| (row.amount, row.store.as_deref(), row.external_id.as_deref()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    Decimal::new(-1250, 2),
+                    Some("CAFE"),
+                    Some("4111XXXX1111:1001")
+                ),
+                // No FITID: imported without an id, so never skipped as a duplicate
+                (Decimal::new(-300, 2), Some("Bakery"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_fitid_in_two_accounts_gives_two_ids() {
+        let statement = |account: &str| {
+            format!(
+                "<STMTRS><BANKACCTFROM><BANKID>0260<ACCTID>{}</BANKACCTFROM>\
+                 <BANKTRANLIST><STMTTRN><DTPOSTED>20260303<TRNAMT>-10.00<FITID>1</STMTTRN>\
+                 </BANKTRANLIST></STMTRS>",
+                account
+            )
+        };
+        let file = format!("<OFX>{}{}</OFX>", statement("111"), statement("222"));
+
+        let preview = parse(file.as_bytes()).unwrap();
+
+        let ids: Vec<Option<&str>> = preview
+            .rows
+            .iter()
+            .map(|row| row.external_id.as_deref())
+            .collect();
+        assert_eq!(ids, vec![Some("0260/111:1"), Some("0260/222:1")]);
+    }
+
+    #[test]
+    fn reports_records_without_date_or_amount() {
+        let file = "<OFX><STMTTRN><DTPOSTED>20260303<FITID>1</STMTTRN>\
+                    <STMTTRN><DTPOSTED>2026-03<TRNAMT>-1.00</STMTTRN></OFX>";
+
+        let preview = parse(file.as_bytes()).unwrap();
+
+        assert!(preview.rows.is_empty());
+        let errors: Vec<(usize, &str)> = preview
+            .errors
+            .iter()
+            .map(|e| (e.line, e.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![(1, "Missing TRNAMT"), (2, "Invalid DTPOSTED '2026-03'")]
+        );
+    }
+
+    #[test]
+    fn rejects_files_without_an_ofx_element() {
+        assert!(parse(b"Date,Amount\n2026-03-03,-1.00\n").is_err());
+    }
+}
@@ -0,0 +1,370 @@
+//! QIF (Quicken Interchange Format) reader
+//!
+//! Reads `!Type:Bank`, `!Type:CCard` and `!Type:Cash` sections. Split
+//! transactions (`S`/`E`/`$` lines) keep their lines as the row's splits.
+//! Transfers (`L[Account]`) are imported without category.
+//!
+//! QIF has no transaction ids, rows are identified by date, amount, payee
+//! and check number (plus an occurrence count for identical records).
+
+use crate::error::{AppError, Result};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::{
+    non_empty, parse_decimal, ContentIds, ImportPreview, RowError, StatementRow, StatementSplit,
+};
+
+/// Fields of one `^` terminated record
+#[derive(Default)]
+struct Record {
+    line: usize,
+    date: Option<String>,
+    amount: Option<String>,
+    payee: Option<String>,
+    number: Option<String>,
+    memo: Option<String>,
+    category: Option<String>,
+    splits: Vec<Split>,
+}
+
+#[derive(Default)]
+struct Split {
+    category: Option<String>,
+    memo: Option<String>,
+    amount: Option<String>,
+}
+
+/// Parse a QIF file
+pub fn parse(content: &[u8]) -> Result<ImportPreview> {
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text.to_string(),
+        Err(_) => encoding_rs::WINDOWS_1252.decode(content).0.into_owned(),
+    };
+
+    if !text
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with('!')
+    {
+        return Err(AppError::Validation(
+            "Not a QIF file (missing !Type header)".to_string(),
+        ));
+    }
+
+    let mut preview = ImportPreview::default();
+    let mut in_transactions = false;
+    let mut record = Record::default();
+    let mut ids = ContentIds::default();
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = raw_line.trim_start_matches('\u{feff}').trim_end();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('!') {
+            let header = header.to_lowercase();
+            in_transactions = matches!(
+                header.as_str(),
+                "type:bank" | "type:ccard" | "type:cash" | "type:oth a" | "type:oth l"
+            );
+            record = Record::default();
+            continue;
+        }
+
+        if !in_transactions {
+            continue;
+        }
+
+        if record.line == 0 {
+            record.line = line_number;
+        }
+
+        let mut chars = line.chars();
+        let code = chars.next().unwrap_or_default();
+        let value = chars.as_str().trim();
+
+        match code {
+            'D' => record.date = Some(value.to_string()),
+            'T' | 'U' => record.amount = Some(value.to_string()),
+            'P' => record.payee = non_empty(value),
+            'N' => record.number = non_empty(value),
+            'M' => record.memo = non_empty(value),
+            'L' => record.category = non_empty(value),
+            'S' => record.splits.push(Split {
+                category: non_empty(value),
+                ..Default::default()
+            }),
+            'E' => {
+                if let Some(split) = record.splits.last_mut() {
+                    split.memo = non_empty(value);
+                }
+            }
+            '$' => {
+                if let Some(split) = record.splits.last_mut() {
+                    split.amount = Some(value.to_string());
+                }
+            }
+            '^' => {
+                let finished = std::mem::take(&mut record);
+                let line = finished.line;
+                match into_row(finished, &mut ids) {
+                    Ok(row) => preview.rows.push(row),
+                    Err(message) => preview.errors.push(RowError { line, message }),
+                }
+            }
+            // Cleared status, address lines, ...
+            _ => {}
+        }
+    }
+
+    Ok(preview)
+}
+
+fn into_row(record: Record, ids: &mut ContentIds) -> std::result::Result<StatementRow, String> {
+    let raw_date = record.date.ok_or("Missing date (D)")?;
+    let transaction_date =
+        parse_qif_date(&raw_date).ok_or_else(|| format!("Invalid date '{}'", raw_date))?;
+
+    let raw_amount = record.amount.ok_or("Missing amount (T)")?;
+    let amount =
+        parse_qif_amount(&raw_amount).ok_or_else(|| format!("Invalid amount '{}'", raw_amount))?;
+
+    let external_id = ids.next(&[
+        &transaction_date.to_string(),
+        &amount.normalize().to_string(),
+        record.payee.as_deref().unwrap_or_default(),
+        record.number.as_deref().unwrap_or_default(),
+    ]);
+
+    let mut splits = Vec::with_capacity(record.splits.len());
+    for split in record.splits {
+        let raw = split.amount.as_deref().ok_or("Split without amount ($)")?;
+        let split_amount =
+            parse_qif_amount(raw).ok_or_else(|| format!("Invalid split amount '{}'", raw))?;
+        if split_amount.is_zero() {
+            continue;
+        }
+
+        splits.push(StatementSplit {
+            amount: split_amount,
+            category: split.category.and_then(|c| category_name(&c)),
+            note: split.memo,
+        });
+    }
+
+    let total: Decimal = splits.iter().map(|s| s.amount).sum();
+    if !splits.is_empty() && total != amount {
+        return Err(format!(
+            "Split amounts ({}) do not add up to the total ({})",
+            total, amount
+        ));
+    }
+
+    // A split's categories are on its lines, a single line is just the
+    // transaction's category
+    let category = match splits.len() {
+        0 => record.category.and_then(|c| category_name(&c)),
+        1 => splits.pop().and_then(|s| s.category),
+        _ => None,
+    };
+
+    Ok(StatementRow {
+        line: record.line,
+        transaction_date,
+        value_date: None,
+        amount,
+        store: record.payee,
+        description: record.memo,
+        external_id: Some(external_id),
+        category,
+        splits,
+    })
+}
+
+/// Category path from an `L`/`S` value (`Parent:Child` is kept as is)
+///
+/// Transfers (`[Account]`) have no category and `Category/Class` loses the class
+fn category_name(value: &str) -> Option<String> {
+    if value.starts_with('[') {
+        return None;
+    }
+
+    let name = value.split('/').next().unwrap_or(value);
+    let path: Vec<&str> = name
+        .split(':')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    non_empty(&path.join(":"))
+}
+
+/// Parse QIF dates: `MM/DD/YYYY`, `MM/DD'YY`, `M/D/YY` and `DD/MM/YYYY`
+/// (day first is assumed only when the first number cannot be a month)
+fn parse_qif_date(raw: &str) -> Option<NaiveDate> {
+    let normalized = raw
+        .replace('\'', "/")
+        .replace(['-', '.'], "/")
+        .replace(' ', "");
+    let parts: Vec<&str> = normalized.split('/').collect();
+    let [first, second, year] = parts.as_slice() else {
+        return None;
+    };
+
+    let first: u32 = first.parse().ok()?;
+    let second: u32 = second.parse().ok()?;
+    let mut year: i32 = year.parse().ok()?;
+
+    if year < 100 {
+        year += if year < 70 { 2000 } else { 1900 };
+    }
+
+    let (month, day) = if first > 12 {
+        (second, first)
+    } else {
+        (first, second)
+    };
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Parse `-1,234.56` or `-1.234,56` (the last separator is the decimal point)
+fn parse_qif_amount(raw: &str) -> Option<Decimal> {
+    let decimal_separator = match (raw.rfind('.'), raw.rfind(',')) {
+        (Some(dot), Some(comma)) if comma > dot => ',',
+        (None, Some(comma)) if raw.len() - comma - 1 <= 2 => ',',
+        _ => '.',
+    };
+
+    parse_decimal(raw, decimal_separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATEMENT: &str = "!Type:Bank\n\
+        D03/01/2026\n\
+        T-45.20\n\
+        PSUPERMARKET\n\
+        LFood:Groceries\n\
+        ^\n\
+        D03/01/2026\n\
+        T-45.20\n\
+        PSUPERMARKET\n\
+        LFood:Groceries\n\
+        ^\n\
+        D03/02/2026\n\
+        T-100.00\n\
+        N1042\n\
+        PLANDLORD\n\
+        LHousing/Home\n\
+        ^\n\
+        D03/03/2026\n\
+        T-20.00\n\
+        L[Savings]\n\
+        ^\n";
+
+    #[test]
+    fn keeps_category_hierarchy_and_drops_class() {
+        let preview = parse(STATEMENT.as_bytes()).unwrap();
+
+        let categories: Vec<Option<&str>> = preview
+            .rows
+            .iter()
+            .map(|row| row.category.as_deref())
+            .collect();
+        assert_eq!(
+            categories,
+            vec![
+                Some("Food:Groceries"),
+                Some("Food:Groceries"),
+                Some("Housing"),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn identical_records_get_distinct_stable_ids() {
+        let first = parse(STATEMENT.as_bytes()).unwrap();
+        let second = parse(STATEMENT.as_bytes()).unwrap();
+
+        let ids: Vec<Option<String>> = first.rows.iter().map(|r| r.external_id.clone()).collect();
+        assert!(ids.iter().all(Option::is_some));
+        assert_ne!(ids[0], ids[1]);
+
+        let again: Vec<Option<String>> =
+            second.rows.iter().map(|r| r.external_id.clone()).collect();
+        assert_eq!(ids, again);
+    }
+
+    #[test]
+    fn check_number_is_part_of_the_id() {
+        let other = STATEMENT.replace("N1042", "N1043");
+
+        let first = parse(STATEMENT.as_bytes()).unwrap();
+        let second = parse(other.as_bytes()).unwrap();
+
+        assert_eq!(first.rows[0].external_id, second.rows[0].external_id);
+        assert_ne!(first.rows[2].external_id, second.rows[2].external_id);
+    }
+
+    #[test]
+    fn split_lines_stay_on_one_row() {
+        let statement = "!Type:Bank\n\
+            D03/05/2026\n\
+            T-60.00\n\
+            PHYPERMARKET\n\
+            SFood:Groceries\n\
+            $-45.00\n\
+            SHousehold\n\
+            EDetergent\n\
+            $-15.00\n\
+            ^\n";
+
+        let preview = parse(statement.as_bytes()).unwrap();
+
+        assert_eq!(preview.rows.len(), 1);
+        let row = &preview.rows[0];
+        assert_eq!(row.amount, Decimal::new(-6000, 2));
+        assert_eq!(row.category, None);
+        let lines: Vec<(Decimal, Option<&str>, Option<&str>)> = row
+            .splits
+            .iter()
+            .map(|s| (s.amount, s.category.as_deref(), s.note.as_deref()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (Decimal::new(-4500, 2), Some("Food:Groceries"), None),
+                (Decimal::new(-1500, 2), Some("Household"), Some("Detergent")),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_splits_that_do_not_add_up() {
+        let statement = "!Type:Bank\nD03/05/2026\nT-60.00\nSFood\n$-45.00\n^\n";
+
+        let preview = parse(statement.as_bytes()).unwrap();
+
+        assert!(preview.rows.is_empty());
+        assert_eq!(preview.errors.len(), 1);
+    }
+
+    #[test]
+    fn parses_day_first_dates_and_comma_amounts() {
+        assert_eq!(
+            parse_qif_date("25/03'26"),
+            NaiveDate::from_ymd_opt(2026, 3, 25)
+        );
+        assert_eq!(
+            parse_qif_amount("-1.234,56"),
+            Some(Decimal::new(-123456, 2))
+        );
+        assert_eq!(parse_qif_amount("1,234.56"), Some(Decimal::new(123456, 2)));
+    }
+}
@@ -1,11 +1,20 @@
 pub mod auth;
+pub mod categorize;
 pub mod commands;
 pub mod config;
 pub mod db;
+pub mod email;
 pub mod error;
+pub mod export;
+pub mod fx;
+pub mod import;
 pub mod models;
+pub mod notifications;
+pub mod recurring;
 pub mod services;
 pub mod state;
+#[cfg(test)]
+mod test_support;
 
 // Re-exports for convenience
 pub use config::Settings;
@@ -13,13 +22,20 @@ pub use db::DbPool;
 pub use error::{AppError, Result};
 
 use commands::*;
+use services::RecurringService;
 use state::AppState;
+use std::collections::HashMap;
+use std::time::Duration;
 use tauri::Manager;
 
+/// How often the background task posts recurring transactions that came due
+const RECURRING_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // Initialize in a blocking task
             let handle = app.handle().clone();
@@ -33,9 +49,72 @@ pub fn run() {
             login,
             logout,
             get_categories,
+            get_category_tree,
+            get_category_summary,
+            create_category,
+            update_category,
+            delete_category,
+            merge_categories,
+            get_categorization_rules,
+            create_categorization_rule,
+            update_categorization_rule,
+            delete_categorization_rule,
+            preview_categorization_rules,
+            apply_categorization_rules,
+            suggest_category,
+            retrain_category_suggestions,
+            get_merchants,
+            rename_merchant,
+            add_merchant_alias,
+            merge_merchants,
+            get_merchant_spending,
+            relink_merchants,
+            get_budgets,
+            set_budget,
+            delete_budget,
+            get_budget_status,
+            get_recurring_transactions,
+            create_recurring_transaction,
+            update_recurring_transaction,
+            delete_recurring_transaction,
+            set_recurring_paused,
+            skip_recurring_occurrence,
+            get_detected_subscriptions,
+            confirm_subscription,
+            dismiss_subscription,
+            get_accounts,
+            create_account,
+            update_account,
+            delete_account,
+            get_account_register,
+            set_base_currency,
+            import_ecb_rates,
+            get_fx_rates,
             get_transactions,
             add_transaction,
+            update_transaction,
+            delete_transaction,
+            create_transfer,
+            get_transfer,
+            delete_transfer,
+            get_transaction_splits,
+            set_transaction_splits,
             get_monthly_summary,
+            sync_email_transactions,
+            get_sync_status,
+            import_email_files,
+            save_csv_profile,
+            get_csv_profiles,
+            delete_csv_profile,
+            preview_csv_import,
+            import_csv,
+            preview_statement_import,
+            import_statement,
+            export_qif,
+            export_transactions,
+            export_journal,
+            backup_user_data,
+            restore_user_data,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -77,5 +156,38 @@ async fn initialize_app(handle: tauri::AppHandle) -> std::result::Result<(), Box
     // Manage state with Tauri
     handle.manage(state);
 
+    // Post recurring transactions that came due while the app was closed,
+    // then keep checking in the background
+    post_recurring_transactions(&handle, &pool).await;
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(RECURRING_CHECK_INTERVAL);
+        // The first tick completes immediately
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            post_recurring_transactions(&handle, &pool).await;
+        }
+    });
+
     Ok(())
 }
+
+/// Post due recurring transactions and check the budgets they count towards
+async fn post_recurring_transactions(handle: &tauri::AppHandle, pool: &DbPool) {
+    let today = chrono::Local::now().date_naive();
+    let posted = match RecurringService::new(pool.clone()).post_due(today).await {
+        Ok(posted) => posted,
+        Err(e) => {
+            tracing::warn!("Posting recurring transactions failed: {}", e);
+            return;
+        }
+    };
+
+    let mut dates: HashMap<uuid::Uuid, Vec<chrono::NaiveDate>> = HashMap::new();
+    for tx in &posted {
+        dates.entry(tx.user_id).or_default().push(tx.transaction_date);
+    }
+    for (user_id, dates) in dates {
+        notifications::check_budget_alerts(handle, pool, user_id, &dates).await;
+    }
+}
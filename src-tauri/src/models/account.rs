@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::Transaction;
+
+/// Name of the account created for every user
+pub const DEFAULT_ACCOUNT_NAME: &str = "Main account";
+
+/// Bank account, card or wallet that transactions are booked to
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Account {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// `checking`, `credit_card`, `cash` or `savings`
+    pub account_type: String,
+    /// Balance before the first recorded transaction
+    pub opening_balance: Decimal,
+    /// ISO 4217 code
+    pub currency: String,
+    /// Used for transactions that do not name an account
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Create an account, or replace the settings of one
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateAccount {
+    pub user_id: Uuid,
+    pub name: String,
+    pub account_type: String,
+    pub opening_balance: Decimal,
+    pub currency: String,
+}
+
+/// Account with its current balance
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AccountWithBalance {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub account: Account,
+    /// Opening balance plus all transactions
+    pub balance: Decimal,
+    pub transaction_count: i64,
+}
+
+/// Transaction with the account balance after it
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TransactionWithBalance {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub transaction: Transaction,
+    pub balance: Decimal,
+}
@@ -0,0 +1,23 @@
+use super::{Account, Category, Transaction, TransactionSplit, User};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Current archive layout, bump when fields are added, removed or change
+/// meaning (older archives are upgraded when read)
+///
+/// 2: accounts, with transactions referring to them
+pub const BACKUP_FORMAT_VERSION: u32 = 2;
+
+/// Everything stored for one user, as written by `backup_user_data`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupArchive {
+    pub version: u32,
+    /// Application version that wrote the archive
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub user: User,
+    pub accounts: Vec<Account>,
+    pub categories: Vec<Category>,
+    pub transactions: Vec<Transaction>,
+    pub splits: Vec<TransactionSplit>,
+}
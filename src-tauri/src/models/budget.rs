@@ -0,0 +1,74 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Alert thresholds of a budget when none are given
+pub const DEFAULT_ALERT_THRESHOLDS: [i32; 2] = [80, 100];
+
+/// Monthly spending limit for a category and its sub-categories
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Budget {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub category_id: Uuid,
+    pub amount: Decimal,
+    /// Unused amounts of earlier months are added to later ones
+    pub rollover: bool,
+    /// First day of the first budgeted month
+    pub start_month: NaiveDate,
+    /// Percentages of the available amount that trigger an alert
+    pub alert_thresholds: Vec<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create a budget, or replace the category's existing one
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetBudget {
+    pub user_id: Uuid,
+    pub category_id: Uuid,
+    pub amount: Decimal,
+    pub rollover: bool,
+    pub start_month: NaiveDate,
+    pub alert_thresholds: Vec<i32>,
+}
+
+/// Progress of a budget in one month
+///
+/// Spending is positive (net of refunds). `available` is the monthly amount
+/// plus anything carried over from earlier months.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct BudgetStatus {
+    pub budget_id: Uuid,
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub amount: Decimal,
+    pub rollover: bool,
+    pub carried_over: Decimal,
+    pub available: Decimal,
+    pub spent: Decimal,
+    pub remaining: Decimal,
+    /// Share of `available` spent, `None` when nothing is available
+    pub percent_used: Option<Decimal>,
+    /// Spending at the end of the month if the current pace continues
+    pub projected_spent: Decimal,
+    pub over_budget: bool,
+    /// Not over budget yet, but will be at the current pace
+    pub projected_over_budget: bool,
+}
+
+/// A budget crossing one of its alert thresholds
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetAlert {
+    pub budget_id: Uuid,
+    pub category_id: Uuid,
+    pub category_name: String,
+    /// First day of the month the alert is for
+    pub month: NaiveDate,
+    pub threshold: i32,
+    pub spent: Decimal,
+    pub available: Decimal,
+    pub percent_used: Decimal,
+}
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -10,6 +12,8 @@ pub struct Category {
     pub name: String,
     pub icon: Option<String>,
     pub is_income: bool,
+    /// Parent for sub-categories, `None` for top-level categories
+    pub parent_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -20,4 +24,80 @@ pub struct CreateCategory {
     pub icon: Option<String>,
     #[serde(default)]
     pub is_income: bool,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+}
+
+/// Changes to an existing category
+///
+/// `None` leaves a field unchanged; `Some(None)` removes the icon or makes
+/// the category top-level
+#[derive(Debug, Clone, Default)]
+pub struct UpdateCategory {
+    pub name: Option<String>,
+    pub icon: Option<Option<String>>,
+    pub is_income: Option<bool>,
+    pub parent_id: Option<Option<Uuid>>,
+}
+
+/// A category with its sub-categories
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: Category,
+    pub children: Vec<CategoryNode>,
+}
+
+impl CategoryNode {
+    /// Arrange a flat list into trees, keeping the input order among siblings
+    ///
+    /// Categories whose parent is missing from the list become roots
+    pub fn build_tree(categories: Vec<Category>) -> Vec<CategoryNode> {
+        let ids: HashSet<Uuid> = categories.iter().map(|c| c.id).collect();
+        let mut children: HashMap<Uuid, Vec<Category>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for category in categories {
+            match category.parent_id.filter(|id| ids.contains(id)) {
+                Some(parent_id) => children.entry(parent_id).or_default().push(category),
+                None => roots.push(category),
+            }
+        }
+
+        fn attach(category: Category, children: &mut HashMap<Uuid, Vec<Category>>) -> CategoryNode {
+            let kids = children.remove(&category.id).unwrap_or_default();
+            CategoryNode {
+                category,
+                children: kids.into_iter().map(|c| attach(c, children)).collect(),
+            }
+        }
+
+        roots
+            .into_iter()
+            .map(|c| attach(c, &mut children))
+            .collect()
+    }
+}
+
+/// Spending/income for a category, including its sub-categories
+#[derive(Debug, Clone, Serialize)]
+pub struct CategorySummary {
+    pub category_id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub is_income: bool,
+    /// Sum of transactions directly in this category
+    pub own_total: Decimal,
+    /// `own_total` plus the totals of all sub-categories
+    pub total: Decimal,
+    pub transaction_count: i64,
+}
+
+/// Learned category suggestion for a store/description
+#[derive(Debug, Clone, Serialize)]
+pub struct CategorySuggestion {
+    pub category_id: Uuid,
+    pub name: String,
+    /// Posterior probability between 0 and 1
+    pub confidence: f64,
 }
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Saved column mapping for a bank's CSV export
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CsvImportProfile {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub delimiter: String,
+    pub has_header: bool,
+    /// Column references are header names, or 0-based indexes without a header
+    pub date_column: String,
+    pub amount_column: Option<String>,
+    pub debit_column: Option<String>,
+    pub credit_column: Option<String>,
+    pub payee_column: Option<String>,
+    pub description_column: Option<String>,
+    /// chrono format string, e.g. `%d/%m/%Y`
+    pub date_format: String,
+    pub decimal_separator: String,
+    pub encoding: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateCsvImportProfile {
+    pub user_id: Uuid,
+    pub name: String,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    #[serde(default = "default_has_header")]
+    pub has_header: bool,
+    pub date_column: String,
+    pub amount_column: Option<String>,
+    pub debit_column: Option<String>,
+    pub credit_column: Option<String>,
+    pub payee_column: Option<String>,
+    pub description_column: Option<String>,
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: String,
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+}
+
+fn default_delimiter() -> String {
+    ",".to_string()
+}
+
+fn default_has_header() -> bool {
+    true
+}
+
+fn default_date_format() -> String {
+    "%d/%m/%Y".to_string()
+}
+
+fn default_decimal_separator() -> String {
+    ",".to_string()
+}
+
+fn default_encoding() -> String {
+    "utf-8".to_string()
+}
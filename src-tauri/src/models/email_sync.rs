@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Gmail sync checkpoint for a user
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailSyncState {
+    pub user_id: Uuid,
+    /// Last Gmail `historyId` that has been fully processed
+    pub history_id: String,
+    pub last_sync_at: DateTime<Utc>,
+    pub last_full_sync_at: Option<DateTime<Utc>>,
+    /// Messages that could not be fetched, retried on the next sync
+    pub failed_message_ids: Vec<String>,
+}
@@ -0,0 +1,29 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Base currency of new users and currency of new accounts
+pub const DEFAULT_CURRENCY: &str = "EUR";
+
+/// Exchange rate on a date: 1 unit of `base` is worth `rate` units of `quote`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FxRate {
+    pub base: String,
+    pub quote: String,
+    pub rate_date: NaiveDate,
+    pub rate: Decimal,
+    /// Provider the rate came from
+    pub source: String,
+}
+
+/// Result of importing rates from a provider
+#[derive(Debug, Clone, Serialize)]
+pub struct RateImportSummary {
+    pub source: String,
+    pub rates: usize,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+    /// Transactions whose converted amount changed with the new rates
+    pub transactions_converted: u64,
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Canonical merchant that store names are linked to
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Merchant {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Matching key, see `categorize::text::merchant_key`
+    pub normalized_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MerchantAlias {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub merchant_id: Uuid,
+    pub alias: String,
+    pub normalized_alias: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Merchant with its aliases
+#[derive(Debug, Clone, Serialize)]
+pub struct MerchantWithAliases {
+    #[serde(flatten)]
+    pub merchant: Merchant,
+    pub aliases: Vec<String>,
+}
+
+/// Spending per merchant for a date range
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct MerchantSpending {
+    pub merchant_id: Uuid,
+    pub name: String,
+    /// Sum of signed amounts (expenses are negative)
+    pub total: Decimal,
+    pub transaction_count: i64,
+}
@@ -1,7 +1,37 @@
 mod user;
 mod category;
 mod transaction;
+mod email_sync;
+mod csv_profile;
+mod backup;
+mod rule;
+mod merchant;
+mod budget;
+mod recurring;
+mod subscription;
+mod account;
+mod transfer;
+mod fx_rate;
+mod split;
 
 pub use user::{User, CreateUser, UpdateUser};
-pub use category::{Category, CreateCategory};
-pub use transaction::{Transaction, CreateTransaction, TransactionFilter};
+pub use category::{
+    Category, CategoryNode, CategorySuggestion, CategorySummary, CreateCategory, UpdateCategory,
+};
+pub use transaction::{
+    Transaction, CreateTransaction, TransactionFilter, TransactionWithCategory, UpdateTransaction,
+};
+pub use email_sync::EmailSyncState;
+pub use csv_profile::{CsvImportProfile, CreateCsvImportProfile};
+pub use backup::{BackupArchive, BACKUP_FORMAT_VERSION};
+pub use rule::{CategorizationRule, CreateCategorizationRule, RuleMatch};
+pub use merchant::{Merchant, MerchantAlias, MerchantSpending, MerchantWithAliases};
+pub use budget::{Budget, BudgetAlert, BudgetStatus, SetBudget, DEFAULT_ALERT_THRESHOLDS};
+pub use recurring::{CreateRecurringTransaction, RecurringTransaction, RecurringWithUpcoming};
+pub use subscription::{DetectedSubscription, SubscriptionReport};
+pub use account::{
+    Account, AccountWithBalance, CreateAccount, TransactionWithBalance, DEFAULT_ACCOUNT_NAME,
+};
+pub use transfer::{CreateTransfer, Transfer};
+pub use fx_rate::{FxRate, RateImportSummary, DEFAULT_CURRENCY};
+pub use split::{SplitLine, TransactionSplit};
@@ -0,0 +1,65 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Transaction posted automatically on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RecurringTransaction {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub category_id: Option<Uuid>,
+    pub amount: Decimal,
+    pub store: Option<String>,
+    pub description: Option<String>,
+    /// `monthly`, `weekly`, `yearly` or `last_business_day`
+    pub frequency: String,
+    /// Day of the month for monthly schedules
+    pub day_of_month: Option<i16>,
+    pub start_date: NaiveDate,
+    /// Last possible occurrence, `None` repeats forever
+    pub end_date: Option<NaiveDate>,
+    /// Next occurrence to post
+    pub next_date: NaiveDate,
+    /// Occurrences that will not be posted
+    pub skipped_dates: Vec<NaiveDate>,
+    pub is_paused: bool,
+    /// Account occurrences are booked to, `None` for the default account
+    pub account_id: Option<Uuid>,
+    /// ISO 4217 code of `amount`, `None` for the account's currency
+    pub currency: Option<String>,
+    /// Occurrences are listed but not posted (the charge is imported)
+    pub reminder_only: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create a recurring transaction, or replace all fields of one
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateRecurringTransaction {
+    pub user_id: Uuid,
+    pub category_id: Option<Uuid>,
+    pub amount: Decimal,
+    pub store: Option<String>,
+    pub description: Option<String>,
+    pub frequency: String,
+    pub day_of_month: Option<i16>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub account_id: Option<Uuid>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub reminder_only: bool,
+}
+
+/// Recurring transaction with its next occurrences
+#[derive(Debug, Clone, Serialize)]
+pub struct RecurringWithUpcoming {
+    #[serde(flatten)]
+    pub recurring: RecurringTransaction,
+    /// Dates that will be posted next, skipped ones left out
+    pub upcoming: Vec<NaiveDate>,
+}
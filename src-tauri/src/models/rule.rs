@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Rule assigning a category to transactions that match it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CategorizationRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub category_id: Uuid,
+    pub name: String,
+    /// `store`, `description` or `any`
+    pub match_field: String,
+    /// `contains`, `exact` or `regex`, always case-insensitive
+    pub match_type: String,
+    /// `None` matches any text
+    pub pattern: Option<String>,
+    /// Inclusive bounds on the absolute amount
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub source: Option<String>,
+    /// Lower values are evaluated first
+    pub priority: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateCategorizationRule {
+    pub user_id: Uuid,
+    pub category_id: Uuid,
+    pub name: String,
+    #[serde(default = "default_match_field")]
+    pub match_field: String,
+    #[serde(default = "default_match_type")]
+    pub match_type: String,
+    pub pattern: Option<String>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_is_active")]
+    pub is_active: bool,
+}
+
+fn default_match_field() -> String {
+    "any".to_string()
+}
+
+fn default_match_type() -> String {
+    "contains".to_string()
+}
+
+fn default_is_active() -> bool {
+    true
+}
+
+/// A category change proposed (or made) by re-running the rules
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMatch {
+    pub transaction_id: Uuid,
+    pub store: Option<String>,
+    pub description: Option<String>,
+    pub amount: Decimal,
+    pub transaction_date: chrono::NaiveDate,
+    pub rule_id: Uuid,
+    pub category_id: Uuid,
+}
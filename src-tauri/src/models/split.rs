@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Part of a transaction booked to its own category
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TransactionSplit {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub category_id: Option<Uuid>,
+    /// In the transaction's currency
+    pub amount: Decimal,
+    pub note: Option<String>,
+    pub position: i16,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One line of a split, as entered
+#[derive(Debug, Clone, Deserialize)]
+pub struct SplitLine {
+    pub category_id: Option<Uuid>,
+    pub amount: Decimal,
+    pub note: Option<String>,
+}
@@ -0,0 +1,37 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Regular charge found in the transaction history
+///
+/// Amounts are positive costs at the latest price.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedSubscription {
+    /// Identifies the subscription for confirming or dismissing it
+    pub key: String,
+    pub merchant_id: Option<Uuid>,
+    pub name: String,
+    /// Category of the latest charge
+    pub category_id: Option<Uuid>,
+    /// `weekly`, `monthly` or `yearly`
+    pub frequency: String,
+    pub amount: Decimal,
+    /// Earlier amount when the latest price change was an increase
+    pub increased_from: Option<Decimal>,
+    pub occurrences: usize,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    /// Expected date of the next charge
+    pub next_date: NaiveDate,
+    pub monthly_cost: Decimal,
+    pub annual_cost: Decimal,
+}
+
+/// Detected subscriptions with their combined cost
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionReport {
+    pub subscriptions: Vec<DetectedSubscription>,
+    pub monthly_total: Decimal,
+    pub annual_total: Decimal,
+}
@@ -9,26 +9,88 @@ pub struct Transaction {
     pub id: Uuid,
     pub user_id: Uuid,
     pub category_id: Option<Uuid>,
+    /// Amount in the user's base currency
     pub amount: Decimal,
     pub store: Option<String>,
     pub description: Option<String>,
     pub source: Option<String>,
     pub email_message_id: Option<String>,
+    /// Bank supplied id (e.g. OFX FITID), unique per user and source
+    pub external_id: Option<String>,
     pub transaction_date: NaiveDate,
+    /// Value date from bank statements, if different from the booking date
+    pub value_date: Option<NaiveDate>,
+    /// Canonical merchant the store name was linked to
+    pub merchant_id: Option<Uuid>,
+    /// Missing in backups written before accounts existed
+    #[serde(default)]
+    pub account_id: Uuid,
+    /// Shared by both legs of a transfer between accounts
+    #[serde(default)]
+    pub transfer_id: Option<Uuid>,
+    /// ISO 4217 code of `original_amount` (empty in backups written before
+    /// currencies existed)
+    #[serde(default)]
+    pub currency: String,
+    /// Amount as charged, in `currency`
+    #[serde(default)]
+    pub original_amount: Decimal,
+    /// Base currency units per unit of `currency`, `None` while no rate is
+    /// known (`amount` is then unconverted)
+    #[serde(default)]
+    pub fx_rate: Option<Decimal>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Transaction joined with its category name (for exports and reports)
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TransactionWithCategory {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub transaction: Transaction,
+    pub category_name: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct CreateTransaction {
     pub user_id: Uuid,
     pub category_id: Option<Uuid>,
+    /// Amount in `currency`, converted to the base currency when saved
     pub amount: Decimal,
     pub store: Option<String>,
     pub description: Option<String>,
     #[serde(default = "default_source")]
     pub source: String,
     pub email_message_id: Option<String>,
+    pub external_id: Option<String>,
     pub transaction_date: NaiveDate,
+    pub value_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub merchant_id: Option<Uuid>,
+    /// `None` books the transaction to the user's default account
+    #[serde(default)]
+    pub account_id: Option<Uuid>,
+    #[serde(default)]
+    pub transfer_id: Option<Uuid>,
+    /// `None` uses the account's currency
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+/// Changes to an existing transaction
+///
+/// `None` leaves a field unchanged; for nullable fields `Some(None)` clears it
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTransaction {
+    pub category_id: Option<Option<Uuid>>,
+    /// New amount in the transaction's currency
+    pub amount: Option<Decimal>,
+    pub store: Option<Option<String>>,
+    pub description: Option<Option<String>>,
+    pub transaction_date: Option<NaiveDate>,
+    pub account_id: Option<Uuid>,
+    /// New currency of the amount
+    pub currency: Option<String>,
 }
 
 fn default_source() -> String {
@@ -40,8 +102,36 @@ pub struct TransactionFilter {
     pub start_date: Option<NaiveDate>,
     pub end_date: Option<NaiveDate>,
     pub category_id: Option<Uuid>,
+    pub account_id: Option<Uuid>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub year: Option<i32>,
     pub month: Option<u32>,
 }
+
+impl TransactionFilter {
+    /// Effective date range: explicit dates win, otherwise `year`/`month`
+    pub fn date_range(&self) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        if self.start_date.is_some() || self.end_date.is_some() {
+            return (self.start_date, self.end_date);
+        }
+
+        match (self.year, self.month) {
+            (Some(year), Some(month)) => {
+                let start = NaiveDate::from_ymd_opt(year, month, 1);
+                let end = if month == 12 {
+                    NaiveDate::from_ymd_opt(year + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(year, month + 1, 1)
+                }
+                .and_then(|d| d.pred_opt());
+                (start, end)
+            }
+            (Some(year), None) => (
+                NaiveDate::from_ymd_opt(year, 1, 1),
+                NaiveDate::from_ymd_opt(year, 12, 31),
+            ),
+            _ => (None, None),
+        }
+    }
+}
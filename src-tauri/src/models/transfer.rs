@@ -0,0 +1,27 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Transaction;
+
+/// Money moved between two of the user's accounts
+#[derive(Debug, Clone, Serialize)]
+pub struct Transfer {
+    pub transfer_id: Uuid,
+    /// Leg with the negative amount
+    pub from: Transaction,
+    /// Leg with the positive amount
+    pub to: Transaction,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateTransfer {
+    pub user_id: Uuid,
+    pub from_account_id: Uuid,
+    pub to_account_id: Uuid,
+    /// Amount moved, positive
+    pub amount: Decimal,
+    pub transaction_date: NaiveDate,
+    pub description: Option<String>,
+}
@@ -11,10 +11,17 @@ pub struct User {
     pub f_name: String,
     pub l_name: String,
     pub photo_url: Option<String>,
+    /// Currency summaries and budgets are in (missing in old backups)
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_base_currency() -> String {
+    super::DEFAULT_CURRENCY.to_string()
+}
+
 impl User {
     pub fn full_name(&self) -> String {
         format!("{} {}", self.f_name, self.l_name)
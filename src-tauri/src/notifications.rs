@@ -0,0 +1,59 @@
+use crate::models::BudgetAlert;
+use crate::services::BudgetService;
+use crate::DbPool;
+use chrono::NaiveDate;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use uuid::Uuid;
+
+/// App event emitted for every budget alert, the payload is a `BudgetAlert`
+pub const BUDGET_ALERT_EVENT: &str = "budget-alert";
+
+/// Check the budgets of the months `dates` fall in and notify about newly
+/// crossed thresholds
+///
+/// Runs after transactions are added or imported, with their dates. Failures
+/// are logged, the transactions are saved either way.
+pub async fn check_budget_alerts(
+    app: &AppHandle,
+    pool: &DbPool,
+    user_id: Uuid,
+    dates: &[NaiveDate],
+) {
+    let alerts = match BudgetService::new(pool.clone())
+        .check_alerts(user_id, dates)
+        .await
+    {
+        Ok(alerts) => alerts,
+        Err(e) => {
+            tracing::warn!("Budget alert check failed: {}", e);
+            return;
+        }
+    };
+
+    for alert in alerts {
+        notify(app, &alert);
+    }
+}
+
+fn notify(app: &AppHandle, alert: &BudgetAlert) {
+    if let Err(e) = app.emit(BUDGET_ALERT_EVENT, alert) {
+        tracing::warn!("Failed to emit budget alert: {}", e);
+    }
+
+    let title = format!(
+        "{} budget reached {}%",
+        alert.category_name, alert.threshold
+    );
+    let body = format!(
+        "Spent {} of {} in {} ({}%)",
+        alert.spent,
+        alert.available,
+        alert.month.format("%B %Y"),
+        alert.percent_used
+    );
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!("Failed to show budget notification: {}", e);
+    }
+}
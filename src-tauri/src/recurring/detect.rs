@@ -0,0 +1,242 @@
+//! Subscription detection from transaction history
+//!
+//! Charges at the same merchant are a subscription when their amounts are
+//! close to each other and the gaps between them match a weekly, monthly
+//! or yearly cadence. Occasional one-off purchases at the same merchant are
+//! ignored as long as the regular charges dominate.
+
+use super::{Frequency, Schedule};
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+
+/// Charges further than this from the typical amount (as a fraction) are
+/// treated as one-off purchases
+const AMOUNT_TOLERANCE: Decimal = Decimal::from_parts(25, 0, 0, false, 2);
+/// Share of gaps that must match the cadence
+const MIN_REGULAR_SHARE: f64 = 0.75;
+
+/// One expense, amount as stored (negative)
+#[derive(Debug, Clone, Copy)]
+pub struct Charge {
+    pub date: NaiveDate,
+    pub amount: Decimal,
+}
+
+/// Regular charge pattern found in a merchant's history
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub frequency: Frequency,
+    /// Number of charges that follow the pattern
+    pub occurrences: usize,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    /// Latest charged amount, positive
+    pub amount: Decimal,
+    /// Earlier amount, when the latest change was a price increase
+    pub increased_from: Option<Decimal>,
+    pub next_date: NaiveDate,
+}
+
+impl Pattern {
+    /// Average cost per month at the latest amount
+    pub fn monthly_cost(&self) -> Decimal {
+        match self.frequency {
+            Frequency::Weekly => (self.amount * Decimal::from(52) / Decimal::from(12)).round_dp(2),
+            Frequency::Yearly => (self.amount / Decimal::from(12)).round_dp(2),
+            Frequency::Monthly | Frequency::LastBusinessDay => self.amount,
+        }
+    }
+
+    /// Cost per year at the latest amount
+    pub fn annual_cost(&self) -> Decimal {
+        match self.frequency {
+            Frequency::Weekly => self.amount * Decimal::from(52),
+            Frequency::Yearly => self.amount,
+            Frequency::Monthly | Frequency::LastBusinessDay => self.amount * Decimal::from(12),
+        }
+    }
+}
+
+/// Detect a subscription in one merchant's charges, sorted oldest first
+///
+/// Returns `None` for irregular charges and for subscriptions that look
+/// cancelled (the next charge is well overdue at `today`).
+pub fn detect(charges: &[Charge], today: NaiveDate) -> Option<Pattern> {
+    let typical = median(charges.iter().map(|c| c.amount.abs()).collect())?;
+    if typical.is_zero() {
+        return None;
+    }
+
+    let regular: Vec<&Charge> = charges
+        .iter()
+        .filter(|c| ((c.amount.abs() - typical) / typical).abs() <= AMOUNT_TOLERANCE)
+        .collect();
+
+    let gaps: Vec<i64> = regular
+        .windows(2)
+        .map(|pair| (pair[1].date - pair[0].date).num_days())
+        .collect();
+
+    let (frequency, matching) = [Frequency::Weekly, Frequency::Monthly, Frequency::Yearly]
+        .into_iter()
+        .map(|frequency| {
+            let matching = gaps.iter().filter(|gap| fits(frequency, **gap)).count();
+            (frequency, matching)
+        })
+        .max_by_key(|(_, matching)| *matching)?;
+
+    let min_occurrences = match frequency {
+        Frequency::Yearly => 2,
+        _ => 3,
+    };
+    let is_regular = matching as f64 >= gaps.len() as f64 * MIN_REGULAR_SHARE;
+    if regular.len() < min_occurrences || !is_regular {
+        return None;
+    }
+
+    let first = regular.first()?;
+    let last = regular.last()?;
+    let schedule = Schedule {
+        frequency,
+        day_of_month: last.date.day(),
+        start_date: last.date,
+    };
+    let next_date = schedule.following(last.date);
+    if (today - next_date).num_days() > grace_days(frequency) {
+        return None;
+    }
+
+    let amount = last.amount.abs();
+    let increased_from = regular
+        .iter()
+        .rev()
+        .map(|c| c.amount.abs())
+        .find(|previous| *previous != amount)
+        .filter(|previous| *previous < amount);
+
+    Some(Pattern {
+        frequency,
+        occurrences: regular.len(),
+        first_date: first.date,
+        last_date: last.date,
+        amount,
+        increased_from,
+        next_date,
+    })
+}
+
+/// Whether a gap in days between two charges matches the cadence
+fn fits(frequency: Frequency, gap: i64) -> bool {
+    match frequency {
+        Frequency::Weekly => (6..=8).contains(&gap),
+        Frequency::Monthly | Frequency::LastBusinessDay => (26..=35).contains(&gap),
+        Frequency::Yearly => (350..=380).contains(&gap),
+    }
+}
+
+/// How late a charge may be before the subscription counts as cancelled
+fn grace_days(frequency: Frequency) -> i64 {
+    match frequency {
+        Frequency::Weekly => 7,
+        Frequency::Monthly | Frequency::LastBusinessDay => 15,
+        Frequency::Yearly => 45,
+    }
+}
+
+fn median(mut values: Vec<Decimal>) -> Option<Decimal> {
+    values.sort();
+    let mid = values.len() / 2;
+    match values.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((values[mid - 1] + values[mid]) / Decimal::TWO),
+        _ => Some(values[mid]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn charges(list: &[(&str, &str)]) -> Vec<Charge> {
+        list.iter()
+            .map(|(day, amount)| Charge {
+                date: date(day),
+                amount: amount.parse().unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn monthly_price_increase_ignoring_one_off_purchases() {
+        let history = charges(&[
+            ("2026-01-05", "-12.99"),
+            ("2026-02-05", "-12.99"),
+            ("2026-03-05", "-12.99"),
+            ("2026-03-20", "-80.00"),
+            ("2026-04-05", "-15.99"),
+        ]);
+
+        let pattern = detect(&history, date("2026-04-20")).unwrap();
+
+        assert_eq!(pattern.frequency, Frequency::Monthly);
+        assert_eq!(pattern.occurrences, 4);
+        assert_eq!(pattern.amount, Decimal::new(1599, 2));
+        assert_eq!(pattern.increased_from, Some(Decimal::new(1299, 2)));
+        assert_eq!(pattern.next_date, date("2026-05-05"));
+        assert_eq!(pattern.annual_cost(), Decimal::new(19188, 2));
+    }
+
+    #[test]
+    fn overdue_subscriptions_count_as_cancelled() {
+        let history = charges(&[
+            ("2026-01-05", "-9.99"),
+            ("2026-02-05", "-9.99"),
+            ("2026-03-05", "-9.99"),
+        ]);
+
+        assert!(detect(&history, date("2026-04-15")).is_some());
+        assert!(detect(&history, date("2026-05-01")).is_none());
+    }
+
+    #[test]
+    fn irregular_charges_are_not_subscriptions() {
+        let history = charges(&[
+            ("2026-01-05", "-20.00"),
+            ("2026-01-08", "-20.00"),
+            ("2026-02-17", "-20.00"),
+            ("2026-05-18", "-20.00"),
+        ]);
+
+        assert!(detect(&history, date("2026-05-20")).is_none());
+    }
+
+    #[test]
+    fn two_yearly_charges_are_enough() {
+        let history = charges(&[("2025-03-01", "-49.00"), ("2026-03-01", "-49.00")]);
+
+        let pattern = detect(&history, date("2026-04-01")).unwrap();
+
+        assert_eq!(pattern.frequency, Frequency::Yearly);
+        assert_eq!(pattern.next_date, date("2027-03-01"));
+        assert_eq!(pattern.monthly_cost(), Decimal::new(408, 2));
+    }
+
+    #[test]
+    fn weekly_monthly_cost_spreads_52_weeks() {
+        let history = charges(&[
+            ("2026-03-02", "-5.00"),
+            ("2026-03-09", "-5.00"),
+            ("2026-03-16", "-5.00"),
+            ("2026-03-23", "-5.00"),
+        ]);
+
+        let pattern = detect(&history, date("2026-03-25")).unwrap();
+
+        assert_eq!(pattern.frequency, Frequency::Weekly);
+        assert_eq!(pattern.monthly_cost(), Decimal::new(2167, 2));
+    }
+}
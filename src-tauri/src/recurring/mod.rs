@@ -0,0 +1,257 @@
+//! Schedules of recurring transactions
+//!
+//! A schedule is stored as a frequency name plus the recurring entry's start
+//! date (and day of month for monthly entries). Occurrences are computed on
+//! the fly; only the next one to post is stored.
+
+pub mod detect;
+
+use crate::error::{AppError, Result};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// How often a recurring transaction repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    /// Every month on a day of the month, clamped to the month's length
+    Monthly,
+    /// Every week on the start date's weekday
+    Weekly,
+    /// Every year on the start date's month and day
+    Yearly,
+    /// Last Monday to Friday of every month
+    LastBusinessDay,
+}
+
+impl Frequency {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "monthly" => Ok(Frequency::Monthly),
+            "weekly" => Ok(Frequency::Weekly),
+            "yearly" => Ok(Frequency::Yearly),
+            "last_business_day" => Ok(Frequency::LastBusinessDay),
+            other => Err(AppError::Validation(format!(
+                "Unknown frequency '{}' (expected monthly, weekly, yearly or last_business_day)",
+                other
+            ))),
+        }
+    }
+
+    /// Value stored in `recurring_transactions.frequency`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Monthly => "monthly",
+            Frequency::Weekly => "weekly",
+            Frequency::Yearly => "yearly",
+            Frequency::LastBusinessDay => "last_business_day",
+        }
+    }
+}
+
+/// When a recurring transaction comes due
+#[derive(Debug, Clone, Copy)]
+pub struct Schedule {
+    pub frequency: Frequency,
+    /// Day of the month for monthly schedules
+    pub day_of_month: u32,
+    /// First possible occurrence
+    pub start_date: NaiveDate,
+}
+
+impl Schedule {
+    pub fn new(frequency: &str, day_of_month: Option<i16>, start_date: NaiveDate) -> Result<Self> {
+        let frequency = Frequency::parse(frequency)?;
+        let day_of_month = match day_of_month {
+            Some(day) if !(1..=31).contains(&day) => {
+                return Err(AppError::Validation(format!(
+                    "Day of month must be between 1 and 31, got {}",
+                    day
+                )))
+            }
+            Some(day) => day as u32,
+            None => start_date.day(),
+        };
+
+        Ok(Self {
+            frequency,
+            day_of_month,
+            start_date,
+        })
+    }
+
+    /// First occurrence on or after `date` (never before the start date)
+    pub fn next_on_or_after(&self, date: NaiveDate) -> NaiveDate {
+        let date = date.max(self.start_date);
+
+        match self.frequency {
+            Frequency::Monthly => {
+                let this_month = clamp_day(date.year(), date.month(), self.day_of_month);
+                if this_month >= date {
+                    this_month
+                } else {
+                    let next = first_of_next_month(date);
+                    clamp_day(next.year(), next.month(), self.day_of_month)
+                }
+            }
+            Frequency::Weekly => {
+                let days_ahead = (self.start_date.weekday().num_days_from_monday() + 7
+                    - date.weekday().num_days_from_monday())
+                    % 7;
+                date + Days::new(days_ahead as u64)
+            }
+            Frequency::Yearly => {
+                let month = self.start_date.month();
+                let day = self.start_date.day();
+                let this_year = clamp_day(date.year(), month, day);
+                if this_year >= date {
+                    this_year
+                } else {
+                    clamp_day(date.year() + 1, month, day)
+                }
+            }
+            Frequency::LastBusinessDay => {
+                let this_month = last_business_day(date.year(), date.month());
+                if this_month >= date {
+                    this_month
+                } else {
+                    let next = first_of_next_month(date);
+                    last_business_day(next.year(), next.month())
+                }
+            }
+        }
+    }
+
+    /// Occurrence after `date`
+    pub fn following(&self, date: NaiveDate) -> NaiveDate {
+        self.next_on_or_after(date.succ_opt().unwrap_or(date))
+    }
+
+    /// The next `count` occurrences from `date` on, leaving out `skipped` ones
+    pub fn upcoming(
+        &self,
+        date: NaiveDate,
+        end_date: Option<NaiveDate>,
+        skipped: &[NaiveDate],
+        count: usize,
+    ) -> Vec<NaiveDate> {
+        let mut dates = Vec::with_capacity(count);
+        let mut next = self.next_on_or_after(date);
+        while dates.len() < count && end_date.is_none_or(|end| next <= end) {
+            if !skipped.contains(&next) {
+                dates.push(next);
+            }
+            next = self.following(next);
+        }
+
+        dates
+    }
+}
+
+fn first_of_next_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1)
+        .and_then(|d| d.checked_add_months(Months::new(1)))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// `day` of the month, or the month's last day if it is shorter
+fn clamp_day(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+fn last_business_day(year: i32, month: u32) -> NaiveDate {
+    let mut date = clamp_day(year, month, 31);
+    while matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+        date = date.pred_opt().unwrap_or(date);
+    }
+
+    date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn monthly_days_are_clamped_to_short_months() {
+        let schedule = Schedule::new("monthly", Some(31), date("2026-01-31")).unwrap();
+
+        assert_eq!(
+            schedule.upcoming(date("2026-01-01"), None, &[], 4),
+            vec![
+                date("2026-01-31"),
+                date("2026-02-28"),
+                date("2026-03-31"),
+                date("2026-04-30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn yearly_leap_day_falls_back_to_february_28() {
+        let schedule = Schedule::new("yearly", None, date("2024-02-29")).unwrap();
+
+        assert_eq!(schedule.following(date("2024-02-29")), date("2025-02-28"));
+        assert_eq!(
+            schedule.next_on_or_after(date("2027-06-01")),
+            date("2028-02-29")
+        );
+    }
+
+    #[test]
+    fn last_business_day_skips_weekends() {
+        let schedule = Schedule::new("last_business_day", None, date("2026-01-01")).unwrap();
+
+        // January 31 is a Saturday, February 28 a Saturday, May 31 a Sunday
+        assert_eq!(
+            schedule.upcoming(date("2026-01-01"), None, &[], 5),
+            vec![
+                date("2026-01-30"),
+                date("2026-02-27"),
+                date("2026-03-31"),
+                date("2026-04-30"),
+                date("2026-05-29"),
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_keeps_the_start_weekday() {
+        let schedule = Schedule::new("weekly", None, date("2026-03-04")).unwrap();
+
+        assert_eq!(
+            schedule.next_on_or_after(date("2026-03-05")),
+            date("2026-03-11")
+        );
+        assert_eq!(
+            schedule.next_on_or_after(date("2026-01-01")),
+            date("2026-03-04")
+        );
+    }
+
+    #[test]
+    fn upcoming_leaves_out_skipped_dates_and_stops_at_the_end() {
+        let schedule = Schedule::new("monthly", Some(15), date("2026-01-15")).unwrap();
+
+        assert_eq!(
+            schedule.upcoming(
+                date("2026-01-01"),
+                Some(date("2026-04-15")),
+                &[date("2026-02-15")],
+                10
+            ),
+            vec![date("2026-01-15"), date("2026-03-15"), date("2026-04-15")]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_days_and_frequencies() {
+        assert!(Schedule::new("monthly", Some(32), date("2026-01-01")).is_err());
+        assert!(Schedule::new("fortnightly", None, date("2026-01-01")).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use crate::db::repository::AccountRepository;
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::fx;
+use crate::models::{
+    Account, AccountWithBalance, CreateAccount, TransactionFilter, TransactionWithBalance,
+};
+use uuid::Uuid;
+
+const ACCOUNT_TYPES: [&str; 4] = ["checking", "credit_card", "cash", "savings"];
+
+pub struct AccountService {
+    pool: DbPool,
+}
+
+impl AccountService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user's accounts with their current balances
+    pub async fn get_accounts(&self, user_id: Uuid) -> Result<Vec<AccountWithBalance>> {
+        AccountRepository::find_with_balances(&self.pool, user_id).await
+    }
+
+    /// Create the account new transactions are booked to by default
+    pub async fn ensure_default_account(&self, user_id: Uuid) -> Result<()> {
+        AccountRepository::ensure_default(&self.pool, user_id).await
+    }
+
+    pub async fn create_account(&self, account: CreateAccount) -> Result<Account> {
+        let account = validate(account)?;
+        AccountRepository::create(&self.pool, account).await
+    }
+
+    pub async fn update_account(&self, id: Uuid, account: CreateAccount) -> Result<Account> {
+        ensure_account_owned(&self.pool, account.user_id, id).await?;
+        let account = validate(account)?;
+        AccountRepository::update(&self.pool, id, account)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Account {}", id)))
+    }
+
+    /// Delete an account without transactions (the default account is kept)
+    pub async fn delete_account(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        let account = ensure_account_owned(&self.pool, user_id, id).await?;
+        if account.is_default {
+            return Err(AppError::Validation(
+                "The default account cannot be deleted".to_string(),
+            ));
+        }
+
+        let count = AccountRepository::count_transactions(&self.pool, id).await?;
+        if count > 0 {
+            return Err(AppError::Validation(format!(
+                "{} still has {} transactions",
+                account.name, count
+            )));
+        }
+
+        if !AccountRepository::delete(&self.pool, id, user_id).await? {
+            return Err(AppError::NotFound(format!("Account {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// An account's transactions with running balances, filtered by date
+    pub async fn get_register(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<Vec<TransactionWithBalance>> {
+        ensure_account_owned(&self.pool, user_id, id).await?;
+        let (start_date, end_date) = filter.date_range();
+        AccountRepository::find_register(&self.pool, id, start_date, end_date).await
+    }
+}
+
+/// Load an account, checking that it belongs to the user
+pub(crate) async fn ensure_account_owned(
+    pool: &DbPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<Account> {
+    match AccountRepository::find_by_id(pool, id).await? {
+        Some(account) if account.user_id == user_id => Ok(account),
+        Some(_) => Err(AppError::Forbidden(format!(
+            "Account {} belongs to another user",
+            id
+        ))),
+        None => Err(AppError::NotFound(format!("Account {}", id))),
+    }
+}
+
+fn validate(mut account: CreateAccount) -> Result<CreateAccount> {
+    account.name = account.name.trim().to_string();
+    if account.name.is_empty() {
+        return Err(AppError::Validation(
+            "Account name cannot be empty".to_string(),
+        ));
+    }
+
+    if !ACCOUNT_TYPES.contains(&account.account_type.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Unknown account type '{}' (expected checking, credit_card, cash or savings)",
+            account.account_type
+        )));
+    }
+
+    account.currency = fx::normalize_code(&account.currency)?;
+
+    Ok(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateTransaction;
+    use crate::services::TransactionService;
+    use crate::test_support::create_user;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn cash_account(user_id: Uuid) -> CreateAccount {
+        CreateAccount {
+            user_id,
+            name: "Wallet".to_string(),
+            account_type: "cash".to_string(),
+            opening_balance: Decimal::new(5000, 2),
+            currency: "eur".to_string(),
+        }
+    }
+
+    async fn spend(pool: &PgPool, user_id: Uuid, account_id: Uuid, day: &str, amount: i64) {
+        TransactionService::new(pool.clone())
+            .create_transaction_from_dto(CreateTransaction {
+                user_id,
+                category_id: None,
+                amount: Decimal::new(-amount, 2),
+                store: Some("KIOSK".to_string()),
+                description: None,
+                source: "manual".to_string(),
+                email_message_id: None,
+                external_id: None,
+                transaction_date: date(day),
+                value_date: None,
+                merchant_id: None,
+                account_id: Some(account_id),
+                transfer_id: None,
+                currency: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn register_balances_include_earlier_transactions(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = AccountService::new(pool.clone());
+        let wallet = service.create_account(cash_account(user.id)).await.unwrap();
+        assert_eq!(wallet.currency, "EUR");
+        spend(&pool, user.id, wallet.id, "2026-02-20", 1000).await;
+        spend(&pool, user.id, wallet.id, "2026-03-02", 500).await;
+        spend(&pool, user.id, wallet.id, "2026-03-09", 250).await;
+
+        let filter = TransactionFilter {
+            year: Some(2026),
+            month: Some(3),
+            ..Default::default()
+        };
+        let register = service
+            .get_register(user.id, wallet.id, &filter)
+            .await
+            .unwrap();
+
+        let balances: Vec<Decimal> = register.iter().map(|row| row.balance).collect();
+        assert_eq!(balances, vec![Decimal::new(3250, 2), Decimal::new(3500, 2)]);
+        let accounts = service.get_accounts(user.id).await.unwrap();
+        let listed = accounts.iter().find(|a| a.account.id == wallet.id).unwrap();
+        assert_eq!(listed.balance, Decimal::new(3250, 2));
+        assert_eq!(listed.transaction_count, 3);
+    }
+
+    #[sqlx::test]
+    async fn only_empty_non_default_accounts_can_be_deleted(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        let service = AccountService::new(pool.clone());
+        let default = service.get_accounts(user.id).await.unwrap().remove(0);
+        let wallet = service.create_account(cash_account(user.id)).await.unwrap();
+        spend(&pool, user.id, wallet.id, "2026-03-02", 500).await;
+
+        let default_result = service.delete_account(user.id, default.account.id).await;
+        assert!(matches!(default_result, Err(AppError::Validation(_))));
+        let used = service.delete_account(user.id, wallet.id).await;
+        assert!(matches!(used, Err(AppError::Validation(_))));
+        let foreign = service.delete_account(other.id, wallet.id).await;
+        assert!(matches!(foreign, Err(AppError::Forbidden(_))));
+
+        let empty = service
+            .create_account(CreateAccount {
+                name: "Savings".to_string(),
+                ..cash_account(user.id)
+            })
+            .await
+            .unwrap();
+        service.delete_account(user.id, empty.id).await.unwrap();
+        assert_eq!(service.get_accounts(user.id).await.unwrap().len(), 2);
+    }
+}
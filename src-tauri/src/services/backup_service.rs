@@ -0,0 +1,630 @@
+use crate::categorize::learning::TrainingDelta;
+use crate::db::repository::{
+    AccountRepository, CategoryRepository, TrainingRepository, TransactionRepository,
+    TransactionSplitRepository, UserRepository,
+};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::models::{
+    BackupArchive, Category, CreateAccount, CreateCategory, CreateTransaction, SplitLine,
+    TransactionFilter, TransactionSplit, UpdateUser, User, BACKUP_FORMAT_VERSION,
+};
+use crate::services::merchant_service::link_merchant;
+use crate::services::transaction_service::ensure_booking_rate;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+/// gzip magic bytes, used to detect compressed archives on restore
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Result of writing a backup archive
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupSummary {
+    pub accounts: usize,
+    pub categories: usize,
+    pub transactions: usize,
+}
+
+/// Result of restoring a backup archive
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreSummary {
+    pub accounts_created: usize,
+    /// Archived accounts mapped onto existing ones (the default account, or
+    /// one with the same name)
+    pub accounts_matched: usize,
+    pub categories_created: usize,
+    /// Archived categories mapped onto existing ones (same name and parent)
+    pub categories_matched: usize,
+    pub transactions_restored: usize,
+    /// Transactions already present (restored before, or same email/bank id)
+    pub transactions_skipped: usize,
+}
+
+pub struct BackupService {
+    pool: DbPool,
+}
+
+impl BackupService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Write the user's profile, accounts, categories and transactions to a
+    /// JSON archive
+    pub async fn backup(&self, user: &User, path: &Path, compress: bool) -> Result<BackupSummary> {
+        let accounts = AccountRepository::find_by_user(&self.pool, user.id).await?;
+        let categories = CategoryRepository::find_by_user(&self.pool, user.id).await?;
+        let transactions = TransactionRepository::find_for_export(
+            &self.pool,
+            user.id,
+            &TransactionFilter::default(),
+        )
+        .await?
+        .into_iter()
+        .map(|row| row.transaction)
+        .collect();
+        let splits = TransactionSplitRepository::find_by_user(&self.pool, user.id).await?;
+
+        let archive = BackupArchive {
+            version: BACKUP_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Utc::now(),
+            user: user.clone(),
+            accounts,
+            categories,
+            transactions,
+            splits,
+        };
+
+        let json = serde_json::to_vec_pretty(&archive)
+            .map_err(|e| AppError::Export(format!("Failed to serialize backup: {}", e)))?;
+
+        let content = if compress {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&json)?;
+            encoder.finish()?
+        } else {
+            json
+        };
+        tokio::fs::write(path, content).await?;
+
+        tracing::info!(
+            "Backed up {} accounts, {} categories and {} transactions",
+            archive.accounts.len(),
+            archive.categories.len(),
+            archive.transactions.len()
+        );
+
+        Ok(BackupSummary {
+            accounts: archive.accounts.len(),
+            categories: archive.categories.len(),
+            transactions: archive.transactions.len(),
+        })
+    }
+
+    /// Restore an archive into the given user's account
+    ///
+    /// New ids are generated for everything and account and category
+    /// references are remapped, so an archive can be restored into another
+    /// database or account. Restoring twice does not create duplicates.
+    pub async fn restore(&self, user: &User, path: &Path) -> Result<RestoreSummary> {
+        let archive = read_archive(&tokio::fs::read(path).await?)?;
+
+        let mut db_tx = self.pool.begin().await?;
+
+        let existing_accounts = AccountRepository::find_by_user(&mut *db_tx, user.id).await?;
+        let mut account_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut accounts_created = 0;
+        let mut accounts_matched = 0;
+
+        for account in &archive.accounts {
+            let matched = existing_accounts.iter().find(|existing| {
+                (account.is_default && existing.is_default)
+                    || existing.name.eq_ignore_ascii_case(&account.name)
+            });
+
+            let id = match matched {
+                Some(existing) => {
+                    accounts_matched += 1;
+                    existing.id
+                }
+                None => {
+                    let created = AccountRepository::create(
+                        &mut *db_tx,
+                        CreateAccount {
+                            user_id: user.id,
+                            name: account.name.clone(),
+                            account_type: account.account_type.clone(),
+                            opening_balance: account.opening_balance,
+                            currency: account.currency.clone(),
+                        },
+                    )
+                    .await?;
+                    accounts_created += 1;
+                    created.id
+                }
+            };
+            account_ids.insert(account.id, id);
+        }
+
+        // Categories are matched by name among their siblings
+        let mut existing: HashMap<(Option<Uuid>, String), Uuid> =
+            CategoryRepository::find_by_user(&mut *db_tx, user.id)
+                .await?
+                .into_iter()
+                .map(|c| ((c.parent_id, c.name.to_lowercase()), c.id))
+                .collect();
+        let mut category_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut categories_created = 0;
+        let mut categories_matched = 0;
+
+        for category in parents_first(&archive.categories) {
+            let parent_id = category
+                .parent_id
+                .and_then(|id| category_ids.get(&id).copied());
+            let key = (parent_id, category.name.to_lowercase());
+
+            let id = match existing.get(&key) {
+                Some(id) => {
+                    categories_matched += 1;
+                    *id
+                }
+                None => {
+                    let created = CategoryRepository::create(
+                        &mut *db_tx,
+                        CreateCategory {
+                            user_id: user.id,
+                            name: category.name.clone(),
+                            icon: category.icon.clone(),
+                            is_income: category.is_income,
+                            parent_id,
+                        },
+                    )
+                    .await?;
+                    categories_created += 1;
+                    existing.insert(key, created.id);
+                    created.id
+                }
+            };
+            category_ids.insert(category.id, id);
+        }
+
+        let mut transactions_restored = 0;
+        let mut transactions_skipped = 0;
+        let mut training = TrainingDelta::default();
+        // Both legs of an archived transfer get the same new transfer id
+        let mut transfer_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut splits: HashMap<Uuid, Vec<TransactionSplit>> = HashMap::new();
+        for split in archive.splits {
+            splits.entry(split.transaction_id).or_default().push(split);
+        }
+
+        for tx in archive.transactions {
+            let archived_id = tx.id;
+            if TransactionRepository::is_restored(&mut *db_tx, user.id, archived_id).await? {
+                transactions_skipped += 1;
+                continue;
+            }
+
+            // Archives written before currencies existed only have the amount
+            let (amount, currency) = if tx.currency.is_empty() {
+                (tx.amount, None)
+            } else {
+                (tx.original_amount, Some(tx.currency))
+            };
+
+            let mut create = CreateTransaction {
+                user_id: user.id,
+                // References to categories missing from the archive are dropped
+                category_id: tx.category_id.and_then(|id| category_ids.get(&id).copied()),
+                amount,
+                store: tx.store,
+                description: tx.description,
+                source: tx.source.unwrap_or_else(|| "manual".to_string()),
+                email_message_id: tx.email_message_id,
+                external_id: tx.external_id,
+                transaction_date: tx.transaction_date,
+                value_date: tx.value_date,
+                merchant_id: None,
+                // Archives without accounts book everything to the default account
+                account_id: account_ids.get(&tx.account_id).copied(),
+                transfer_id: tx
+                    .transfer_id
+                    .map(|id| *transfer_ids.entry(id).or_insert_with(Uuid::new_v4)),
+                currency,
+            };
+
+            if let Some(message_id) = create.email_message_id.clone() {
+                match TransactionRepository::find_by_email_message_id(&mut *db_tx, &message_id)
+                    .await?
+                {
+                    Some(found) if found.user_id == user.id => {
+                        transactions_skipped += 1;
+                        continue;
+                    }
+                    // Message ids are unique across users, keep the row without it
+                    Some(_) => create.email_message_id = None,
+                    None => {}
+                }
+            }
+
+            if let Some(external_id) = &create.external_id {
+                let found = TransactionRepository::find_by_external_id(
+                    &mut *db_tx,
+                    user.id,
+                    &create.source,
+                    external_id,
+                )
+                .await?;
+                if found.is_some() {
+                    transactions_skipped += 1;
+                    continue;
+                }
+            }
+
+            if let Some(category_id) = create.category_id {
+                training.learn(
+                    category_id,
+                    create.store.as_deref(),
+                    create.description.as_deref(),
+                );
+            }
+
+            link_merchant(&mut db_tx, &mut create).await?;
+            ensure_booking_rate(
+                &mut *db_tx,
+                user.id,
+                create.account_id,
+                create.currency.as_deref(),
+                create.transaction_date,
+            )
+            .await?;
+            let created = TransactionRepository::create(&mut *db_tx, create).await?;
+            TransactionRepository::record_restored(&mut *db_tx, user.id, archived_id, created.id)
+                .await?;
+            for split in splits.remove(&archived_id).unwrap_or_default() {
+                let line = SplitLine {
+                    category_id: split
+                        .category_id
+                        .and_then(|id| category_ids.get(&id).copied()),
+                    amount: split.amount,
+                    note: split.note,
+                };
+                TransactionSplitRepository::create(&mut *db_tx, created.id, split.position, &line)
+                    .await?;
+            }
+            transactions_restored += 1;
+        }
+
+        TrainingRepository::apply(&mut db_tx, user.id, &training).await?;
+
+        db_tx.commit().await?;
+
+        // Only carry the profile over when restoring into the same person
+        if archive.user.email.eq_ignore_ascii_case(&user.email) {
+            UserRepository::update(
+                &self.pool,
+                user.id,
+                UpdateUser {
+                    f_name: Some(archive.user.f_name),
+                    l_name: Some(archive.user.l_name),
+                    photo_url: archive.user.photo_url,
+                },
+            )
+            .await?;
+        }
+
+        tracing::info!(
+            "Restored {} transactions ({} skipped), {} new accounts, {} new categories",
+            transactions_restored,
+            transactions_skipped,
+            accounts_created,
+            categories_created
+        );
+
+        Ok(RestoreSummary {
+            accounts_created,
+            accounts_matched,
+            categories_created,
+            categories_matched,
+            transactions_restored,
+            transactions_skipped,
+        })
+    }
+}
+
+/// Archived categories ordered so every parent comes before its children
+///
+/// Categories whose parent is missing from the archive (or that are part of
+/// a cycle) are treated as top-level.
+fn parents_first(categories: &[Category]) -> Vec<&Category> {
+    let archived: HashSet<Uuid> = categories.iter().map(|c| c.id).collect();
+    let mut placed: HashSet<Uuid> = HashSet::new();
+    let mut ordered = Vec::with_capacity(categories.len());
+    let mut pending: Vec<&Category> = categories.iter().collect();
+
+    while !pending.is_empty() {
+        let (ready, waiting): (Vec<&Category>, Vec<&Category>) =
+            pending.into_iter().partition(|c| match c.parent_id {
+                Some(parent_id) if archived.contains(&parent_id) => placed.contains(&parent_id),
+                _ => true,
+            });
+
+        if ready.is_empty() {
+            ordered.extend(waiting);
+            break;
+        }
+        placed.extend(ready.iter().map(|c| c.id));
+        ordered.extend(ready);
+        pending = waiting;
+    }
+
+    ordered
+}
+
+/// Decode a plain or gzip compressed archive, upgrading older versions
+fn read_archive(content: &[u8]) -> Result<BackupArchive> {
+    let json = if content.starts_with(&GZIP_MAGIC) {
+        let mut json = Vec::new();
+        GzDecoder::new(content)
+            .read_to_end(&mut json)
+            .map_err(|e| AppError::Validation(format!("Corrupt backup archive: {}", e)))?;
+        json
+    } else {
+        content.to_vec()
+    };
+
+    let invalid =
+        |e: serde_json::Error| AppError::Validation(format!("Invalid backup archive: {}", e));
+    let mut value: serde_json::Value = serde_json::from_slice(&json).map_err(invalid)?;
+
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| AppError::Validation("Backup archive has no version".to_string()))?;
+    if version > u64::from(BACKUP_FORMAT_VERSION) {
+        return Err(AppError::Validation(format!(
+            "Backup format version {} is newer than supported ({}), update the app first",
+            version, BACKUP_FORMAT_VERSION
+        )));
+    }
+
+    if version < 2 {
+        upgrade_v1(&mut value);
+    }
+
+    serde_json::from_value(value).map_err(invalid)
+}
+
+/// Version 1 archives have no accounts, and no splits when written before
+/// split transactions existed
+fn upgrade_v1(value: &mut serde_json::Value) {
+    if let Some(archive) = value.as_object_mut() {
+        archive.insert("accounts".to_string(), serde_json::json!([]));
+        archive
+            .entry("splits")
+            .or_insert_with(|| serde_json::json!([]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_user;
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+
+    fn coffee(user_id: Uuid) -> CreateTransaction {
+        CreateTransaction {
+            user_id,
+            category_id: None,
+            amount: Decimal::new(-250, 2),
+            store: Some("COFFEE ISLAND".to_string()),
+            description: None,
+            source: "manual".to_string(),
+            email_message_id: None,
+            external_id: None,
+            transaction_date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            value_date: None,
+            merchant_id: None,
+            account_id: None,
+            transfer_id: None,
+            currency: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn identical_transactions_are_restored_once_each(pool: PgPool) {
+        let owner = create_user(&pool, "owner@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        TransactionRepository::create(&pool, coffee(owner.id))
+            .await
+            .unwrap();
+        TransactionRepository::create(&pool, coffee(owner.id))
+            .await
+            .unwrap();
+
+        let service = BackupService::new(pool.clone());
+        let path = std::env::temp_dir().join(format!("{}.json", owner.id));
+        service.backup(&owner, &path, false).await.unwrap();
+
+        // The other user already has an identical coffee, it is not a duplicate
+        TransactionRepository::create(&pool, coffee(other.id))
+            .await
+            .unwrap();
+
+        let first = service.restore(&other, &path).await.unwrap();
+        let second = service.restore(&other, &path).await.unwrap();
+        let same_database = service.restore(&owner, &path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            (first.transactions_restored, first.transactions_skipped),
+            (2, 0)
+        );
+        assert_eq!(
+            (second.transactions_restored, second.transactions_skipped),
+            (0, 2)
+        );
+        assert_eq!(
+            (
+                same_database.transactions_restored,
+                same_database.transactions_skipped
+            ),
+            (0, 2)
+        );
+    }
+
+    #[sqlx::test]
+    async fn transactions_keep_their_account(pool: PgPool) {
+        let owner = create_user(&pool, "owner@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        let savings = AccountRepository::create(
+            &pool,
+            CreateAccount {
+                user_id: owner.id,
+                name: "Savings".to_string(),
+                account_type: "savings".to_string(),
+                opening_balance: Decimal::new(10000, 2),
+                currency: "EUR".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        TransactionRepository::create(&pool, coffee(owner.id))
+            .await
+            .unwrap();
+        TransactionRepository::create(
+            &pool,
+            CreateTransaction {
+                account_id: Some(savings.id),
+                ..coffee(owner.id)
+            },
+        )
+        .await
+        .unwrap();
+
+        let service = BackupService::new(pool.clone());
+        let path = std::env::temp_dir().join(format!("{}.json", owner.id));
+        service.backup(&owner, &path, true).await.unwrap();
+        let summary = service.restore(&other, &path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((summary.accounts_created, summary.accounts_matched), (1, 1));
+        let accounts = AccountRepository::find_with_balances(&pool, other.id)
+            .await
+            .unwrap();
+        let balances: Vec<(&str, i64, Decimal)> = accounts
+            .iter()
+            .map(|a| (a.account.name.as_str(), a.transaction_count, a.balance))
+            .collect();
+        assert_eq!(
+            balances,
+            vec![
+                ("Main account", 1, Decimal::new(-250, 2)),
+                ("Savings", 1, Decimal::new(9750, 2)),
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn version_1_archives_restore_into_the_default_account(pool: PgPool) {
+        let owner = create_user(&pool, "owner@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        TransactionRepository::create(&pool, coffee(owner.id))
+            .await
+            .unwrap();
+
+        let service = BackupService::new(pool.clone());
+        let path = std::env::temp_dir().join(format!("{}.json", owner.id));
+        service.backup(&owner, &path, false).await.unwrap();
+
+        let mut archive: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        let fields = archive.as_object_mut().unwrap();
+        fields.insert("version".to_string(), serde_json::json!(1));
+        fields.remove("accounts");
+        fields.remove("splits");
+        for tx in fields["transactions"].as_array_mut().unwrap() {
+            tx.as_object_mut().unwrap().remove("account_id");
+        }
+        std::fs::write(&path, serde_json::to_vec(&archive).unwrap()).unwrap();
+
+        let summary = service.restore(&other, &path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(summary.transactions_restored, 1);
+        let accounts = AccountRepository::find_with_balances(&pool, other.id)
+            .await
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].transaction_count, 1);
+    }
+
+    #[test]
+    fn rejects_archives_from_newer_versions() {
+        let json = serde_json::json!({ "version": BACKUP_FORMAT_VERSION + 1 });
+
+        let result = read_archive(json.to_string().as_bytes());
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[sqlx::test]
+    async fn sub_categories_with_the_same_name_are_restored_under_their_parents(pool: PgPool) {
+        let owner = create_user(&pool, "owner@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        for parent in ["Food", "Bills"] {
+            let parent = CategoryRepository::create(
+                &pool,
+                CreateCategory {
+                    user_id: owner.id,
+                    name: parent.to_string(),
+                    icon: None,
+                    is_income: false,
+                    parent_id: None,
+                },
+            )
+            .await
+            .unwrap();
+            CategoryRepository::create(
+                &pool,
+                CreateCategory {
+                    user_id: owner.id,
+                    name: "Other".to_string(),
+                    icon: None,
+                    is_income: false,
+                    parent_id: Some(parent.id),
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let service = BackupService::new(pool.clone());
+        let path = std::env::temp_dir().join(format!("{}.json", owner.id));
+        service.backup(&owner, &path, false).await.unwrap();
+        let first = service.restore(&other, &path).await.unwrap();
+        let second = service.restore(&other, &path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(first.categories_created, 4);
+        assert_eq!(
+            (second.categories_created, second.categories_matched),
+            (0, 4)
+        );
+        let restored = CategoryRepository::find_by_user(&pool, other.id)
+            .await
+            .unwrap();
+        let others = restored
+            .iter()
+            .filter(|c| c.name == "Other" && c.parent_id.is_some())
+            .count();
+        assert_eq!(others, 2);
+    }
+}
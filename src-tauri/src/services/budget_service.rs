@@ -0,0 +1,340 @@
+use crate::db::repository::{BudgetRepository, CategoryRepository};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::models::{Budget, BudgetAlert, BudgetStatus, SetBudget, TransactionFilter};
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+pub struct BudgetService {
+    pool: DbPool,
+}
+
+impl BudgetService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_budgets(&self, user_id: Uuid) -> Result<Vec<Budget>> {
+        BudgetRepository::find_by_user(&self.pool, user_id).await
+    }
+
+    /// Set the monthly budget of an expense category
+    pub async fn set_budget(&self, mut budget: SetBudget) -> Result<Budget> {
+        if budget.amount <= Decimal::ZERO {
+            return Err(AppError::Validation(
+                "Budget amount must be greater than zero".to_string(),
+            ));
+        }
+
+        match CategoryRepository::find_by_id(&self.pool, budget.category_id).await? {
+            Some(category) if category.user_id != budget.user_id => {
+                return Err(AppError::Forbidden(format!(
+                    "Category {} belongs to another user",
+                    budget.category_id
+                )));
+            }
+            Some(category) if category.is_income => {
+                return Err(AppError::Validation(format!(
+                    "{} is an income category and cannot have a budget",
+                    category.name
+                )));
+            }
+            Some(_) => {}
+            None => {
+                return Err(AppError::NotFound(format!(
+                    "Category {}",
+                    budget.category_id
+                )))
+            }
+        }
+
+        if let Some(threshold) = budget.alert_thresholds.iter().find(|t| **t <= 0) {
+            return Err(AppError::Validation(format!(
+                "Alert threshold must be a positive percentage, got {}",
+                threshold
+            )));
+        }
+        budget.alert_thresholds.sort_unstable();
+        budget.alert_thresholds.dedup();
+
+        budget.start_month = budget.start_month.with_day(1).unwrap_or(budget.start_month);
+        BudgetRepository::upsert(&self.pool, budget).await
+    }
+
+    pub async fn delete_budget(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        if !BudgetRepository::delete(&self.pool, id, user_id).await? {
+            return Err(AppError::NotFound(format!("Budget {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Spent, remaining and projection per budget for a month
+    pub async fn get_budget_status(
+        &self,
+        user_id: Uuid,
+        year: i32,
+        month: u32,
+    ) -> Result<Vec<BudgetStatus>> {
+        let filter = TransactionFilter {
+            year: Some(year),
+            month: Some(month),
+            ..Default::default()
+        };
+        let (Some(month_start), Some(month_end)) = filter.date_range() else {
+            return Err(AppError::Validation("Invalid year/month".to_string()));
+        };
+
+        let today = chrono::Local::now().date_naive();
+        BudgetRepository::get_status(&self.pool, user_id, month_start, month_end, today).await
+    }
+
+    /// Alerts for thresholds crossed in the months containing `dates`
+    ///
+    /// Every month is checked once, however many of the dates fall in it (an
+    /// import can add transactions to several months). Each threshold of a
+    /// budget is reported once per month; thresholds already reported are
+    /// skipped.
+    pub async fn check_alerts(
+        &self,
+        user_id: Uuid,
+        dates: &[NaiveDate],
+    ) -> Result<Vec<BudgetAlert>> {
+        let months: BTreeSet<NaiveDate> = dates
+            .iter()
+            .map(|date| date.with_day(1).unwrap_or(*date))
+            .collect();
+        let budgets = BudgetRepository::find_by_user(&self.pool, user_id).await?;
+
+        let mut alerts = Vec::new();
+        for month in months {
+            alerts.extend(self.check_month_alerts(user_id, &budgets, month).await?);
+        }
+
+        Ok(alerts)
+    }
+
+    async fn check_month_alerts(
+        &self,
+        user_id: Uuid,
+        budgets: &[Budget],
+        month: NaiveDate,
+    ) -> Result<Vec<BudgetAlert>> {
+        let status = self
+            .get_budget_status(user_id, month.year(), month.month())
+            .await?;
+
+        let mut alerts = Vec::new();
+        for budget in budgets {
+            let Some(status) = status.iter().find(|s| s.budget_id == budget.id) else {
+                continue;
+            };
+            if status.available <= Decimal::ZERO {
+                continue;
+            }
+
+            let percent_used = status.spent * Decimal::from(100) / status.available;
+
+            // Record every crossed threshold, but only report the highest new one
+            let mut newest = None;
+            for &threshold in &budget.alert_thresholds {
+                if percent_used < Decimal::from(threshold) {
+                    continue;
+                }
+                if BudgetRepository::record_alert(&self.pool, budget.id, user_id, month, threshold)
+                    .await?
+                {
+                    newest = Some(threshold);
+                }
+            }
+
+            if let Some(threshold) = newest {
+                alerts.push(BudgetAlert {
+                    budget_id: budget.id,
+                    category_id: budget.category_id,
+                    category_name: status.category_name.clone(),
+                    month,
+                    threshold,
+                    spent: status.spent,
+                    available: status.available,
+                    percent_used: percent_used.round_dp(1),
+                });
+            }
+        }
+
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Category, CreateCategory, CreateTransaction, SplitLine};
+    use crate::services::TransactionService;
+    use crate::test_support::create_user;
+    use sqlx::PgPool;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    async fn category(pool: &PgPool, user_id: Uuid, name: &str, parent: Option<Uuid>) -> Category {
+        CategoryRepository::create(
+            pool,
+            CreateCategory {
+                user_id,
+                name: name.to_string(),
+                icon: None,
+                is_income: false,
+                parent_id: parent,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn spend(
+        pool: &PgPool,
+        user_id: Uuid,
+        category_id: Option<Uuid>,
+        day: &str,
+        amount: i64,
+    ) -> Uuid {
+        TransactionService::new(pool.clone())
+            .create_transaction_from_dto(CreateTransaction {
+                user_id,
+                category_id,
+                amount: Decimal::new(-amount, 2),
+                store: Some("SHOP".to_string()),
+                description: None,
+                source: "manual".to_string(),
+                email_message_id: None,
+                external_id: None,
+                transaction_date: date(day),
+                value_date: None,
+                merchant_id: None,
+                account_id: None,
+                transfer_id: None,
+                currency: None,
+            })
+            .await
+            .unwrap()
+            .id
+    }
+
+    fn budget(user_id: Uuid, category_id: Uuid, rollover: bool, thresholds: Vec<i32>) -> SetBudget {
+        SetBudget {
+            user_id,
+            category_id,
+            amount: Decimal::new(10000, 2),
+            rollover,
+            start_month: date("2026-01-15"),
+            alert_thresholds: thresholds,
+        }
+    }
+
+    #[sqlx::test]
+    async fn rollover_carries_unused_amounts_but_not_overspending(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let groceries = category(&pool, user.id, "Groceries", None).await;
+        let bakery = category(&pool, user.id, "Bakery", Some(groceries.id)).await;
+        let household = category(&pool, user.id, "Household", None).await;
+        BudgetService::new(pool.clone())
+            .set_budget(budget(user.id, groceries.id, true, vec![]))
+            .await
+            .unwrap();
+
+        spend(&pool, user.id, Some(groceries.id), "2026-01-10", 6000).await;
+        spend(&pool, user.id, Some(groceries.id), "2026-02-10", 13000).await;
+        // March: a sub-category purchase and one line of a split
+        spend(&pool, user.id, Some(bakery.id), "2026-03-02", 2000).await;
+        let split = spend(&pool, user.id, None, "2026-03-05", 5000).await;
+        let line = |category_id: Uuid, amount: i64| SplitLine {
+            category_id: Some(category_id),
+            amount: Decimal::new(-amount, 2),
+            note: None,
+        };
+        TransactionService::new(pool.clone())
+            .set_splits(
+                user.id,
+                split,
+                None,
+                vec![line(groceries.id, 1000), line(household.id, 4000)],
+            )
+            .await
+            .unwrap();
+
+        let status = BudgetRepository::get_status(
+            &pool,
+            user.id,
+            date("2026-03-01"),
+            date("2026-03-31"),
+            date("2026-03-10"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status.len(), 1);
+        let march = &status[0];
+        assert_eq!(march.carried_over, Decimal::new(4000, 2));
+        assert_eq!(march.available, Decimal::new(14000, 2));
+        assert_eq!(march.spent, Decimal::new(3000, 2));
+        assert_eq!(march.remaining, Decimal::new(11000, 2));
+        assert_eq!(march.projected_spent, Decimal::new(9300, 2));
+        assert!(!march.over_budget);
+    }
+
+    #[sqlx::test]
+    async fn each_threshold_alerts_once_per_month(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let groceries = category(&pool, user.id, "Groceries", None).await;
+        let service = BudgetService::new(pool.clone());
+        service
+            .set_budget(budget(user.id, groceries.id, false, vec![80, 50, 80]))
+            .await
+            .unwrap();
+
+        spend(&pool, user.id, Some(groceries.id), "2026-03-02", 8500).await;
+        let first = service
+            .check_alerts(user.id, &[date("2026-03-20")])
+            .await
+            .unwrap();
+        let again = service
+            .check_alerts(user.id, &[date("2026-03-21")])
+            .await
+            .unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].threshold, 80);
+        assert_eq!(first[0].percent_used, Decimal::new(850, 1));
+        assert!(again.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn every_month_of_an_import_is_checked(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let groceries = category(&pool, user.id, "Groceries", None).await;
+        let service = BudgetService::new(pool.clone());
+        service
+            .set_budget(budget(user.id, groceries.id, false, vec![80]))
+            .await
+            .unwrap();
+
+        // A statement reaching back into last month
+        spend(&pool, user.id, Some(groceries.id), "2026-02-10", 9000).await;
+        spend(&pool, user.id, Some(groceries.id), "2026-02-20", 500).await;
+        spend(&pool, user.id, Some(groceries.id), "2026-03-02", 8500).await;
+        let alerts = service
+            .check_alerts(
+                user.id,
+                &[date("2026-02-10"), date("2026-02-20"), date("2026-03-02")],
+            )
+            .await
+            .unwrap();
+
+        let months: Vec<NaiveDate> = alerts.iter().map(|alert| alert.month).collect();
+        assert_eq!(months, vec![date("2026-02-01"), date("2026-03-01")]);
+    }
+}
@@ -1,7 +1,14 @@
-use crate::db::repository::CategoryRepository;
+use crate::db::repository::{
+    BudgetRepository, CategoryRepository, RecurringRepository, RuleRepository, TrainingRepository,
+    TransactionRepository, TransactionSplitRepository,
+};
 use crate::db::DbPool;
 use crate::error::{AppError, Result};
-use crate::models::{Category, CreateCategory};
+use crate::models::{Category, CategoryNode, CategorySummary, CreateCategory, UpdateCategory};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{Acquire, PgConnection, PgExecutor};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 pub struct CategoryService {
@@ -13,14 +20,10 @@ impl CategoryService {
         Self { pool }
     }
 
+    /// Create a category; sub-categories take `is_income` from their parent
     pub async fn create_category(&self, category: CreateCategory) -> Result<Category> {
-        if category.name.trim().is_empty() {
-            return Err(AppError::Validation(
-                "Category name cannot be empty".to_string(),
-            ));
-        }
-
-        CategoryRepository::create(&self.pool, category).await
+        let mut conn = self.pool.acquire().await?;
+        create_category_in(&mut conn, category).await
     }
 
     pub async fn get_category(&self, id: Uuid) -> Result<Option<Category>> {
@@ -31,6 +34,62 @@ impl CategoryService {
         CategoryRepository::find_by_user(&self.pool, user_id).await
     }
 
+    /// Categories arranged as parent/sub-category trees
+    pub async fn get_category_tree(&self, user_id: Uuid) -> Result<Vec<CategoryNode>> {
+        CategoryRepository::find_tree_by_user(&self.pool, user_id).await
+    }
+
+    /// Totals per category for a date range, sub-categories rolled up into parents
+    pub async fn get_category_summary(
+        &self,
+        user_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<CategorySummary>> {
+        let categories = CategoryRepository::find_by_user(&self.pool, user_id).await?;
+        let totals =
+            TransactionRepository::get_category_totals(&self.pool, user_id, start_date, end_date)
+                .await?;
+
+        let parents: HashMap<Uuid, Option<Uuid>> =
+            categories.iter().map(|c| (c.id, c.parent_id)).collect();
+        let mut own: HashMap<Uuid, (Decimal, i64)> = HashMap::new();
+        let mut rolled_up: HashMap<Uuid, (Decimal, i64)> = HashMap::new();
+
+        for (category_id, amount, count) in totals {
+            own.insert(category_id, (amount, count));
+
+            // Add to the category itself and every ancestor (the visited set
+            // guards against cycles in inconsistent data)
+            let mut current = Some(category_id);
+            let mut visited = HashSet::new();
+            while let Some(id) = current.filter(|id| visited.insert(*id)) {
+                let entry = rolled_up.entry(id).or_insert((Decimal::ZERO, 0));
+                entry.0 += amount;
+                entry.1 += count;
+                current = parents.get(&id).copied().flatten();
+            }
+        }
+
+        Ok(categories
+            .into_iter()
+            .map(|c| {
+                let (own_total, _) = own.get(&c.id).copied().unwrap_or((Decimal::ZERO, 0));
+                let (total, transaction_count) =
+                    rolled_up.get(&c.id).copied().unwrap_or((Decimal::ZERO, 0));
+                CategorySummary {
+                    category_id: c.id,
+                    name: c.name,
+                    parent_id: c.parent_id,
+                    is_income: c.is_income,
+                    own_total,
+                    total,
+                    transaction_count,
+                }
+            })
+            .collect())
+    }
+
     pub async fn get_income_categories(&self, user_id: Uuid) -> Result<Vec<Category>> {
         CategoryRepository::find_income_categories(&self.pool, user_id).await
     }
@@ -39,8 +98,161 @@ impl CategoryService {
         CategoryRepository::find_expense_categories(&self.pool, user_id).await
     }
 
-    pub async fn delete_category(&self, id: Uuid) -> Result<()> {
-        CategoryRepository::delete(&self.pool, id).await
+    /// Rename, re-icon, move or change the income flag of a category
+    ///
+    /// Moving under a parent adopts the parent's `is_income`; changing the
+    /// flag of a parent applies it to all of its sub-categories
+    pub async fn update_category(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        mut changes: UpdateCategory,
+    ) -> Result<Category> {
+        if let Some(name) = &changes.name {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(AppError::Validation(
+                    "Category name cannot be empty".to_string(),
+                ));
+            }
+            changes.name = Some(name.to_string());
+        }
+
+        let current = self.ensure_owned(user_id, id).await?;
+
+        let parent_id = changes.parent_id.unwrap_or(current.parent_id);
+        if let Some(parent_id) = parent_id {
+            let parent = self.ensure_owned(user_id, parent_id).await?;
+            if changes.parent_id.is_some() {
+                self.ensure_not_descendant(user_id, id, parent_id).await?;
+            }
+            changes.is_income = Some(parent.is_income);
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        let category = CategoryRepository::update(&mut *db_tx, id, user_id, &changes)
+            .await
+            .map_err(|e| name_conflict(e, changes.name.as_deref().unwrap_or(&current.name)))?
+            .ok_or_else(|| AppError::NotFound(format!("Category {}", id)))?;
+
+        if category.is_income != current.is_income {
+            CategoryRepository::set_income_for_descendants(
+                &mut *db_tx,
+                id,
+                user_id,
+                category.is_income,
+            )
+            .await?;
+        }
+        db_tx.commit().await?;
+
+        Ok(category)
+    }
+
+    /// Delete a category, its transactions become uncategorized and its
+    /// sub-categories top-level
+    pub async fn delete_category(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        let category = self.ensure_owned(user_id, id).await?;
+
+        let deleted = CategoryRepository::delete(&self.pool, id, user_id)
+            .await
+            .map_err(|e| children_conflict(e, &category.name))?;
+        if !deleted {
+            return Err(AppError::NotFound(format!("Category {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Move all transactions of `source_id` into `target_id` and delete the source
+    ///
+    /// Everything else referring to the source (split lines, rules, learned
+    /// suggestions, recurring transactions, its budget) moves to the target
+    /// as well. Merging two
+    /// categories that both have a budget is rejected. Returns the number of transactions moved
+    pub async fn merge_categories(
+        &self,
+        user_id: Uuid,
+        source_id: Uuid,
+        target_id: Uuid,
+    ) -> Result<u64> {
+        if source_id == target_id {
+            return Err(AppError::Validation(
+                "Cannot merge a category into itself".to_string(),
+            ));
+        }
+
+        let source = self.ensure_owned(user_id, source_id).await?;
+        self.ensure_owned(user_id, target_id).await?;
+        self.ensure_not_descendant(user_id, source_id, target_id)
+            .await?;
+
+        let budgets = BudgetRepository::find_by_user(&self.pool, user_id).await?;
+        let has_budget = |id: Uuid| budgets.iter().any(|b| b.category_id == id);
+        if has_budget(source_id) && has_budget(target_id) {
+            return Err(AppError::Validation(format!(
+                "Both categories have a budget, delete the budget of '{}' before merging",
+                source.name
+            )));
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        let moved =
+            TransactionRepository::reassign_category(&mut *db_tx, user_id, source_id, target_id)
+                .await?;
+        TransactionSplitRepository::reassign_category(&mut *db_tx, user_id, source_id, target_id)
+            .await?;
+        // Rules would be deleted with the source (ON DELETE CASCADE)
+        RuleRepository::reassign_category(&mut *db_tx, user_id, source_id, target_id).await?;
+        BudgetRepository::reassign_category(&mut *db_tx, user_id, source_id, target_id).await?;
+        // Otherwise future occurrences would be posted uncategorized
+        RecurringRepository::reassign_category(&mut *db_tx, user_id, source_id, target_id).await?;
+        // The moved transactions keep counting for suggestions
+        TrainingRepository::merge_category(&mut db_tx, user_id, source_id, target_id).await?;
+        // Sub-categories of the source move under the target
+        CategoryRepository::reparent_children(&mut *db_tx, user_id, source_id, target_id)
+            .await
+            .map_err(|e| children_conflict(e, &source.name))?;
+        CategoryRepository::delete(&mut *db_tx, source_id, user_id).await?;
+        db_tx.commit().await?;
+
+        tracing::info!(
+            "Merged category {} into {} ({} transactions)",
+            source_id,
+            target_id,
+            moved
+        );
+        Ok(moved)
+    }
+
+    /// Reject `candidate` if it is `id` itself or one of its sub-categories
+    ///
+    /// Used before making `candidate` the parent of `id` (or of its children)
+    async fn ensure_not_descendant(&self, user_id: Uuid, id: Uuid, candidate: Uuid) -> Result<()> {
+        let parents: HashMap<Uuid, Option<Uuid>> =
+            CategoryRepository::find_by_user(&self.pool, user_id)
+                .await?
+                .into_iter()
+                .map(|c| (c.id, c.parent_id))
+                .collect();
+
+        let mut current = Some(candidate);
+        let mut visited = HashSet::new();
+        while let Some(node) = current.filter(|node| visited.insert(*node)) {
+            if node == id {
+                return Err(AppError::Validation(
+                    "A category cannot be placed under itself or its own sub-category".to_string(),
+                ));
+            }
+            current = parents.get(&node).copied().flatten();
+        }
+
+        Ok(())
+    }
+
+    /// Distinguish a missing category from one owned by another user
+    async fn ensure_owned(&self, user_id: Uuid, id: Uuid) -> Result<Category> {
+        find_owned(&self.pool, user_id, id).await
     }
 
     /// Create default categories for a new user
@@ -68,6 +280,7 @@ impl CategoryService {
                     name: name.to_string(),
                     icon: icon.map(|s| s.to_string()),
                     is_income,
+                    parent_id: None,
                 },
             )
             .await?;
@@ -77,3 +290,563 @@ impl CategoryService {
         Ok(categories)
     }
 }
+
+/// Validate and create a category on an open connection
+///
+/// Used by `create_category` and by imports creating categories inside their
+/// own database transaction. The insert runs in a savepoint, so a taken name
+/// is reported without aborting the caller's transaction.
+pub(crate) async fn create_category_in(
+    conn: &mut PgConnection,
+    mut category: CreateCategory,
+) -> Result<Category> {
+    category.name = category.name.trim().to_string();
+    if category.name.is_empty() {
+        return Err(AppError::Validation(
+            "Category name cannot be empty".to_string(),
+        ));
+    }
+
+    if let Some(parent_id) = category.parent_id {
+        let parent = find_owned(&mut *conn, category.user_id, parent_id).await?;
+        category.is_income = parent.is_income;
+    }
+
+    let name = category.name.clone();
+    let mut savepoint = conn.begin().await?;
+    match CategoryRepository::create(&mut *savepoint, category).await {
+        Ok(category) => {
+            savepoint.commit().await?;
+            Ok(category)
+        }
+        Err(e) => {
+            savepoint.rollback().await?;
+            Err(name_conflict(e, &name))
+        }
+    }
+}
+
+/// Distinguish a missing category from one owned by another user
+async fn find_owned<'e, E>(executor: E, user_id: Uuid, id: Uuid) -> Result<Category>
+where
+    E: PgExecutor<'e>,
+{
+    match CategoryRepository::find_by_id(executor, id).await? {
+        Some(category) if category.user_id == user_id => Ok(category),
+        Some(_) => Err(AppError::Forbidden(format!(
+            "Category {} belongs to another user",
+            id
+        ))),
+        None => Err(AppError::NotFound(format!("Category {}", id))),
+    }
+}
+
+/// Whether an error is a duplicate name among sibling categories
+fn is_name_conflict(error: &AppError) -> bool {
+    matches!(
+        error,
+        AppError::Database(sqlx::Error::Database(db))
+            if matches!(db.constraint(), Some("unique_top_level_category" | "unique_sub_category"))
+    )
+}
+
+/// Turn a duplicate sibling name into a readable error
+fn name_conflict(error: AppError, name: &str) -> AppError {
+    if is_name_conflict(&error) {
+        AppError::Validation(format!("A category named '{}' already exists there", name))
+    } else {
+        error
+    }
+}
+
+/// Turn a duplicate name caused by moving the sub-categories of `name`
+/// into a readable error
+fn children_conflict(error: AppError, name: &str) -> AppError {
+    if is_name_conflict(&error) {
+        AppError::Validation(format!(
+            "A sub-category of '{}' has the same name as a category it would move next to, rename it first",
+            name
+        ))
+    } else {
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_user;
+    use sqlx::PgPool;
+
+    fn category(user_id: Uuid, name: &str, parent_id: Option<Uuid>) -> CreateCategory {
+        CreateCategory {
+            user_id,
+            name: name.to_string(),
+            icon: None,
+            is_income: false,
+            parent_id,
+        }
+    }
+
+    #[sqlx::test]
+    async fn names_are_unique_among_siblings(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool);
+
+        let food = service
+            .create_category(category(user.id, "Food", None))
+            .await
+            .unwrap();
+        let bills = service
+            .create_category(category(user.id, "Bills", None))
+            .await
+            .unwrap();
+        service
+            .create_category(category(user.id, "Other", Some(food.id)))
+            .await
+            .unwrap();
+        service
+            .create_category(category(user.id, "Other", Some(bills.id)))
+            .await
+            .unwrap();
+        service
+            .create_category(category(user.id, "Other", None))
+            .await
+            .unwrap();
+
+        for parent_id in [Some(food.id), None] {
+            let duplicate = service
+                .create_category(category(user.id, "Other", parent_id))
+                .await;
+            assert!(matches!(duplicate, Err(AppError::Validation(_))));
+        }
+    }
+
+    #[sqlx::test]
+    async fn taken_names_leave_the_surrounding_transaction_usable(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let mut db_tx = pool.begin().await.unwrap();
+
+        create_category_in(&mut db_tx, category(user.id, "Food", None))
+            .await
+            .unwrap();
+        let duplicate = create_category_in(&mut db_tx, category(user.id, " Food ", None)).await;
+        assert!(matches!(duplicate, Err(AppError::Validation(_))));
+        let blank = create_category_in(&mut db_tx, category(user.id, "  ", None)).await;
+        assert!(matches!(blank, Err(AppError::Validation(_))));
+        create_category_in(&mut db_tx, category(user.id, "Fuel", None))
+            .await
+            .unwrap();
+        db_tx.commit().await.unwrap();
+
+        let mut names: Vec<String> = CategoryRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Food", "Fuel"]);
+    }
+
+    #[sqlx::test]
+    async fn deleting_a_parent_rejects_clashing_sub_categories(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool);
+
+        let food = service
+            .create_category(category(user.id, "Food", None))
+            .await
+            .unwrap();
+        service
+            .create_category(category(user.id, "Other", Some(food.id)))
+            .await
+            .unwrap();
+        service
+            .create_category(category(user.id, "Other", None))
+            .await
+            .unwrap();
+
+        let result = service.delete_category(user.id, food.id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    async fn transaction(pool: &PgPool, user_id: Uuid, category_id: Uuid) -> Uuid {
+        TransactionRepository::create(
+            pool,
+            crate::models::CreateTransaction {
+                user_id,
+                category_id: Some(category_id),
+                amount: Decimal::new(-1000, 2),
+                store: None,
+                description: None,
+                source: "manual".to_string(),
+                email_message_id: None,
+                external_id: None,
+                transaction_date: NaiveDate::from_ymd_opt(2026, 3, 5).unwrap(),
+                value_date: None,
+                merchant_id: None,
+                account_id: None,
+                transfer_id: None,
+                currency: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn category_of(pool: &PgPool, transaction_id: Uuid) -> Option<Uuid> {
+        TransactionRepository::find_by_id(pool, transaction_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .category_id
+    }
+
+    #[sqlx::test]
+    async fn updates_rename_and_change_the_income_flag(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool);
+        let food = service
+            .create_category(category(user.id, "Food", None))
+            .await
+            .unwrap();
+        service
+            .create_category(category(user.id, "Bills", None))
+            .await
+            .unwrap();
+
+        let renamed = service
+            .update_category(
+                user.id,
+                food.id,
+                UpdateCategory {
+                    name: Some(" Groceries ".to_string()),
+                    icon: Some(Some("cart".to_string())),
+                    is_income: Some(true),
+                    parent_id: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "Groceries");
+        assert_eq!(renamed.icon.as_deref(), Some("cart"));
+        assert!(renamed.is_income);
+
+        let rename = |name: &str| UpdateCategory {
+            name: Some(name.to_string()),
+            icon: None,
+            is_income: None,
+            parent_id: None,
+        };
+        let taken = service
+            .update_category(user.id, food.id, rename("Bills"))
+            .await;
+        assert!(matches!(taken, Err(AppError::Validation(_))));
+        let blank = service.update_category(user.id, food.id, rename(" ")).await;
+        assert!(matches!(blank, Err(AppError::Validation(_))));
+    }
+
+    #[sqlx::test]
+    async fn merging_moves_transactions_and_deletes_the_source(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let source = service
+            .create_category(category(user.id, "Supermarket", None))
+            .await
+            .unwrap();
+        let target = service
+            .create_category(category(user.id, "Groceries", None))
+            .await
+            .unwrap();
+        let first = transaction(&pool, user.id, source.id).await;
+        let second = transaction(&pool, user.id, source.id).await;
+        let untouched = transaction(&pool, user.id, target.id).await;
+
+        let moved = service
+            .merge_categories(user.id, source.id, target.id)
+            .await
+            .unwrap();
+
+        assert_eq!(moved, 2);
+        for id in [first, second, untouched] {
+            assert_eq!(category_of(&pool, id).await, Some(target.id));
+        }
+        assert!(service.get_category(source.id).await.unwrap().is_none());
+
+        let into_itself = service
+            .merge_categories(user.id, target.id, target.id)
+            .await;
+        assert!(matches!(into_itself, Err(AppError::Validation(_))));
+    }
+
+    #[sqlx::test]
+    async fn deleting_leaves_transactions_uncategorized(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let food = service
+            .create_category(category(user.id, "Food", None))
+            .await
+            .unwrap();
+        let transaction_id = transaction(&pool, user.id, food.id).await;
+
+        service.delete_category(user.id, food.id).await.unwrap();
+
+        assert_eq!(category_of(&pool, transaction_id).await, None);
+        assert!(service.get_category(food.id).await.unwrap().is_none());
+    }
+
+    #[sqlx::test]
+    async fn other_users_categories_cannot_be_changed(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        let service = CategoryService::new(pool);
+        let own = service
+            .create_category(category(user.id, "Food", None))
+            .await
+            .unwrap();
+        let foreign = service
+            .create_category(category(other.id, "Food", None))
+            .await
+            .unwrap();
+        let rename = || UpdateCategory {
+            name: Some("Mine".to_string()),
+            icon: None,
+            is_income: None,
+            parent_id: None,
+        };
+
+        let updated = service.update_category(user.id, foreign.id, rename()).await;
+        assert!(matches!(updated, Err(AppError::Forbidden(_))));
+        let deleted = service.delete_category(user.id, foreign.id).await;
+        assert!(matches!(deleted, Err(AppError::Forbidden(_))));
+        let merged = service.merge_categories(user.id, foreign.id, own.id).await;
+        assert!(matches!(merged, Err(AppError::Forbidden(_))));
+        let merged = service.merge_categories(user.id, own.id, foreign.id).await;
+        assert!(matches!(merged, Err(AppError::Forbidden(_))));
+
+        let unknown = service
+            .update_category(user.id, Uuid::new_v4(), rename())
+            .await;
+        assert!(matches!(unknown, Err(AppError::NotFound(_))));
+        assert!(service.get_category(foreign.id).await.unwrap().is_some());
+    }
+
+    #[sqlx::test]
+    async fn merging_moves_rules_to_the_target(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let source = service
+            .create_category(category(user.id, "Supermarket", None))
+            .await
+            .unwrap();
+        let target = service
+            .create_category(category(user.id, "Groceries", None))
+            .await
+            .unwrap();
+        RuleRepository::create(
+            &pool,
+            crate::models::CreateCategorizationRule {
+                user_id: user.id,
+                category_id: source.id,
+                name: "AB".to_string(),
+                match_field: "store".to_string(),
+                match_type: "contains".to_string(),
+                pattern: Some("vasilopoulos".to_string()),
+                min_amount: None,
+                max_amount: None,
+                source: None,
+                priority: 0,
+                is_active: true,
+            },
+        )
+        .await
+        .unwrap();
+
+        service
+            .merge_categories(user.id, source.id, target.id)
+            .await
+            .unwrap();
+
+        let rules = RuleRepository::find_by_user(&pool, user.id).await.unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].category_id, target.id);
+    }
+
+    #[sqlx::test]
+    async fn merging_adds_learned_counts_to_the_target(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let source = service
+            .create_category(category(user.id, "Supermarket", None))
+            .await
+            .unwrap();
+        let target = service
+            .create_category(category(user.id, "Groceries", None))
+            .await
+            .unwrap();
+
+        let mut training = crate::categorize::learning::TrainingDelta::default();
+        training.learn(source.id, Some("Lidl"), None);
+        training.learn(target.id, Some("Lidl"), None);
+        training.learn(target.id, Some("Sklavenitis"), None);
+        let mut conn = pool.acquire().await.unwrap();
+        TrainingRepository::apply(&mut conn, user.id, &training)
+            .await
+            .unwrap();
+        drop(conn);
+
+        service
+            .merge_categories(user.id, source.id, target.id)
+            .await
+            .unwrap();
+
+        let counts: Vec<(Uuid, String, i32)> = sqlx::query_as(
+            "SELECT category_id, token, count FROM category_token_counts WHERE user_id = $1",
+        )
+        .bind(user.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert!(counts
+            .iter()
+            .all(|(category_id, _, _)| *category_id == target.id));
+        let lidl = counts.iter().find(|(_, token, _)| token == "lidl").unwrap();
+        assert_eq!(lidl.2, 2);
+
+        let documents: Vec<(Uuid, i32)> = sqlx::query_as(
+            "SELECT category_id, documents FROM category_training_totals WHERE user_id = $1",
+        )
+        .bind(user.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(documents, vec![(target.id, 3)]);
+    }
+
+    fn budget(user_id: Uuid, category_id: Uuid) -> crate::models::SetBudget {
+        crate::models::SetBudget {
+            user_id,
+            category_id,
+            amount: rust_decimal::Decimal::new(30000, 2),
+            rollover: false,
+            start_month: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            alert_thresholds: vec![80, 100],
+        }
+    }
+
+    #[sqlx::test]
+    async fn merging_moves_the_budget_and_its_alerts(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let source = service
+            .create_category(category(user.id, "Supermarket", None))
+            .await
+            .unwrap();
+        let target = service
+            .create_category(category(user.id, "Groceries", None))
+            .await
+            .unwrap();
+        let moved = BudgetRepository::upsert(&pool, budget(user.id, source.id))
+            .await
+            .unwrap();
+        let month = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        BudgetRepository::record_alert(&pool, moved.id, user.id, month, 80)
+            .await
+            .unwrap();
+
+        service
+            .merge_categories(user.id, source.id, target.id)
+            .await
+            .unwrap();
+
+        let budgets = BudgetRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap();
+        assert_eq!(budgets.len(), 1);
+        assert_eq!(
+            (budgets[0].id, budgets[0].category_id),
+            (moved.id, target.id)
+        );
+        // Already sent, so it does not fire again
+        let sent_again = BudgetRepository::record_alert(&pool, moved.id, user.id, month, 80)
+            .await
+            .unwrap();
+        assert!(!sent_again);
+    }
+
+    #[sqlx::test]
+    async fn merging_two_budgeted_categories_is_rejected(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let source = service
+            .create_category(category(user.id, "Supermarket", None))
+            .await
+            .unwrap();
+        let target = service
+            .create_category(category(user.id, "Groceries", None))
+            .await
+            .unwrap();
+        for category_id in [source.id, target.id] {
+            BudgetRepository::upsert(&pool, budget(user.id, category_id))
+                .await
+                .unwrap();
+        }
+
+        let result = service
+            .merge_categories(user.id, source.id, target.id)
+            .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        let budgets = BudgetRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap();
+        assert_eq!(budgets.len(), 2);
+    }
+
+    #[sqlx::test]
+    async fn merging_moves_recurring_transactions(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = CategoryService::new(pool.clone());
+        let source = service
+            .create_category(category(user.id, "Phone", None))
+            .await
+            .unwrap();
+        let target = service
+            .create_category(category(user.id, "Bills", None))
+            .await
+            .unwrap();
+        let start_date = NaiveDate::from_ymd_opt(2026, 3, 5).unwrap();
+        RecurringRepository::create(
+            &pool,
+            crate::models::CreateRecurringTransaction {
+                user_id: user.id,
+                category_id: Some(source.id),
+                amount: rust_decimal::Decimal::new(-2500, 2),
+                store: Some("COSMOTE".to_string()),
+                description: None,
+                frequency: "monthly".to_string(),
+                day_of_month: Some(5),
+                start_date,
+                end_date: None,
+                account_id: None,
+                currency: None,
+                reminder_only: false,
+            },
+            start_date,
+        )
+        .await
+        .unwrap();
+
+        service
+            .merge_categories(user.id, source.id, target.id)
+            .await
+            .unwrap();
+
+        let recurring = RecurringRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap();
+        assert_eq!(recurring[0].category_id, Some(target.id));
+    }
+}
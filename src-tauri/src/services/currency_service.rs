@@ -0,0 +1,218 @@
+use crate::db::repository::{FxRateRepository, UserRepository};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::fx::{self, RateProvider};
+use crate::models::{FxRate, RateImportSummary, User};
+use uuid::Uuid;
+
+/// Rates written per statement (the full ECB history has ~200k)
+const IMPORT_CHUNK_SIZE: usize = 5000;
+
+pub struct CurrencyService {
+    pool: DbPool,
+}
+
+impl CurrencyService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Change the currency a user's totals are kept in
+    ///
+    /// All of the user's transactions are converted again; budgets keep
+    /// their amounts. Fails when a transaction's currency has no rate to the
+    /// new base currency.
+    pub async fn set_base_currency(&self, user_id: Uuid, currency: &str) -> Result<User> {
+        let currency = fx::normalize_code(currency)?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let user = UserRepository::set_base_currency(&mut *db_tx, user_id, &currency).await?;
+
+        // Booking them 1:1 would silently change amounts, budgets and summaries
+        let missing = FxRateRepository::find_unconvertible_currencies(&mut *db_tx, user_id).await?;
+        if !missing.is_empty() {
+            return Err(AppError::Validation(format!(
+                "No exchange rate from {} to {}, import exchange rates first",
+                missing.join(", "),
+                currency
+            )));
+        }
+
+        let converted =
+            FxRateRepository::reconvert_transactions(&mut *db_tx, Some(user_id)).await?;
+        db_tx.commit().await?;
+
+        tracing::info!(
+            "Base currency set to {}, {} transactions converted",
+            currency,
+            converted
+        );
+        Ok(user)
+    }
+
+    /// Store the rates of a provider and convert transactions that were
+    /// saved without a rate, or with a rate from another day
+    pub async fn import_rates(&self, provider: Box<dyn RateProvider>) -> Result<RateImportSummary> {
+        let source = provider.name().to_string();
+        // Providers do blocking file or network IO
+        let rates = tokio::task::spawn_blocking(move || provider.fetch())
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Rate provider failed: {}", e)))??;
+
+        let mut db_tx = self.pool.begin().await?;
+        for chunk in rates.chunks(IMPORT_CHUNK_SIZE) {
+            FxRateRepository::upsert_many(&mut *db_tx, chunk).await?;
+        }
+        let transactions_converted =
+            FxRateRepository::reconvert_transactions(&mut *db_tx, None).await?;
+        db_tx.commit().await?;
+
+        tracing::info!(
+            "Imported {} exchange rates from {}, {} transactions converted",
+            rates.len(),
+            source,
+            transactions_converted
+        );
+
+        Ok(RateImportSummary {
+            source,
+            rates: rates.len(),
+            first_date: rates.iter().map(|r| r.rate_date).min(),
+            last_date: rates.iter().map(|r| r.rate_date).max(),
+            transactions_converted,
+        })
+    }
+
+    /// Latest stored rate of every currency pair
+    pub async fn get_latest_rates(&self) -> Result<Vec<FxRate>> {
+        FxRateRepository::find_latest(&self.pool).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repository::TransactionRepository;
+    use crate::models::{CreateTransaction, TransactionFilter};
+    use crate::services::TransactionService;
+    use crate::test_support::create_user;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn rate(quote: &str, day: &str, value: i64) -> FxRate {
+        FxRate {
+            base: "EUR".to_string(),
+            quote: quote.to_string(),
+            rate_date: date(day),
+            rate: Decimal::new(value, 4),
+            source: "test".to_string(),
+        }
+    }
+
+    async fn find(pool: &PgPool, from: &str, to: &str, day: &str) -> Option<Decimal> {
+        FxRateRepository::find_rate(pool, from, to, date(day))
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn rates_are_inverted_and_crossed_through_the_euro(pool: PgPool) {
+        FxRateRepository::upsert_many(
+            &pool,
+            &[
+                rate("USD", "2026-03-02", 12500),
+                rate("GBP", "2026-03-02", 8000),
+                rate("USD", "2026-03-05", 10000),
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            find(&pool, "EUR", "USD", "2026-03-03").await,
+            Some(Decimal::new(125, 2))
+        );
+        assert_eq!(
+            find(&pool, "USD", "EUR", "2026-03-03").await,
+            Some(Decimal::new(8, 1))
+        );
+        assert_eq!(
+            find(&pool, "USD", "GBP", "2026-03-03").await,
+            Some(Decimal::new(64, 2))
+        );
+        // Latest rate on or before the date, else the nearest later one
+        assert_eq!(
+            find(&pool, "EUR", "USD", "2026-03-06").await,
+            Some(Decimal::ONE)
+        );
+        assert_eq!(
+            find(&pool, "EUR", "USD", "2026-01-01").await,
+            Some(Decimal::new(125, 2))
+        );
+        assert_eq!(find(&pool, "EUR", "JPY", "2026-03-03").await, None);
+        assert_eq!(
+            find(&pool, "JPY", "JPY", "2026-03-03").await,
+            Some(Decimal::ONE)
+        );
+    }
+
+    async fn amounts(pool: &PgPool, user_id: Uuid) -> Vec<Decimal> {
+        TransactionRepository::find_by_user(pool, user_id, &TransactionFilter::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tx| tx.amount)
+            .collect()
+    }
+
+    #[sqlx::test]
+    async fn base_currency_changes_need_rates_for_every_transaction(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        FxRateRepository::upsert_many(&pool, &[rate("USD", "2026-03-02", 12500)])
+            .await
+            .unwrap();
+        TransactionService::new(pool.clone())
+            .create_transaction_from_dto(CreateTransaction {
+                user_id: user.id,
+                category_id: None,
+                amount: Decimal::new(-3000, 2),
+                store: Some("AMAZON".to_string()),
+                description: None,
+                source: "manual".to_string(),
+                email_message_id: None,
+                external_id: None,
+                transaction_date: date("2026-03-03"),
+                value_date: None,
+                merchant_id: None,
+                account_id: None,
+                transfer_id: None,
+                currency: Some("USD".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(amounts(&pool, user.id).await, vec![Decimal::new(-2400, 2)]);
+        let service = CurrencyService::new(pool.clone());
+
+        // No rate connects USD and GBP, nothing is converted at 1:1
+        let result = service.set_base_currency(user.id, "GBP").await;
+        match result {
+            Err(AppError::Validation(message)) => assert!(message.contains("USD"), "{}", message),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+        let unchanged = UserRepository::find_by_id(&pool, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.base_currency, "EUR");
+        assert_eq!(amounts(&pool, user.id).await, vec![Decimal::new(-2400, 2)]);
+
+        let user = service.set_base_currency(user.id, "usd").await.unwrap();
+        assert_eq!(user.base_currency, "USD");
+        assert_eq!(amounts(&pool, user.id).await, vec![Decimal::new(-3000, 2)]);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::db::repository::{
+    AccountRepository, CategoryRepository, TransactionRepository, TransactionSplitRepository,
+};
+use crate::db::DbPool;
+use crate::error::Result;
+use crate::export::{self, journal::JournalFormat, ExportFormat, LocaleFormat};
+use crate::models::{TransactionFilter, TransactionSplit, User};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+pub struct ExportService {
+    pool: DbPool,
+}
+
+impl ExportService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Write the user's transactions as QIF, returns the number exported
+    pub async fn export_qif(
+        &self,
+        user_id: Uuid,
+        path: &Path,
+        filter: &TransactionFilter,
+    ) -> Result<usize> {
+        let transactions =
+            TransactionRepository::find_for_export(&self.pool, user_id, filter).await?;
+        let categories = CategoryRepository::find_by_user(&self.pool, user_id).await?;
+        let splits = self.splits_by_transaction(user_id).await?;
+
+        let content = export::qif::write(&transactions, &categories, &splits);
+        tokio::fs::write(path, content).await?;
+
+        tracing::info!("Exported {} transactions to QIF", transactions.len());
+        Ok(transactions.len())
+    }
+
+    /// Write the user's transactions as CSV or XLSX, returns the number exported
+    ///
+    /// `locale` only affects CSV output; XLSX cells are typed and formatted
+    /// by the spreadsheet app
+    pub async fn export_transactions(
+        &self,
+        user_id: Uuid,
+        path: &Path,
+        filter: &TransactionFilter,
+        format: ExportFormat,
+        locale: Option<&str>,
+    ) -> Result<usize> {
+        let transactions =
+            TransactionRepository::find_for_export(&self.pool, user_id, filter).await?;
+
+        let content = match format {
+            ExportFormat::Csv => {
+                export::csv::write(&transactions, &LocaleFormat::for_locale(locale))?
+            }
+            ExportFormat::Xlsx => export::xlsx::write(&transactions)?,
+        };
+        tokio::fs::write(path, content).await?;
+
+        tracing::info!(
+            "Exported {} transactions to {:?}",
+            transactions.len(),
+            format
+        );
+        Ok(transactions.len())
+    }
+
+    /// Write the user's transactions as a Beancount or Ledger journal
+    pub async fn export_journal(
+        &self,
+        user: &User,
+        path: &Path,
+        filter: &TransactionFilter,
+        format: JournalFormat,
+    ) -> Result<usize> {
+        let transactions =
+            TransactionRepository::find_for_export(&self.pool, user.id, filter).await?;
+        let categories = CategoryRepository::find_by_user(&self.pool, user.id).await?;
+        let accounts = AccountRepository::find_by_user(&self.pool, user.id).await?;
+        let splits = self.splits_by_transaction(user.id).await?;
+
+        let content =
+            export::journal::write(format, user, &transactions, &categories, &accounts, &splits);
+        tokio::fs::write(path, content).await?;
+
+        tracing::info!(
+            "Exported {} transactions to {:?} journal",
+            transactions.len(),
+            format
+        );
+        Ok(transactions.len())
+    }
+
+    /// The user's split lines grouped by transaction, in line order
+    async fn splits_by_transaction(
+        &self,
+        user_id: Uuid,
+    ) -> Result<HashMap<Uuid, Vec<TransactionSplit>>> {
+        let mut splits: HashMap<Uuid, Vec<TransactionSplit>> = HashMap::new();
+        for split in TransactionSplitRepository::find_by_user(&self.pool, user_id).await? {
+            splits.entry(split.transaction_id).or_default().push(split);
+        }
+
+        Ok(splits)
+    }
+}
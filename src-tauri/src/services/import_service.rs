@@ -0,0 +1,603 @@
+use crate::categorize::learning::TrainingDelta;
+use crate::categorize::RuleEngine;
+use crate::db::repository::{
+    AccountRepository, CategoryRepository, CsvProfileRepository, TrainingRepository,
+    TransactionRepository, TransactionSplitRepository,
+};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::import::{self, ImportPreview, StatementFormat, StatementRow};
+use crate::models::{CreateCategory, CreateCsvImportProfile, CsvImportProfile, SplitLine};
+use crate::services::account_service::ensure_account_owned;
+use crate::services::category_service::create_category_in;
+use crate::services::merchant_service::link_merchant;
+use crate::services::transaction_service::ensure_booking_rate;
+use chrono::{Datelike, NaiveDate};
+use encoding_rs::Encoding;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Result of committing a statement import
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementImportResult {
+    pub imported: usize,
+    /// Rows already imported before (matched by bank supplied or derived id)
+    pub skipped: usize,
+    /// Rows that could not be parsed (not imported)
+    pub errors: Vec<import::RowError>,
+    /// First day of every month that received transactions
+    pub months: Vec<NaiveDate>,
+}
+
+pub struct ImportService {
+    pool: DbPool,
+}
+
+impl ImportService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn save_csv_profile(
+        &self,
+        profile: CreateCsvImportProfile,
+    ) -> Result<CsvImportProfile> {
+        if profile.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Profile name cannot be empty".to_string(),
+            ));
+        }
+
+        if profile.date_column.trim().is_empty() {
+            return Err(AppError::Validation("Date column is required".to_string()));
+        }
+
+        let is_set =
+            |column: &Option<String>| column.as_deref().is_some_and(|c| !c.trim().is_empty());
+        let has_amount = is_set(&profile.amount_column);
+        let has_debit_credit = is_set(&profile.debit_column) && is_set(&profile.credit_column);
+        if !has_amount && !has_debit_credit {
+            return Err(AppError::Validation(
+                "Either an amount column or both debit and credit columns are required".to_string(),
+            ));
+        }
+
+        if profile.delimiter.len() != 1 {
+            return Err(AppError::Validation(
+                "Delimiter must be a single character".to_string(),
+            ));
+        }
+
+        if profile.decimal_separator != "," && profile.decimal_separator != "." {
+            return Err(AppError::Validation(
+                "Decimal separator must be ',' or '.'".to_string(),
+            ));
+        }
+
+        if Encoding::for_label(profile.encoding.trim().as_bytes()).is_none() {
+            return Err(AppError::Validation(format!(
+                "Unknown encoding '{}'",
+                profile.encoding
+            )));
+        }
+
+        CsvProfileRepository::upsert(&self.pool, profile).await
+    }
+
+    pub async fn get_csv_profiles(&self, user_id: Uuid) -> Result<Vec<CsvImportProfile>> {
+        CsvProfileRepository::find_by_user(&self.pool, user_id).await
+    }
+
+    pub async fn delete_csv_profile(&self, id: Uuid, user_id: Uuid) -> Result<()> {
+        CsvProfileRepository::delete(&self.pool, id, user_id).await
+    }
+
+    /// Parse a CSV file without saving anything
+    pub async fn preview_csv(
+        &self,
+        user_id: Uuid,
+        path: &Path,
+        profile_id: Uuid,
+    ) -> Result<ImportPreview> {
+        self.read_csv(user_id, path, profile_id, None).await
+    }
+
+    /// Parse a CSV file and save all valid rows
+    ///
+    /// Rows are booked to `account_id`, or the default account
+    pub async fn import_csv(
+        &self,
+        user_id: Uuid,
+        path: &Path,
+        profile_id: Uuid,
+        account_id: Option<Uuid>,
+    ) -> Result<StatementImportResult> {
+        // Derived ids are per account, so the default account is named
+        let account_id = match account_id {
+            Some(id) => id,
+            None => {
+                AccountRepository::find_default(&self.pool, user_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Default account".to_string()))?
+                    .id
+            }
+        };
+
+        let preview = self
+            .read_csv(user_id, path, profile_id, Some(account_id))
+            .await?;
+        self.commit_rows(user_id, preview, "csv", Some(account_id))
+            .await
+    }
+
+    async fn read_csv(
+        &self,
+        user_id: Uuid,
+        path: &Path,
+        profile_id: Uuid,
+        account_id: Option<Uuid>,
+    ) -> Result<ImportPreview> {
+        let profile = CsvProfileRepository::find_by_id(&self.pool, profile_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("CSV import profile not found".to_string()))?;
+
+        let content = tokio::fs::read(path).await?;
+        import::csv::parse(&content, &profile, account_id)
+    }
+
+    /// Parse a statement file without saving anything
+    pub async fn preview_statement(
+        &self,
+        path: &Path,
+        format: StatementFormat,
+    ) -> Result<ImportPreview> {
+        let content = tokio::fs::read(path).await?;
+        format.parse(&content)
+    }
+
+    /// Parse a statement file and save all rows not imported before
+    ///
+    /// Rows are booked to `account_id`, or the default account
+    pub async fn import_statement(
+        &self,
+        user_id: Uuid,
+        path: &Path,
+        format: StatementFormat,
+        account_id: Option<Uuid>,
+    ) -> Result<StatementImportResult> {
+        let preview = self.preview_statement(path, format).await?;
+        self.commit_rows(user_id, preview, format.source(), account_id)
+            .await
+    }
+
+    /// Save rows in a single database transaction (all or nothing)
+    ///
+    /// Rows with an `external_id` already stored for this source are skipped
+    async fn commit_rows(
+        &self,
+        user_id: Uuid,
+        preview: ImportPreview,
+        source: &str,
+        account_id: Option<Uuid>,
+    ) -> Result<StatementImportResult> {
+        if let Some(account_id) = account_id {
+            ensure_account_owned(&self.pool, user_id, account_id).await?;
+        }
+
+        let rules = RuleEngine::load(&self.pool, user_id).await?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let categories = resolve_categories(&mut db_tx, user_id, &preview.rows).await?;
+        let mut imported = 0;
+        let mut skipped = 0;
+        let mut seen = HashSet::new();
+        let mut months = BTreeSet::new();
+        let mut training = TrainingDelta::default();
+
+        for mut row in preview.rows {
+            if row.amount.is_zero() {
+                continue;
+            }
+
+            if let Some(external_id) = row.external_id.clone() {
+                let exists = TransactionRepository::find_by_external_id(
+                    &mut *db_tx,
+                    user_id,
+                    source,
+                    &external_id,
+                )
+                .await?
+                .is_some();
+
+                if exists || !seen.insert(external_id) {
+                    skipped += 1;
+                    continue;
+                }
+            }
+
+            let category_id = row
+                .category
+                .as_ref()
+                .and_then(|name| categories.get(&name.to_lowercase()).copied());
+
+            let splits = std::mem::take(&mut row.splits);
+            let mut tx = row.into_create_transaction(user_id, category_id, account_id, source);
+            let merchant = link_merchant(&mut db_tx, &mut tx).await?;
+            // Split rows are categorized by their lines
+            if splits.is_empty() {
+                rules.apply(&mut tx, merchant.as_ref().map(|m| m.name.as_str()));
+            }
+
+            if let Some(category_id) = tx.category_id {
+                training.learn(category_id, tx.store.as_deref(), tx.description.as_deref());
+            }
+
+            ensure_booking_rate(
+                &mut *db_tx,
+                user_id,
+                tx.account_id,
+                tx.currency.as_deref(),
+                tx.transaction_date,
+            )
+            .await?;
+            let created = TransactionRepository::create(&mut *db_tx, tx).await?;
+            for (position, split) in splits.into_iter().enumerate() {
+                let line = SplitLine {
+                    category_id: split
+                        .category
+                        .and_then(|name| categories.get(&name.to_lowercase()).copied()),
+                    amount: split.amount,
+                    note: split.note,
+                };
+                TransactionSplitRepository::create(&mut *db_tx, created.id, position as i16, &line)
+                    .await?;
+            }
+            months.insert(
+                created
+                    .transaction_date
+                    .with_day(1)
+                    .unwrap_or(created.transaction_date),
+            );
+            imported += 1;
+        }
+
+        TrainingRepository::apply(&mut db_tx, user_id, &training).await?;
+        db_tx.commit().await?;
+
+        tracing::info!(
+            "Imported {} {} statement rows ({} already imported)",
+            imported,
+            source,
+            skipped
+        );
+
+        Ok(StatementImportResult {
+            imported,
+            skipped,
+            errors: preview.errors,
+            months: months.into_iter().collect(),
+        })
+    }
+}
+
+/// Map category paths used by the rows to ids, creating unknown ones
+///
+/// Paths are `Parent:Child` (QIF style); each level is looked up under its
+/// parent. New top-level categories are income categories when their first
+/// row is a credit, sub-categories follow their parent. Categories are
+/// created in the import transaction so a failed import leaves none behind,
+/// with the same validation as `CategoryService::create_category`.
+async fn resolve_categories(
+    db_tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    rows: &[StatementRow],
+) -> Result<HashMap<String, Uuid>> {
+    let mut by_parent: HashMap<(Option<Uuid>, String), (Uuid, bool)> =
+        CategoryRepository::find_by_user(&mut **db_tx, user_id)
+            .await?
+            .into_iter()
+            .map(|c| ((c.parent_id, category_key(&c.name)), (c.id, c.is_income)))
+            .collect();
+    let mut by_path = HashMap::new();
+
+    // Split lines bring their own categories (and amounts deciding whether
+    // new ones are income)
+    let mut paths: Vec<(&String, Decimal)> = Vec::new();
+    for row in rows {
+        paths.extend(row.category.as_ref().map(|path| (path, row.amount)));
+        paths.extend(
+            row.splits
+                .iter()
+                .filter_map(|split| split.category.as_ref().map(|path| (path, split.amount))),
+        );
+    }
+
+    for (path, amount) in paths {
+        let key = path.to_lowercase();
+        if by_path.contains_key(&key) {
+            continue;
+        }
+
+        let mut parent: Option<(Uuid, bool)> = None;
+        for name in path.split(':').map(str::trim).filter(|n| !n.is_empty()) {
+            let level = (parent.map(|(id, _)| id), category_key(name));
+            let category = match by_parent.get(&level) {
+                Some(existing) => *existing,
+                None => {
+                    let is_income = match parent {
+                        Some((_, is_income)) => is_income,
+                        None => amount > Decimal::ZERO,
+                    };
+                    let created = create_category_in(
+                        db_tx,
+                        CreateCategory {
+                            user_id,
+                            name: name.to_string(),
+                            icon: None,
+                            is_income,
+                            parent_id: level.0,
+                        },
+                    )
+                    .await;
+                    let category = match created {
+                        Ok(category) => category,
+                        // Created elsewhere since the categories were read
+                        Err(e) => CategoryRepository::find_by_user(&mut **db_tx, user_id)
+                            .await?
+                            .into_iter()
+                            .find(|c| c.parent_id == level.0 && category_key(&c.name) == level.1)
+                            .ok_or(e)?,
+                    };
+
+                    tracing::info!("Created category '{}' from import", category.name);
+                    by_parent.insert(level, (category.id, category.is_income));
+                    (category.id, category.is_income)
+                }
+            };
+            parent = Some(category);
+        }
+
+        if let Some((id, _)) = parent {
+            by_path.insert(key, id);
+        }
+    }
+
+    Ok(by_path)
+}
+
+/// Category names compared the way users see them
+fn category_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TransactionFilter;
+    use crate::services::CategoryService;
+    use crate::test_support::create_user;
+    use sqlx::PgPool;
+
+    fn csv_profile(user_id: Uuid, amount_column: Option<&str>) -> CreateCsvImportProfile {
+        CreateCsvImportProfile {
+            user_id,
+            name: "Bank".to_string(),
+            delimiter: ";".to_string(),
+            has_header: true,
+            date_column: "Date".to_string(),
+            amount_column: amount_column.map(str::to_string),
+            debit_column: None,
+            credit_column: None,
+            payee_column: Some("Payee".to_string()),
+            description_column: None,
+            date_format: "%d/%m/%Y".to_string(),
+            decimal_separator: ",".to_string(),
+            encoding: "utf-8".to_string(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn rejects_blank_amount_column(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = ImportService::new(pool);
+
+        let result = service
+            .save_csv_profile(csv_profile(user.id, Some(" ")))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[sqlx::test]
+    async fn reimporting_a_csv_skips_rows_seen_before(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = ImportService::new(pool);
+        let profile = service
+            .save_csv_profile(csv_profile(user.id, Some("Amount")))
+            .await
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("{}.csv", user.id));
+        std::fs::write(
+            &path,
+            "Date;Payee;Amount\n01/03/2026;COFFEE ISLAND;-2,50\n01/03/2026;COFFEE ISLAND;-2,50\n",
+        )
+        .unwrap();
+        let first = service
+            .import_csv(user.id, &path, profile.id, None)
+            .await
+            .unwrap();
+
+        // A later export overlapping the first one
+        std::fs::write(
+            &path,
+            "Date;Payee;Amount\n01/03/2026;COFFEE ISLAND;-2,50\n01/03/2026;COFFEE ISLAND;-2,50\n\
+             02/03/2026;BAKERY;-1,20\n",
+        )
+        .unwrap();
+        // ...read with a recreated profile, naming the default account
+        service
+            .delete_csv_profile(profile.id, user.id)
+            .await
+            .unwrap();
+        let profile = service
+            .save_csv_profile(csv_profile(user.id, Some("Amount")))
+            .await
+            .unwrap();
+        let account = AccountRepository::find_default(&service.pool, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let second = service
+            .import_csv(user.id, &path, profile.id, Some(account.id))
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((first.imported, first.skipped), (2, 0));
+        assert_eq!((second.imported, second.skipped), (1, 2));
+        assert_eq!(
+            second.months,
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[sqlx::test]
+    async fn qif_categories_are_created_as_a_hierarchy(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = ImportService::new(pool.clone());
+
+        let path = std::env::temp_dir().join(format!("{}.qif", user.id));
+        std::fs::write(
+            &path,
+            "!Type:Bank\nD03/01/2026\nT-45.20\nPSUPERMARKET\nLFood:Groceries\n^\n\
+             D03/02/2026\nT-12.00\nPTAVERNA\nLFood:Restaurants\n^\n",
+        )
+        .unwrap();
+        let first = service
+            .import_statement(user.id, &path, StatementFormat::Qif, None)
+            .await
+            .unwrap();
+        let second = service
+            .import_statement(user.id, &path, StatementFormat::Qif, None)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((first.imported, first.skipped), (2, 0));
+        assert_eq!((second.imported, second.skipped), (0, 2));
+
+        let categories = CategoryRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap();
+        let food = categories.iter().find(|c| c.name == "Food").unwrap();
+        let children: Vec<&str> = categories
+            .iter()
+            .filter(|c| c.parent_id == Some(food.id))
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(children, vec!["Groceries", "Restaurants"]);
+    }
+
+    #[sqlx::test]
+    async fn qif_categories_reuse_existing_names_ignoring_case(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let groceries = CategoryService::new(pool.clone())
+            .create_category(CreateCategory {
+                user_id: user.id,
+                name: "Groceries".to_string(),
+                icon: None,
+                is_income: false,
+                parent_id: None,
+            })
+            .await
+            .unwrap();
+        let service = ImportService::new(pool.clone());
+
+        let path = std::env::temp_dir().join(format!("{}.qif", user.id));
+        std::fs::write(
+            &path,
+            "!Type:Bank\nD03/01/2026\nT-45.20\nPSUPERMARKET\nL groceries \n^\n",
+        )
+        .unwrap();
+        let result = service
+            .import_statement(user.id, &path, StatementFormat::Qif, None)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.imported, 1);
+        let categories = CategoryRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap();
+        assert_eq!(categories.len(), 1);
+        let transactions =
+            TransactionRepository::find_by_user(&pool, user.id, &TransactionFilter::default())
+                .await
+                .unwrap();
+        assert_eq!(transactions[0].category_id, Some(groceries.id));
+    }
+
+    #[sqlx::test]
+    async fn qif_splits_are_imported_as_split_lines(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = ImportService::new(pool.clone());
+
+        let path = std::env::temp_dir().join(format!("{}.qif", user.id));
+        std::fs::write(
+            &path,
+            "!Type:Bank\nD03/05/2026\nT-60.00\nPHYPERMARKET\n\
+             SFood:Groceries\n$-45.00\nSHousehold\nEDetergent\n$-15.00\n^\n",
+        )
+        .unwrap();
+        let result = service
+            .import_statement(user.id, &path, StatementFormat::Qif, None)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.imported, 1);
+        let transactions =
+            TransactionRepository::find_by_user(&pool, user.id, &TransactionFilter::default())
+                .await
+                .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].category_id, None);
+
+        let categories: HashMap<Uuid, String> = CategoryRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.id, c.name))
+            .collect();
+        let lines: Vec<(Decimal, Option<&str>, Option<String>)> =
+            TransactionSplitRepository::find_by_transaction(&pool, transactions[0].id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|s| {
+                    (
+                        s.amount,
+                        s.category_id.map(|id| categories[&id].as_str()),
+                        s.note,
+                    )
+                })
+                .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (Decimal::new(-4500, 2), Some("Groceries"), None),
+                (
+                    Decimal::new(-1500, 2),
+                    Some("Household"),
+                    Some("Detergent".to_string())
+                ),
+            ]
+        );
+    }
+}
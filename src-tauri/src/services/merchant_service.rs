@@ -0,0 +1,199 @@
+use crate::categorize::text;
+use crate::db::repository::{MerchantRepository, TransactionRepository};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::models::{CreateTransaction, Merchant, MerchantSpending, MerchantWithAliases};
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct MerchantService {
+    pool: DbPool,
+}
+
+impl MerchantService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user's merchants with their aliases, by name
+    pub async fn get_merchants(&self, user_id: Uuid) -> Result<Vec<MerchantWithAliases>> {
+        let merchants = MerchantRepository::find_by_user(&self.pool, user_id).await?;
+
+        let mut aliases: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for alias in MerchantRepository::find_aliases_by_user(&self.pool, user_id).await? {
+            aliases
+                .entry(alias.merchant_id)
+                .or_default()
+                .push(alias.alias);
+        }
+
+        Ok(merchants
+            .into_iter()
+            .map(|merchant| MerchantWithAliases {
+                aliases: aliases.remove(&merchant.id).unwrap_or_default(),
+                merchant,
+            })
+            .collect())
+    }
+
+    /// Change the display name; the matching key stays the same
+    pub async fn rename_merchant(&self, user_id: Uuid, id: Uuid, name: &str) -> Result<Merchant> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation(
+                "Merchant name cannot be empty".to_string(),
+            ));
+        }
+
+        self.ensure_owned(user_id, id).await?;
+        MerchantRepository::rename(&self.pool, id, user_id, name)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Merchant {}", id)))
+    }
+
+    /// Link another spelling of a store name to the merchant
+    ///
+    /// Unlinked transactions with a matching store are linked right away.
+    pub async fn add_alias(&self, user_id: Uuid, merchant_id: Uuid, alias: &str) -> Result<u64> {
+        let merchant = self.ensure_owned(user_id, merchant_id).await?;
+
+        let alias = alias.trim();
+        let key = text::merchant_key(alias);
+        if key.is_empty() {
+            return Err(AppError::Validation(format!(
+                "'{}' is not a usable merchant alias",
+                alias
+            )));
+        }
+        if key == merchant.normalized_name {
+            return Ok(0);
+        }
+
+        if let Some(existing) = MerchantRepository::find_by_key(&self.pool, user_id, &key).await? {
+            if existing.normalized_name == key {
+                return Err(AppError::Validation(format!(
+                    "'{}' is already the merchant {}, merge the merchants instead",
+                    alias, existing.name
+                )));
+            }
+        }
+
+        MerchantRepository::add_alias(&self.pool, user_id, merchant_id, alias, &key).await?;
+        self.relink_transactions(user_id).await
+    }
+
+    /// Move the source's transactions and aliases to the target and delete it
+    ///
+    /// The source name is kept as an alias of the target so future
+    /// transactions from that store are linked to the target.
+    pub async fn merge_merchants(
+        &self,
+        user_id: Uuid,
+        source_id: Uuid,
+        target_id: Uuid,
+    ) -> Result<u64> {
+        if source_id == target_id {
+            return Err(AppError::Validation(
+                "Cannot merge a merchant into itself".to_string(),
+            ));
+        }
+
+        let source = self.ensure_owned(user_id, source_id).await?;
+        self.ensure_owned(user_id, target_id).await?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let moved =
+            TransactionRepository::reassign_merchant(&mut *db_tx, user_id, source_id, target_id)
+                .await?;
+        MerchantRepository::move_aliases(&mut *db_tx, user_id, source_id, target_id).await?;
+        MerchantRepository::delete(&mut *db_tx, source_id, user_id).await?;
+        MerchantRepository::add_alias(
+            &mut *db_tx,
+            user_id,
+            target_id,
+            &source.name,
+            &source.normalized_name,
+        )
+        .await?;
+        db_tx.commit().await?;
+
+        tracing::info!(
+            "Merged merchant {} into {} ({} transactions)",
+            source_id,
+            target_id,
+            moved
+        );
+        Ok(moved)
+    }
+
+    /// Totals per merchant for a date range
+    pub async fn get_merchant_spending(
+        &self,
+        user_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MerchantSpending>> {
+        TransactionRepository::get_merchant_spending(&self.pool, user_id, start_date, end_date)
+            .await
+    }
+
+    /// Link transactions that have a store but no merchant yet
+    ///
+    /// Returns how many transactions were linked
+    pub async fn relink_transactions(&self, user_id: Uuid) -> Result<u64> {
+        let transactions =
+            TransactionRepository::find_without_merchant(&self.pool, user_id).await?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let mut linked = 0;
+        for tx in transactions {
+            let Some(store) = tx.store.as_deref() else {
+                continue;
+            };
+            if let Some(merchant) =
+                MerchantRepository::find_or_create(&mut db_tx, user_id, store).await?
+            {
+                TransactionRepository::set_merchant(&mut *db_tx, tx.id, user_id, Some(merchant.id))
+                    .await?;
+                linked += 1;
+            }
+        }
+        db_tx.commit().await?;
+
+        tracing::info!("Linked {} transactions to merchants", linked);
+        Ok(linked)
+    }
+
+    /// Distinguish a missing merchant from one owned by another user
+    async fn ensure_owned(&self, user_id: Uuid, id: Uuid) -> Result<Merchant> {
+        match MerchantRepository::find_by_id(&self.pool, id).await? {
+            Some(merchant) if merchant.user_id == user_id => Ok(merchant),
+            Some(_) => Err(AppError::Forbidden(format!(
+                "Merchant {} belongs to another user",
+                id
+            ))),
+            None => Err(AppError::NotFound(format!("Merchant {}", id))),
+        }
+    }
+}
+
+/// Set `merchant_id` from the store name, creating the merchant if needed
+///
+/// Returns the linked merchant so callers can match rules on its name
+pub(crate) async fn link_merchant(
+    conn: &mut PgConnection,
+    tx: &mut CreateTransaction,
+) -> Result<Option<Merchant>> {
+    if tx.merchant_id.is_some() {
+        return Ok(None);
+    }
+    let Some(store) = tx.store.as_deref() else {
+        return Ok(None);
+    };
+
+    let merchant = MerchantRepository::find_or_create(conn, tx.user_id, store).await?;
+    tx.merchant_id = merchant.as_ref().map(|m| m.id);
+    Ok(merchant)
+}
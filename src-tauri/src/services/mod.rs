@@ -1,7 +1,29 @@
 mod user_service;
 mod category_service;
 mod transaction_service;
+mod import_service;
+mod export_service;
+mod backup_service;
+mod rule_service;
+mod suggestion_service;
+mod merchant_service;
+mod budget_service;
+mod recurring_service;
+mod subscription_service;
+mod account_service;
+mod currency_service;
 
 pub use user_service::UserService;
 pub use category_service::CategoryService;
 pub use transaction_service::TransactionService;
+pub use import_service::{ImportService, StatementImportResult};
+pub use export_service::ExportService;
+pub use backup_service::{BackupService, BackupSummary, RestoreSummary};
+pub use rule_service::RuleService;
+pub use suggestion_service::SuggestionService;
+pub use merchant_service::MerchantService;
+pub use budget_service::BudgetService;
+pub use recurring_service::{RecurringService, RECURRING_SOURCE};
+pub use subscription_service::SubscriptionService;
+pub use account_service::AccountService;
+pub use currency_service::CurrencyService;
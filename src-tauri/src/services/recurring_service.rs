@@ -0,0 +1,436 @@
+use crate::db::repository::{CategoryRepository, RecurringRepository, TransactionRepository};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::fx;
+use crate::models::{
+    CreateRecurringTransaction, CreateTransaction, RecurringTransaction, RecurringWithUpcoming,
+    Transaction,
+};
+use crate::recurring::{Frequency, Schedule};
+use crate::services::account_service::ensure_account_owned;
+use crate::services::TransactionService;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Value stored in `transactions.source` for posted occurrences
+pub const RECURRING_SOURCE: &str = "recurring";
+
+/// Occurrences listed with each recurring transaction
+const UPCOMING_COUNT: usize = 3;
+
+pub struct RecurringService {
+    pool: DbPool,
+}
+
+impl RecurringService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user's recurring transactions with their next occurrences
+    pub async fn get_recurring(&self, user_id: Uuid) -> Result<Vec<RecurringWithUpcoming>> {
+        RecurringRepository::find_by_user(&self.pool, user_id)
+            .await?
+            .into_iter()
+            .map(with_upcoming)
+            .collect()
+    }
+
+    pub async fn create_recurring(
+        &self,
+        recurring: CreateRecurringTransaction,
+    ) -> Result<RecurringWithUpcoming> {
+        let (recurring, next_date) = self.prepare(recurring).await?;
+
+        with_upcoming(RecurringRepository::create(&self.pool, recurring, next_date).await?)
+    }
+
+    /// Validate a new entry and find its first occurrence, for callers that
+    /// save it in their own database transaction
+    pub(crate) async fn prepare(
+        &self,
+        recurring: CreateRecurringTransaction,
+    ) -> Result<(CreateRecurringTransaction, NaiveDate)> {
+        let (recurring, schedule) = self.validate(recurring).await?;
+        let next_date = schedule.next_on_or_after(recurring.start_date);
+
+        Ok((recurring, next_date))
+    }
+
+    /// Replace an entry's fields
+    ///
+    /// Occurrences already posted stay as they are; the schedule is picked up
+    /// again from today (or the previous next date, if earlier).
+    pub async fn update_recurring(
+        &self,
+        id: Uuid,
+        recurring: CreateRecurringTransaction,
+    ) -> Result<RecurringWithUpcoming> {
+        let previous = self.ensure_owned(recurring.user_id, id).await?;
+        let (recurring, schedule) = self.validate(recurring).await?;
+
+        let today = chrono::Local::now().date_naive();
+        let next_date = schedule.next_on_or_after(previous.next_date.min(today));
+
+        let updated = RecurringRepository::update(&self.pool, id, recurring, next_date)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Recurring transaction {}", id)))?;
+        with_upcoming(updated)
+    }
+
+    pub async fn delete_recurring(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        if !RecurringRepository::delete(&self.pool, id, user_id).await? {
+            return Err(AppError::NotFound(format!("Recurring transaction {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Pause or resume an entry
+    ///
+    /// Occurrences that came due while paused are not posted on resume.
+    pub async fn set_paused(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        is_paused: bool,
+    ) -> Result<RecurringWithUpcoming> {
+        let previous = self.ensure_owned(user_id, id).await?;
+        let schedule = schedule_of(&previous)?;
+
+        let today = chrono::Local::now().date_naive();
+        let next_date = if !is_paused && previous.next_date < today {
+            schedule.next_on_or_after(today)
+        } else {
+            previous.next_date
+        };
+
+        let updated =
+            RecurringRepository::set_paused(&self.pool, id, user_id, is_paused, next_date)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Recurring transaction {}", id)))?;
+        with_upcoming(updated)
+    }
+
+    /// Skip one upcoming occurrence, the next one when no date is given
+    pub async fn skip_occurrence(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        date: Option<NaiveDate>,
+    ) -> Result<RecurringWithUpcoming> {
+        let recurring = self.ensure_owned(user_id, id).await?;
+        let schedule = schedule_of(&recurring)?;
+
+        let date = match date {
+            Some(date) => date,
+            None => schedule
+                .upcoming(
+                    recurring.next_date,
+                    recurring.end_date,
+                    &recurring.skipped_dates,
+                    1,
+                )
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    AppError::Validation("There are no upcoming occurrences".to_string())
+                })?,
+        };
+
+        if date < recurring.next_date
+            || schedule.next_on_or_after(date) != date
+            || recurring.end_date.is_some_and(|end| date > end)
+        {
+            return Err(AppError::Validation(format!(
+                "{} is not an upcoming occurrence",
+                date
+            )));
+        }
+
+        let updated = RecurringRepository::add_skipped_date(&self.pool, id, user_id, date)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Recurring transaction {}", id)))?;
+        with_upcoming(updated)
+    }
+
+    /// Post every occurrence due on or before `today`, for all users
+    ///
+    /// Reminder-only entries just move on to their next occurrence.
+    /// Returns the transactions created. An entry that fails is logged and
+    /// retried on the next run; the others are still posted.
+    pub async fn post_due(&self, today: NaiveDate) -> Result<Vec<Transaction>> {
+        let mut posted = Vec::new();
+        for recurring in RecurringRepository::find_due(&self.pool, today).await? {
+            if let Err(e) = self.post_entry(&recurring, today, &mut posted).await {
+                tracing::warn!(
+                    "Failed to post recurring transaction {}: {}",
+                    recurring.id,
+                    e
+                );
+            }
+        }
+
+        if !posted.is_empty() {
+            tracing::info!("Posted {} recurring transactions", posted.len());
+        }
+        Ok(posted)
+    }
+
+    async fn post_entry(
+        &self,
+        recurring: &RecurringTransaction,
+        today: NaiveDate,
+        posted: &mut Vec<Transaction>,
+    ) -> Result<()> {
+        let schedule = schedule_of(recurring)?;
+        let transactions = TransactionService::new(self.pool.clone());
+
+        let mut date = recurring.next_date;
+        while date <= today && recurring.end_date.is_none_or(|end| date <= end) {
+            if !recurring.reminder_only && !recurring.skipped_dates.contains(&date) {
+                // The occurrence date makes the id unique, so a run interrupted
+                // before moving `next_date` does not post it twice
+                let external_id = format!("{}:{}", recurring.id, date);
+                let existing = TransactionRepository::find_by_external_id(
+                    &self.pool,
+                    recurring.user_id,
+                    RECURRING_SOURCE,
+                    &external_id,
+                )
+                .await?;
+
+                if existing.is_none() {
+                    let created = transactions
+                        .create_transaction_from_dto(CreateTransaction {
+                            user_id: recurring.user_id,
+                            category_id: recurring.category_id,
+                            amount: recurring.amount,
+                            store: recurring.store.clone(),
+                            description: recurring.description.clone(),
+                            source: RECURRING_SOURCE.to_string(),
+                            email_message_id: None,
+                            external_id: Some(external_id),
+                            transaction_date: date,
+                            value_date: None,
+                            merchant_id: None,
+                            account_id: recurring.account_id,
+                            transfer_id: None,
+                            currency: recurring.currency.clone(),
+                        })
+                        .await?;
+                    posted.push(created);
+                }
+            }
+
+            date = schedule.following(date);
+            RecurringRepository::set_next_date(&self.pool, recurring.id, date).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn validate(
+        &self,
+        mut recurring: CreateRecurringTransaction,
+    ) -> Result<(CreateRecurringTransaction, Schedule)> {
+        if recurring.amount == Decimal::ZERO {
+            return Err(AppError::Validation("Amount cannot be zero".to_string()));
+        }
+
+        let schedule = Schedule::new(
+            &recurring.frequency,
+            recurring.day_of_month,
+            recurring.start_date,
+        )?;
+        // Only monthly schedules use a day of the month
+        recurring.day_of_month = match schedule.frequency {
+            Frequency::Monthly => Some(schedule.day_of_month as i16),
+            _ => None,
+        };
+
+        if recurring
+            .end_date
+            .is_some_and(|end| end < recurring.start_date)
+        {
+            return Err(AppError::Validation(
+                "End date cannot be before the start date".to_string(),
+            ));
+        }
+
+        recurring.currency = recurring
+            .currency
+            .as_deref()
+            .map(fx::normalize_code)
+            .transpose()?;
+        if let Some(account_id) = recurring.account_id {
+            ensure_account_owned(&self.pool, recurring.user_id, account_id).await?;
+        }
+
+        if let Some(category_id) = recurring.category_id {
+            match CategoryRepository::find_by_id(&self.pool, category_id).await? {
+                Some(category) if category.user_id == recurring.user_id => {}
+                Some(_) => {
+                    return Err(AppError::Forbidden(format!(
+                        "Category {} belongs to another user",
+                        category_id
+                    )))
+                }
+                None => return Err(AppError::NotFound(format!("Category {}", category_id))),
+            }
+        }
+
+        Ok((recurring, schedule))
+    }
+
+    /// Distinguish a missing entry from one owned by another user
+    async fn ensure_owned(&self, user_id: Uuid, id: Uuid) -> Result<RecurringTransaction> {
+        match RecurringRepository::find_by_id(&self.pool, id).await? {
+            Some(recurring) if recurring.user_id == user_id => Ok(recurring),
+            Some(_) => Err(AppError::Forbidden(format!(
+                "Recurring transaction {} belongs to another user",
+                id
+            ))),
+            None => Err(AppError::NotFound(format!("Recurring transaction {}", id))),
+        }
+    }
+}
+
+fn schedule_of(recurring: &RecurringTransaction) -> Result<Schedule> {
+    Schedule::new(
+        &recurring.frequency,
+        recurring.day_of_month,
+        recurring.start_date,
+    )
+}
+
+/// An entry with its next occurrences (none while paused)
+pub(crate) fn with_upcoming(recurring: RecurringTransaction) -> Result<RecurringWithUpcoming> {
+    let upcoming = if recurring.is_paused {
+        Vec::new()
+    } else {
+        schedule_of(&recurring)?.upcoming(
+            recurring.next_date,
+            recurring.end_date,
+            &recurring.skipped_dates,
+            UPCOMING_COUNT,
+        )
+    };
+
+    Ok(RecurringWithUpcoming {
+        recurring,
+        upcoming,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repository::AccountRepository;
+    use crate::models::CreateAccount;
+    use crate::test_support::create_user;
+    use sqlx::PgPool;
+
+    fn rent(
+        user_id: Uuid,
+        account_id: Option<Uuid>,
+        currency: Option<&str>,
+    ) -> CreateRecurringTransaction {
+        CreateRecurringTransaction {
+            user_id,
+            category_id: None,
+            amount: Decimal::new(-45000, 2),
+            store: Some("LANDLORD".to_string()),
+            description: None,
+            frequency: "monthly".to_string(),
+            day_of_month: Some(1),
+            start_date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            end_date: None,
+            account_id,
+            currency: currency.map(str::to_string),
+            reminder_only: false,
+        }
+    }
+
+    #[sqlx::test]
+    async fn occurrences_are_booked_to_the_entry_account(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let savings = AccountRepository::create(
+            &pool,
+            CreateAccount {
+                user_id: user.id,
+                name: "Savings".to_string(),
+                account_type: "savings".to_string(),
+                opening_balance: Decimal::ZERO,
+                currency: "EUR".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let service = RecurringService::new(pool.clone());
+        service
+            .create_recurring(rent(user.id, Some(savings.id), Some("eur")))
+            .await
+            .unwrap();
+
+        let posted = service
+            .post_due(NaiveDate::from_ymd_opt(2026, 4, 15).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(posted.len(), 2);
+        assert!(posted
+            .iter()
+            .all(|tx| tx.account_id == savings.id && tx.currency == "EUR"));
+    }
+
+    #[sqlx::test]
+    async fn reminder_only_entries_advance_without_posting(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = RecurringService::new(pool.clone());
+        let created = service
+            .create_recurring(CreateRecurringTransaction {
+                reminder_only: true,
+                ..rent(user.id, None, None)
+            })
+            .await
+            .unwrap();
+
+        let posted = service
+            .post_due(NaiveDate::from_ymd_opt(2026, 4, 15).unwrap())
+            .await
+            .unwrap();
+
+        assert!(posted.is_empty());
+        let entry = service
+            .ensure_owned(user.id, created.recurring.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            entry.next_date,
+            NaiveDate::from_ymd_opt(2026, 5, 1).unwrap()
+        );
+    }
+
+    #[sqlx::test]
+    async fn rejects_other_users_accounts_and_invalid_currencies(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        let other_account = AccountRepository::find_by_user(&pool, other.id)
+            .await
+            .unwrap()
+            .remove(0);
+        let service = RecurringService::new(pool);
+
+        let foreign = service
+            .create_recurring(rent(user.id, Some(other_account.id), None))
+            .await;
+        assert!(matches!(foreign, Err(AppError::Forbidden(_))));
+
+        let invalid = service
+            .create_recurring(rent(user.id, None, Some("EURO")))
+            .await;
+        assert!(matches!(invalid, Err(AppError::Validation(_))));
+    }
+}
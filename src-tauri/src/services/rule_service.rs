@@ -0,0 +1,280 @@
+use crate::categorize::learning::TrainingDelta;
+use crate::categorize::{self, RuleEngine, RuleInput};
+use crate::db::repository::{
+    CategoryRepository, MerchantRepository, RuleRepository, TrainingRepository,
+    TransactionRepository,
+};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::models::{CategorizationRule, CreateCategorizationRule, RuleMatch};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct RuleService {
+    pool: DbPool,
+}
+
+impl RuleService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_rules(&self, user_id: Uuid) -> Result<Vec<CategorizationRule>> {
+        RuleRepository::find_by_user(&self.pool, user_id).await
+    }
+
+    pub async fn create_rule(&self, rule: CreateCategorizationRule) -> Result<CategorizationRule> {
+        let rule = self.validate(rule).await?;
+        RuleRepository::create(&self.pool, rule).await
+    }
+
+    pub async fn update_rule(
+        &self,
+        id: Uuid,
+        rule: CreateCategorizationRule,
+    ) -> Result<CategorizationRule> {
+        let rule = self.validate(rule).await?;
+        RuleRepository::update(&self.pool, id, rule)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Rule {}", id)))
+    }
+
+    pub async fn delete_rule(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        if !RuleRepository::delete(&self.pool, id, user_id).await? {
+            return Err(AppError::NotFound(format!("Rule {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Category changes the rules would make to uncategorized transactions
+    pub async fn preview_rules(&self, user_id: Uuid) -> Result<Vec<RuleMatch>> {
+        let engine = RuleEngine::load(&self.pool, user_id).await?;
+        if engine.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let transactions = TransactionRepository::find_uncategorized(&self.pool, user_id).await?;
+        let merchants: HashMap<Uuid, String> =
+            MerchantRepository::find_by_user(&self.pool, user_id)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.name))
+                .collect();
+
+        Ok(transactions
+            .into_iter()
+            .filter_map(|tx| {
+                let rule = engine.find_match(RuleInput {
+                    store: tx.store.as_deref(),
+                    merchant: tx
+                        .merchant_id
+                        .and_then(|id| merchants.get(&id))
+                        .map(String::as_str),
+                    description: tx.description.as_deref(),
+                    amount: tx.amount,
+                    source: tx.source.as_deref(),
+                })?;
+
+                Some(RuleMatch {
+                    transaction_id: tx.id,
+                    store: tx.store.clone(),
+                    description: tx.description.clone(),
+                    amount: tx.amount,
+                    transaction_date: tx.transaction_date,
+                    rule_id: rule.id,
+                    category_id: rule.category_id,
+                })
+            })
+            .collect())
+    }
+
+    /// Categorize uncategorized transactions using the rules
+    ///
+    /// Returns the changes that were made
+    pub async fn apply_rules(&self, user_id: Uuid) -> Result<Vec<RuleMatch>> {
+        let matches = self.preview_rules(user_id).await?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let mut applied = Vec::with_capacity(matches.len());
+        let mut training = TrainingDelta::default();
+        for rule_match in matches {
+            let updated = TransactionRepository::set_category_if_empty(
+                &mut *db_tx,
+                rule_match.transaction_id,
+                user_id,
+                rule_match.category_id,
+            )
+            .await?;
+
+            if updated {
+                training.learn(
+                    rule_match.category_id,
+                    rule_match.store.as_deref(),
+                    rule_match.description.as_deref(),
+                );
+                applied.push(rule_match);
+            }
+        }
+        TrainingRepository::apply(&mut db_tx, user_id, &training).await?;
+        db_tx.commit().await?;
+
+        tracing::info!("Categorized {} transactions by rules", applied.len());
+        Ok(applied)
+    }
+
+    async fn validate(
+        &self,
+        mut rule: CreateCategorizationRule,
+    ) -> Result<CreateCategorizationRule> {
+        rule.name = rule.name.trim().to_string();
+        if rule.name.is_empty() {
+            return Err(AppError::Validation(
+                "Rule name cannot be empty".to_string(),
+            ));
+        }
+
+        categorize::validate_rule(&rule.match_field, &rule.match_type, rule.pattern.as_deref())?;
+
+        if let (Some(min), Some(max)) = (rule.min_amount, rule.max_amount) {
+            if min > max {
+                return Err(AppError::Validation(
+                    "Minimum amount cannot be greater than maximum amount".to_string(),
+                ));
+            }
+        }
+
+        match CategoryRepository::find_by_id(&self.pool, rule.category_id).await? {
+            Some(category) if category.user_id == rule.user_id => Ok(rule),
+            Some(_) => Err(AppError::Forbidden(format!(
+                "Category {} belongs to another user",
+                rule.category_id
+            ))),
+            None => Err(AppError::NotFound(format!("Category {}", rule.category_id))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateCategory, CreateTransaction};
+    use crate::services::TransactionService;
+    use crate::test_support::create_user;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+
+    async fn category(pool: &PgPool, user_id: Uuid, name: &str) -> Uuid {
+        CategoryRepository::create(
+            pool,
+            CreateCategory {
+                user_id,
+                name: name.to_string(),
+                icon: None,
+                is_income: false,
+                parent_id: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    fn rule(user_id: Uuid, category_id: Uuid, pattern: &str) -> CreateCategorizationRule {
+        CreateCategorizationRule {
+            user_id,
+            category_id,
+            name: pattern.to_string(),
+            match_field: "any".to_string(),
+            match_type: "contains".to_string(),
+            pattern: Some(pattern.to_string()),
+            min_amount: None,
+            max_amount: None,
+            source: None,
+            priority: 0,
+            is_active: true,
+        }
+    }
+
+    #[sqlx::test]
+    async fn lower_priority_values_are_tried_first(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let fuel = category(&pool, user.id, "Fuel").await;
+        let car_wash = category(&pool, user.id, "Car wash").await;
+        let service = RuleService::new(pool.clone());
+        service
+            .create_rule(CreateCategorizationRule {
+                priority: 10,
+                ..rule(user.id, fuel, "shell")
+            })
+            .await
+            .unwrap();
+        service
+            .create_rule(CreateCategorizationRule {
+                priority: 1,
+                ..rule(user.id, car_wash, "shell wash")
+            })
+            .await
+            .unwrap();
+
+        let transactions = TransactionService::new(pool.clone());
+        let mut categories = Vec::new();
+        for store in ["SHELL WASH 12", "SHELL 12"] {
+            let created = transactions
+                .create_transaction_from_dto(CreateTransaction {
+                    user_id: user.id,
+                    category_id: None,
+                    amount: Decimal::new(-1500, 2),
+                    store: Some(store.to_string()),
+                    description: None,
+                    source: "manual".to_string(),
+                    email_message_id: None,
+                    external_id: None,
+                    transaction_date: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+                    value_date: None,
+                    merchant_id: None,
+                    account_id: None,
+                    transfer_id: None,
+                    currency: None,
+                })
+                .await
+                .unwrap();
+            categories.push(created.category_id);
+        }
+
+        assert_eq!(categories, vec![Some(car_wash), Some(fuel)]);
+    }
+
+    #[sqlx::test]
+    async fn invalid_rules_are_rejected_on_save(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        let fuel = category(&pool, user.id, "Fuel").await;
+        let foreign = category(&pool, other.id, "Fuel").await;
+        let service = RuleService::new(pool.clone());
+
+        let regex = service
+            .create_rule(CreateCategorizationRule {
+                match_type: "regex".to_string(),
+                pattern: Some("(shell".to_string()),
+                ..rule(user.id, fuel, "shell")
+            })
+            .await;
+        assert!(matches!(regex, Err(AppError::Validation(_))));
+
+        let bounds = service
+            .create_rule(CreateCategorizationRule {
+                min_amount: Some(Decimal::new(50, 0)),
+                max_amount: Some(Decimal::new(10, 0)),
+                ..rule(user.id, fuel, "shell")
+            })
+            .await;
+        assert!(matches!(bounds, Err(AppError::Validation(_))));
+
+        let category = service.create_rule(rule(user.id, foreign, "shell")).await;
+        assert!(matches!(category, Err(AppError::Forbidden(_))));
+
+        assert!(service.get_rules(user.id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::categorize::text;
+use crate::db::repository::{
+    MerchantRepository, RecurringRepository, SubscriptionRepository, TransactionRepository,
+};
+use crate::db::DbPool;
+use crate::error::{AppError, Result};
+use crate::models::{
+    CreateRecurringTransaction, DetectedSubscription, RecurringWithUpcoming, SubscriptionReport,
+    Transaction,
+};
+use crate::recurring::detect::{self, Charge};
+use crate::recurring::Frequency;
+use crate::services::recurring_service::with_upcoming;
+use crate::services::RecurringService;
+use chrono::{Datelike, Months, NaiveDate};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// How far back charges are analyzed (yearly subscriptions need two)
+const HISTORY_MONTHS: u32 = 25;
+
+pub struct SubscriptionService {
+    pool: DbPool,
+}
+
+impl SubscriptionService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Subscriptions found in the user's history, confirmed and dismissed
+    /// ones left out, most expensive first
+    pub async fn get_detected(&self, user_id: Uuid) -> Result<SubscriptionReport> {
+        let today = chrono::Local::now().date_naive();
+        let decided: HashSet<String> =
+            SubscriptionRepository::find_decided_keys(&self.pool, user_id)
+                .await?
+                .into_iter()
+                .collect();
+
+        let mut subscriptions: Vec<DetectedSubscription> = self
+            .detect_all(user_id, today)
+            .await?
+            .into_iter()
+            .filter(|s| !decided.contains(&s.key))
+            .collect();
+        subscriptions.sort_by(|a, b| {
+            b.monthly_cost
+                .cmp(&a.monthly_cost)
+                .then(a.name.cmp(&b.name))
+        });
+
+        let monthly_total = subscriptions.iter().map(|s| s.monthly_cost).sum();
+        let annual_total = subscriptions.iter().map(|s| s.annual_cost).sum();
+
+        Ok(SubscriptionReport {
+            subscriptions,
+            monthly_total,
+            annual_total,
+        })
+    }
+
+    /// Turn a detected subscription into a recurring transaction starting
+    /// with its next expected charge
+    ///
+    /// The entry is reminder-only: the charges keep arriving through imports,
+    /// posting them as well would count every charge twice.
+    pub async fn confirm(&self, user_id: Uuid, key: &str) -> Result<RecurringWithUpcoming> {
+        let today = chrono::Local::now().date_naive();
+        let subscription = self
+            .detect_all(user_id, today)
+            .await?
+            .into_iter()
+            .find(|s| s.key == key)
+            .ok_or_else(|| AppError::NotFound(format!("Subscription {}", key)))?;
+
+        // Monthly charges stay on the latest charge's day, even when a short
+        // month moved the next expected date
+        let day_of_month = match Frequency::parse(&subscription.frequency)? {
+            Frequency::Monthly => Some(subscription.last_date.day() as i16),
+            _ => None,
+        };
+
+        let (recurring, next_date) = RecurringService::new(self.pool.clone())
+            .prepare(CreateRecurringTransaction {
+                user_id,
+                category_id: subscription.category_id,
+                amount: -subscription.amount,
+                store: Some(subscription.name),
+                description: None,
+                frequency: subscription.frequency,
+                day_of_month,
+                start_date: subscription.next_date,
+                end_date: None,
+                account_id: None,
+                currency: None,
+                reminder_only: true,
+            })
+            .await?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let recurring = RecurringRepository::create(&mut *db_tx, recurring, next_date).await?;
+        SubscriptionRepository::record_decision(
+            &mut *db_tx,
+            user_id,
+            key,
+            "confirmed",
+            Some(recurring.id),
+        )
+        .await?;
+        db_tx.commit().await?;
+
+        with_upcoming(recurring)
+    }
+
+    /// Hide a detected subscription from future results
+    pub async fn dismiss(&self, user_id: Uuid, key: &str) -> Result<()> {
+        if key.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Subscription key cannot be empty".to_string(),
+            ));
+        }
+
+        SubscriptionRepository::record_decision(&self.pool, user_id, key, "dismissed", None).await
+    }
+
+    async fn detect_all(
+        &self,
+        user_id: Uuid,
+        today: NaiveDate,
+    ) -> Result<Vec<DetectedSubscription>> {
+        let since = today
+            .checked_sub_months(Months::new(HISTORY_MONTHS))
+            .unwrap_or(NaiveDate::MIN);
+        let transactions =
+            TransactionRepository::find_store_expenses_since(&self.pool, user_id, since).await?;
+        let merchants: HashMap<Uuid, String> =
+            MerchantRepository::find_by_user(&self.pool, user_id)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.name))
+                .collect();
+
+        // Grouped by merchant, falling back to the normalized store name
+        let mut groups: HashMap<String, Vec<&Transaction>> = HashMap::new();
+        for tx in &transactions {
+            let key = match (tx.merchant_id, tx.store.as_deref()) {
+                (Some(merchant_id), _) => format!("merchant:{}", merchant_id),
+                (None, Some(store)) => format!("store:{}", text::merchant_key(store)),
+                (None, None) => continue,
+            };
+            groups.entry(key).or_default().push(tx);
+        }
+
+        let mut detected = Vec::new();
+        for (key, group) in groups {
+            let charges: Vec<Charge> = group
+                .iter()
+                .map(|tx| Charge {
+                    date: tx.transaction_date,
+                    amount: tx.amount,
+                })
+                .collect();
+            let Some(pattern) = detect::detect(&charges, today) else {
+                continue;
+            };
+
+            let Some(latest) = group.last() else {
+                continue;
+            };
+            let name = latest
+                .merchant_id
+                .and_then(|id| merchants.get(&id).cloned())
+                .or_else(|| latest.store.as_deref().map(text::merchant_display_name))
+                .unwrap_or_else(|| key.clone());
+
+            detected.push(DetectedSubscription {
+                key,
+                merchant_id: latest.merchant_id,
+                name,
+                category_id: latest.category_id,
+                frequency: pattern.frequency.as_str().to_string(),
+                amount: pattern.amount,
+                increased_from: pattern.increased_from,
+                occurrences: pattern.occurrences,
+                first_date: pattern.first_date,
+                last_date: pattern.last_date,
+                next_date: pattern.next_date,
+                monthly_cost: pattern.monthly_cost(),
+                annual_cost: pattern.annual_cost(),
+            });
+        }
+
+        Ok(detected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateTransaction;
+    use crate::services::TransactionService;
+    use crate::test_support::create_user;
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn confirmed_subscriptions_are_reminders_only(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let today = chrono::Local::now().date_naive();
+        let transactions = TransactionService::new(pool.clone());
+        for months_ago in 1..=4 {
+            transactions
+                .create_transaction_from_dto(CreateTransaction {
+                    user_id: user.id,
+                    category_id: None,
+                    amount: Decimal::new(-1299, 2),
+                    store: Some("NETFLIX".to_string()),
+                    description: None,
+                    source: "manual".to_string(),
+                    email_message_id: None,
+                    external_id: None,
+                    transaction_date: today - Months::new(months_ago),
+                    value_date: None,
+                    merchant_id: None,
+                    account_id: None,
+                    transfer_id: None,
+                    currency: None,
+                })
+                .await
+                .unwrap();
+        }
+        let service = SubscriptionService::new(pool.clone());
+        let detected = service.get_detected(user.id).await.unwrap();
+        assert_eq!(detected.subscriptions.len(), 1);
+
+        let confirmed = service
+            .confirm(user.id, &detected.subscriptions[0].key)
+            .await
+            .unwrap();
+
+        assert!(confirmed.recurring.reminder_only);
+        assert!(service
+            .get_detected(user.id)
+            .await
+            .unwrap()
+            .subscriptions
+            .is_empty());
+        let posted = RecurringService::new(pool)
+            .post_due(confirmed.recurring.next_date)
+            .await
+            .unwrap();
+        assert!(posted.is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::categorize::learning::{self, TrainingDelta};
+use crate::db::repository::{CategoryRepository, TrainingRepository, TransactionRepository};
+use crate::db::DbPool;
+use crate::error::Result;
+use crate::models::{CategorySuggestion, TransactionFilter};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct SuggestionService {
+    pool: DbPool,
+}
+
+impl SuggestionService {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Most likely categories for a store/description, best first
+    pub async fn suggest_category(
+        &self,
+        user_id: Uuid,
+        store: Option<&str>,
+        description: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<CategorySuggestion>> {
+        let tokens = learning::tokenize(store, description);
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut model = TrainingRepository::load(&self.pool, user_id, &tokens).await?;
+        // Users with history from before learning existed start untrained
+        if model.totals.is_empty() && self.retrain(user_id).await? > 0 {
+            model = TrainingRepository::load(&self.pool, user_id, &tokens).await?;
+        }
+
+        let names: HashMap<Uuid, String> = CategoryRepository::find_by_user(&self.pool, user_id)
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c.name))
+            .collect();
+
+        Ok(model
+            .rank(&tokens, limit)
+            .into_iter()
+            .filter_map(|(category_id, confidence)| {
+                Some(CategorySuggestion {
+                    category_id,
+                    name: names.get(&category_id)?.clone(),
+                    confidence,
+                })
+            })
+            .collect())
+    }
+
+    /// Rebuild the statistics from all categorized transactions
+    ///
+    /// Returns the number of transactions learned from
+    pub async fn retrain(&self, user_id: Uuid) -> Result<usize> {
+        let transactions = TransactionRepository::find_for_export(
+            &self.pool,
+            user_id,
+            &TransactionFilter::default(),
+        )
+        .await?;
+
+        let mut delta = TrainingDelta::default();
+        let mut learned = 0;
+        for row in &transactions {
+            let tx = &row.transaction;
+            if let Some(category_id) = tx.category_id {
+                delta.learn(category_id, tx.store.as_deref(), tx.description.as_deref());
+                learned += 1;
+            }
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        TrainingRepository::clear(&mut db_tx, user_id).await?;
+        TrainingRepository::apply(&mut db_tx, user_id, &delta).await?;
+        db_tx.commit().await?;
+
+        tracing::info!("Trained category suggestions on {} transactions", learned);
+        Ok(learned)
+    }
+}
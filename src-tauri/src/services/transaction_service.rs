@@ -1,9 +1,21 @@
-use crate::db::repository::{MonthlySummary, TransactionRepository};
+use crate::categorize::learning::TrainingDelta;
+use crate::categorize::RuleEngine;
+use crate::db::repository::{
+    AccountRepository, CategoryRepository, FxRateRepository, MerchantRepository, MonthlySummary,
+    TrainingRepository, TransactionRepository, TransactionSplitRepository,
+};
 use crate::db::DbPool;
 use crate::error::{AppError, Result};
-use crate::models::{CreateTransaction, Transaction, TransactionFilter};
+use crate::fx;
+use crate::models::{
+    CreateTransaction, CreateTransfer, SplitLine, Transaction, TransactionFilter, TransactionSplit,
+    Transfer, UpdateTransaction,
+};
+use crate::services::account_service::ensure_account_owned;
+use crate::services::merchant_service::link_merchant;
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use sqlx::postgres::PgExecutor;
 use uuid::Uuid;
 
 pub struct TransactionService {
@@ -15,6 +27,7 @@ impl TransactionService {
         Self { pool }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_transaction(
         &self,
         user_id: Uuid,
@@ -36,11 +49,17 @@ impl TransactionService {
             description,
             category_id,
             transaction_date,
+            value_date: None,
+            merchant_id: None,
+            account_id: None,
+            transfer_id: None,
+            currency: None,
             source,
             email_message_id: None,
+            external_id: None,
         };
 
-        TransactionRepository::create(&self.pool, tx).await
+        self.insert(tx).await
     }
 
     pub async fn create_transaction_from_dto(&self, tx: CreateTransaction) -> Result<Transaction> {
@@ -48,7 +67,166 @@ impl TransactionService {
             return Err(AppError::Validation("Amount cannot be zero".to_string()));
         }
 
-        TransactionRepository::create(&self.pool, tx).await
+        self.insert(tx).await
+    }
+
+    /// Move money between two of the user's accounts
+    ///
+    /// Both legs are saved together, without category or merchant, so they
+    /// count towards account balances but not as income or expense. The
+    /// amount is in the source account's currency; between accounts in
+    /// different currencies the receiving leg is converted with the rate for
+    /// the transfer date.
+    pub async fn create_transfer(&self, transfer: CreateTransfer) -> Result<Transfer> {
+        if transfer.amount <= Decimal::ZERO {
+            return Err(AppError::Validation(
+                "Transfer amount must be positive".to_string(),
+            ));
+        }
+        if transfer.from_account_id == transfer.to_account_id {
+            return Err(AppError::Validation(
+                "Cannot transfer to the same account".to_string(),
+            ));
+        }
+
+        let from_account =
+            ensure_account_owned(&self.pool, transfer.user_id, transfer.from_account_id).await?;
+        let to_account =
+            ensure_account_owned(&self.pool, transfer.user_id, transfer.to_account_id).await?;
+
+        let received = if from_account.currency == to_account.currency {
+            transfer.amount
+        } else {
+            let rate = FxRateRepository::find_rate(
+                &self.pool,
+                &from_account.currency,
+                &to_account.currency,
+                transfer.transaction_date,
+            )
+            .await?
+            .ok_or_else(|| {
+                AppError::Validation(format!(
+                    "No exchange rate from {} to {}, import exchange rates first",
+                    from_account.currency, to_account.currency
+                ))
+            })?;
+            (transfer.amount * rate).round_dp(2)
+        };
+        for account in [&from_account, &to_account] {
+            ensure_booking_rate(
+                &self.pool,
+                transfer.user_id,
+                Some(account.id),
+                None,
+                transfer.transaction_date,
+            )
+            .await?;
+        }
+
+        let transfer_id = Uuid::new_v4();
+        let leg = |account_id: Uuid, amount: Decimal, store: String| CreateTransaction {
+            user_id: transfer.user_id,
+            category_id: None,
+            amount,
+            store: Some(store),
+            description: transfer.description.clone(),
+            source: "manual".to_string(),
+            email_message_id: None,
+            external_id: None,
+            transaction_date: transfer.transaction_date,
+            value_date: None,
+            merchant_id: None,
+            account_id: Some(account_id),
+            transfer_id: Some(transfer_id),
+            currency: None,
+        };
+
+        let mut db_tx = self.pool.begin().await?;
+        let from = TransactionRepository::create(
+            &mut *db_tx,
+            leg(
+                from_account.id,
+                -transfer.amount,
+                format!("Transfer to {}", to_account.name),
+            ),
+        )
+        .await?;
+        let to = TransactionRepository::create(
+            &mut *db_tx,
+            leg(
+                to_account.id,
+                received,
+                format!("Transfer from {}", from_account.name),
+            ),
+        )
+        .await?;
+        db_tx.commit().await?;
+
+        Ok(Transfer {
+            transfer_id,
+            from,
+            to,
+        })
+    }
+
+    /// Both legs of a transfer
+    pub async fn get_transfer(&self, user_id: Uuid, transfer_id: Uuid) -> Result<Transfer> {
+        let mut legs = TransactionRepository::find_by_transfer_id(&self.pool, user_id, transfer_id)
+            .await?
+            .into_iter();
+
+        match (legs.next(), legs.next()) {
+            (Some(from), Some(to)) => Ok(Transfer {
+                transfer_id,
+                from,
+                to,
+            }),
+            _ => Err(AppError::NotFound(format!("Transfer {}", transfer_id))),
+        }
+    }
+
+    /// Delete both legs of a transfer
+    pub async fn delete_transfer(&self, user_id: Uuid, transfer_id: Uuid) -> Result<()> {
+        if TransactionRepository::delete_transfer(&self.pool, transfer_id, user_id).await? == 0 {
+            return Err(AppError::NotFound(format!("Transfer {}", transfer_id)));
+        }
+
+        Ok(())
+    }
+
+    /// Link the merchant, categorize by rules when no category was given,
+    /// save and learn from it
+    async fn insert(&self, mut tx: CreateTransaction) -> Result<Transaction> {
+        tx.currency = tx.currency.as_deref().map(fx::normalize_code).transpose()?;
+        if let Some(account_id) = tx.account_id {
+            ensure_account_owned(&self.pool, tx.user_id, account_id).await?;
+        }
+        ensure_booking_rate(
+            &self.pool,
+            tx.user_id,
+            tx.account_id,
+            tx.currency.as_deref(),
+            tx.transaction_date,
+        )
+        .await?;
+
+        let mut db_tx = self.pool.begin().await?;
+        let merchant = link_merchant(&mut db_tx, &mut tx).await?;
+
+        if tx.category_id.is_none() {
+            RuleEngine::load(&self.pool, tx.user_id)
+                .await?
+                .apply(&mut tx, merchant.as_ref().map(|m| m.name.as_str()));
+        }
+
+        let created = TransactionRepository::create(&mut *db_tx, tx).await?;
+
+        let mut delta = TrainingDelta::default();
+        learn(&mut delta, &created, 1);
+        TrainingRepository::apply(&mut db_tx, created.user_id, &delta).await?;
+        db_tx.commit().await?;
+
+        Ok(created)
     }
 
     pub async fn get_transaction(&self, id: Uuid) -> Result<Option<Transaction>> {
@@ -120,18 +298,766 @@ impl TransactionService {
         Ok(tx.is_some())
     }
 
+    /// Update a transaction, checking that it belongs to the user
     pub async fn update_transaction(
         &self,
+        user_id: Uuid,
         id: Uuid,
-        category_id: Option<Uuid>,
-        amount: Option<Decimal>,
-        store: Option<String>,
-        description: Option<String>,
+        mut changes: UpdateTransaction,
     ) -> Result<Transaction> {
-        TransactionRepository::update(&self.pool, id, category_id, amount, store, description).await
+        if changes.amount == Some(Decimal::ZERO) {
+            return Err(AppError::Validation("Amount cannot be zero".to_string()));
+        }
+
+        let previous = self.ensure_owned(user_id, id).await?;
+        // The legs of a transfer must keep mirroring each other
+        if previous.transfer_id.is_some()
+            && (changes.category_id.is_some()
+                || changes.amount.is_some()
+                || changes.store.is_some()
+                || changes.transaction_date.is_some()
+                || changes.account_id.is_some()
+                || changes.currency.is_some())
+        {
+            return Err(AppError::Validation(
+                "Only the description of a transfer can be changed, delete and re-create it instead"
+                    .to_string(),
+            ));
+        }
+        if let Some(Some(category_id)) = changes.category_id {
+            match CategoryRepository::find_by_id(&self.pool, category_id).await? {
+                Some(category) if category.user_id == user_id => {}
+                Some(_) => {
+                    return Err(AppError::Forbidden(format!(
+                        "Category {} belongs to another user",
+                        category_id
+                    )))
+                }
+                None => return Err(AppError::NotFound(format!("Category {}", category_id))),
+            }
+        }
+        changes.currency = changes
+            .currency
+            .as_deref()
+            .map(fx::normalize_code)
+            .transpose()?;
+        if let Some(account_id) = changes.account_id {
+            let account = ensure_account_owned(&self.pool, user_id, account_id).await?;
+            // Amounts in the old account's currency move to the new one's,
+            // foreign charges keep their currency
+            if changes.currency.is_none() && account_id != previous.account_id {
+                let old_account =
+                    AccountRepository::find_by_id(&self.pool, previous.account_id).await?;
+                if old_account.is_none_or(|old| old.currency == previous.currency) {
+                    changes.currency = Some(account.currency);
+                }
+            }
+        }
+        ensure_booking_rate(
+            &self.pool,
+            user_id,
+            Some(changes.account_id.unwrap_or(previous.account_id)),
+            Some(changes.currency.as_deref().unwrap_or(&previous.currency)),
+            changes
+                .transaction_date
+                .unwrap_or(previous.transaction_date),
+        )
+        .await?;
+        // Split lines must keep adding up to the amount
+        if changes
+            .amount
+            .is_some_and(|amount| amount != previous.original_amount)
+            && !TransactionSplitRepository::find_by_transaction(&self.pool, id)
+                .await?
+                .is_empty()
+        {
+            return Err(AppError::Validation(
+                "Cannot change the amount of a split transaction, update its split lines together with it"
+                    .to_string(),
+            ));
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        let mut updated = TransactionRepository::update(&mut *db_tx, id, user_id, &changes)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Transaction {}", id)))?;
+
+        // A new store name may belong to a different merchant
+        if changes.store.is_some() {
+            let merchant = match updated.store.as_deref() {
+                Some(store) => {
+                    MerchantRepository::find_or_create(&mut db_tx, user_id, store).await?
+                }
+                None => None,
+            };
+            updated.merchant_id = merchant.map(|m| m.id);
+            TransactionRepository::set_merchant(&mut *db_tx, id, user_id, updated.merchant_id)
+                .await?;
+        }
+
+        let mut delta = TrainingDelta::default();
+        learn(&mut delta, &previous, -1);
+        learn(&mut delta, &updated, 1);
+        TrainingRepository::apply(&mut db_tx, user_id, &delta).await?;
+        db_tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    /// Delete a transaction, checking that it belongs to the user
+    ///
+    /// Deleting either leg of a transfer deletes the whole transfer.
+    pub async fn delete_transaction(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        let previous = self.ensure_owned(user_id, id).await?;
+        if let Some(transfer_id) = previous.transfer_id {
+            return self.delete_transfer(user_id, transfer_id).await;
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        if !TransactionRepository::delete(&mut *db_tx, id, user_id).await? {
+            return Err(AppError::NotFound(format!("Transaction {}", id)));
+        }
+
+        let mut delta = TrainingDelta::default();
+        learn(&mut delta, &previous, -1);
+        TrainingRepository::apply(&mut db_tx, user_id, &delta).await?;
+        db_tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// A transaction's split lines, empty when it is not split
+    pub async fn get_splits(&self, user_id: Uuid, id: Uuid) -> Result<Vec<TransactionSplit>> {
+        self.ensure_owned(user_id, id).await?;
+        TransactionSplitRepository::find_by_transaction(&self.pool, id).await
+    }
+
+    /// Replace a transaction's split lines, no lines removes the split
+    ///
+    /// Lines are in the transaction's currency and must add up to its
+    /// amount; with `amount` given the transaction's amount is changed too.
+    pub async fn set_splits(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        amount: Option<Decimal>,
+        mut lines: Vec<SplitLine>,
+    ) -> Result<Vec<TransactionSplit>> {
+        let transaction = self.ensure_owned(user_id, id).await?;
+        if transaction.transfer_id.is_some() {
+            return Err(AppError::Validation(
+                "Transfers cannot be split".to_string(),
+            ));
+        }
+        if lines.len() == 1 {
+            return Err(AppError::Validation(
+                "A split needs at least two lines".to_string(),
+            ));
+        }
+        if amount == Some(Decimal::ZERO) {
+            return Err(AppError::Validation("Amount cannot be zero".to_string()));
+        }
+
+        let expected = amount.unwrap_or(transaction.original_amount);
+        let total: Decimal = lines.iter().map(|line| line.amount).sum();
+        if !lines.is_empty() && total != expected {
+            return Err(AppError::Validation(format!(
+                "Split lines add up to {} {}, expected {} {}",
+                total, transaction.currency, expected, transaction.currency
+            )));
+        }
+
+        for line in &mut lines {
+            line.note = line
+                .note
+                .take()
+                .map(|note| note.trim().to_string())
+                .filter(|note| !note.is_empty());
+            if line.amount == Decimal::ZERO {
+                return Err(AppError::Validation(
+                    "Split line amount cannot be zero".to_string(),
+                ));
+            }
+            if let Some(category_id) = line.category_id {
+                match CategoryRepository::find_by_id(&self.pool, category_id).await? {
+                    Some(category) if category.user_id == user_id => {}
+                    Some(_) => {
+                        return Err(AppError::Forbidden(format!(
+                            "Category {} belongs to another user",
+                            category_id
+                        )))
+                    }
+                    None => return Err(AppError::NotFound(format!("Category {}", category_id))),
+                }
+            }
+        }
+
+        let amount_changed = amount.is_some_and(|amount| amount != transaction.original_amount);
+        if amount_changed {
+            ensure_booking_rate(
+                &self.pool,
+                user_id,
+                Some(transaction.account_id),
+                Some(&transaction.currency),
+                transaction.transaction_date,
+            )
+            .await?;
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        if amount_changed {
+            let changes = UpdateTransaction {
+                amount,
+                ..Default::default()
+            };
+            TransactionRepository::update(&mut *db_tx, id, user_id, &changes).await?;
+        }
+
+        TransactionSplitRepository::delete_by_transaction(&mut *db_tx, id).await?;
+        let mut splits = Vec::with_capacity(lines.len());
+        for (position, line) in lines.iter().enumerate() {
+            splits.push(
+                TransactionSplitRepository::create(&mut *db_tx, id, position as i16, line).await?,
+            );
+        }
+        db_tx.commit().await?;
+
+        Ok(splits)
     }
 
-    pub async fn delete_transaction(&self, id: Uuid) -> Result<()> {
-        TransactionRepository::delete(&self.pool, id).await
+    /// Distinguish a missing transaction from one owned by another user
+    async fn ensure_owned(&self, user_id: Uuid, id: Uuid) -> Result<Transaction> {
+        match TransactionRepository::find_by_id(&self.pool, id).await? {
+            Some(tx) if tx.user_id == user_id => Ok(tx),
+            Some(_) => Err(AppError::Forbidden(format!(
+                "Transaction {} belongs to another user",
+                id
+            ))),
+            None => Err(AppError::NotFound(format!("Transaction {}", id))),
+        }
+    }
+}
+
+/// Reject an amount that no known rate converts to the user's base
+/// currency, instead of booking it 1:1
+pub(crate) async fn ensure_booking_rate<'e, E>(
+    executor: E,
+    user_id: Uuid,
+    account_id: Option<Uuid>,
+    currency: Option<&str>,
+    date: NaiveDate,
+) -> Result<()>
+where
+    E: PgExecutor<'e>,
+{
+    match FxRateRepository::find_booking_rate(executor, user_id, account_id, currency, date).await?
+    {
+        Some((_, _, Some(_))) => Ok(()),
+        Some((currency, base_currency, None)) => Err(AppError::Validation(format!(
+            "No exchange rate from {} to {}, import exchange rates first",
+            currency, base_currency
+        ))),
+        None => Err(AppError::NotFound(match account_id {
+            Some(id) => format!("Account {}", id),
+            None => "Default account".to_string(),
+        })),
+    }
+}
+
+/// Add (`sign` 1) or remove (`sign` -1) a categorized transaction from the suggestion model
+fn learn(delta: &mut TrainingDelta, tx: &Transaction, sign: i32) {
+    let Some(category_id) = tx.category_id else {
+        return;
+    };
+
+    if sign > 0 {
+        delta.learn(category_id, tx.store.as_deref(), tx.description.as_deref());
+    } else {
+        delta.unlearn(category_id, tx.store.as_deref(), tx.description.as_deref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Account, CreateAccount, CreateCategory, FxRate};
+    use crate::test_support::create_user;
+    use sqlx::PgPool;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn purchase(
+        user_id: Uuid,
+        account_id: Option<Uuid>,
+        currency: Option<&str>,
+    ) -> CreateTransaction {
+        CreateTransaction {
+            user_id,
+            category_id: None,
+            amount: Decimal::new(-1000, 2),
+            store: Some("SHOP".to_string()),
+            description: None,
+            source: "manual".to_string(),
+            email_message_id: None,
+            external_id: None,
+            transaction_date: date("2026-03-10"),
+            value_date: None,
+            merchant_id: None,
+            account_id,
+            transfer_id: None,
+            currency: currency.map(str::to_string),
+        }
+    }
+
+    async fn usd_account(pool: &PgPool, user_id: Uuid) -> Account {
+        AccountRepository::create(
+            pool,
+            CreateAccount {
+                user_id,
+                name: "Dollar card".to_string(),
+                account_type: "credit_card".to_string(),
+                opening_balance: Decimal::ZERO,
+                currency: "USD".to_string(),
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn add_usd_rate(pool: &PgPool) {
+        // 1 EUR = 1.25 USD
+        FxRateRepository::upsert_many(
+            pool,
+            &[FxRate {
+                base: "EUR".to_string(),
+                quote: "USD".to_string(),
+                rate_date: date("2026-03-01"),
+                rate: Decimal::new(125, 2),
+                source: "test".to_string(),
+            }],
+        )
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn rejects_amounts_without_an_exchange_rate(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = TransactionService::new(pool.clone());
+
+        let missing = service
+            .create_transaction_from_dto(purchase(user.id, None, Some("USD")))
+            .await;
+        assert!(matches!(missing, Err(AppError::Validation(_))));
+
+        add_usd_rate(&pool).await;
+        let created = service
+            .create_transaction_from_dto(purchase(user.id, None, Some("USD")))
+            .await
+            .unwrap();
+        assert_eq!(created.amount, Decimal::new(-800, 2));
+        assert_eq!(created.fx_rate, Some(Decimal::new(8, 1)));
+    }
+
+    #[sqlx::test]
+    async fn moving_to_another_account_takes_its_currency(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let card = usd_account(&pool, user.id).await;
+        let service = TransactionService::new(pool.clone());
+        let created = service
+            .create_transaction_from_dto(purchase(user.id, None, None))
+            .await
+            .unwrap();
+        let changes = UpdateTransaction {
+            account_id: Some(card.id),
+            ..Default::default()
+        };
+
+        let missing = service
+            .update_transaction(user.id, created.id, changes.clone())
+            .await;
+        assert!(matches!(missing, Err(AppError::Validation(_))));
+
+        add_usd_rate(&pool).await;
+        let moved = service
+            .update_transaction(user.id, created.id, changes)
+            .await
+            .unwrap();
+        assert_eq!(moved.account_id, card.id);
+        assert_eq!(moved.currency, "USD");
+        assert_eq!(moved.amount, Decimal::new(-800, 2));
+    }
+
+    #[sqlx::test]
+    async fn balances_convert_foreign_charges_to_the_account_currency(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let card = usd_account(&pool, user.id).await;
+        add_usd_rate(&pool).await;
+        let service = TransactionService::new(pool.clone());
+        // Charged in EUR on the dollar card
+        let created = service
+            .create_transaction_from_dto(purchase(user.id, Some(card.id), Some("EUR")))
+            .await
+            .unwrap();
+
+        let balances = AccountRepository::find_with_balances(&pool, user.id)
+            .await
+            .unwrap();
+        let card_balance = balances.iter().find(|a| a.account.id == card.id).unwrap();
+        assert_eq!(card_balance.balance, Decimal::new(-1250, 2));
+
+        let register = AccountRepository::find_register(&pool, card.id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(register[0].transaction.id, created.id);
+        assert_eq!(register[0].balance, Decimal::new(-1250, 2));
+    }
+
+    #[sqlx::test]
+    async fn monthly_summary_counts_split_lines_by_category(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let category = |name: &str, is_income: bool| CreateCategory {
+            user_id: user.id,
+            name: name.to_string(),
+            icon: None,
+            is_income,
+            parent_id: None,
+        };
+        let salary = CategoryRepository::create(&pool, category("Salary", true))
+            .await
+            .unwrap();
+        let fees = CategoryRepository::create(&pool, category("Fees", false))
+            .await
+            .unwrap();
+        let service = TransactionService::new(pool.clone());
+        let payslip = service
+            .create_transaction_from_dto(CreateTransaction {
+                amount: Decimal::new(10000, 2),
+                ..purchase(user.id, None, None)
+            })
+            .await
+            .unwrap();
+        let line = |category_id: Uuid, amount: i64| SplitLine {
+            category_id: Some(category_id),
+            amount: Decimal::new(amount, 2),
+            note: None,
+        };
+        service
+            .set_splits(
+                user.id,
+                payslip.id,
+                None,
+                vec![line(salary.id, 12000), line(fees.id, -2000)],
+            )
+            .await
+            .unwrap();
+
+        let summary = service.get_monthly_summary(user.id, 2026, 3).await.unwrap();
+
+        assert_eq!(summary.income, Decimal::new(12000, 2));
+        assert_eq!(summary.expenses, Decimal::new(2000, 2));
+        assert_eq!(summary.transaction_count, 1);
+    }
+
+    #[sqlx::test]
+    async fn monthly_summary_covers_the_whole_month_by_sign(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = TransactionService::new(pool.clone());
+        for _ in 0..120 {
+            service
+                .create_transaction_from_dto(CreateTransaction {
+                    amount: Decimal::new(-100, 2),
+                    ..purchase(user.id, None, None)
+                })
+                .await
+                .unwrap();
+        }
+        // An uncategorized refund is income
+        service
+            .create_transaction_from_dto(CreateTransaction {
+                amount: Decimal::new(500, 2),
+                ..purchase(user.id, None, None)
+            })
+            .await
+            .unwrap();
+
+        let summary = service.get_monthly_summary(user.id, 2026, 3).await.unwrap();
+
+        assert_eq!(summary.income, Decimal::new(500, 2));
+        assert_eq!(summary.expenses, Decimal::new(12000, 2));
+        assert_eq!(summary.transaction_count, 121);
+    }
+
+    fn transfer(user_id: Uuid, from: Uuid, to: Uuid) -> CreateTransfer {
+        CreateTransfer {
+            user_id,
+            from_account_id: from,
+            to_account_id: to,
+            amount: Decimal::new(10000, 2),
+            transaction_date: date("2026-03-10"),
+            description: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn transfers_convert_the_receiving_leg(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let main = AccountRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap()
+            .remove(0);
+        let card = usd_account(&pool, user.id).await;
+        add_usd_rate(&pool).await;
+        let service = TransactionService::new(pool.clone());
+
+        let created = service
+            .create_transfer(transfer(user.id, main.id, card.id))
+            .await
+            .unwrap();
+
+        assert_eq!(created.from.amount, Decimal::new(-10000, 2));
+        assert_eq!(created.to.original_amount, Decimal::new(12500, 2));
+        assert_eq!(created.to.currency, "USD");
+        assert_eq!(created.to.amount, Decimal::new(10000, 2));
+        let summary = service.get_monthly_summary(user.id, 2026, 3).await.unwrap();
+        assert_eq!(summary.transaction_count, 0);
+
+        service
+            .delete_transaction(user.id, created.to.id)
+            .await
+            .unwrap();
+        assert!(TransactionRepository::find_by_id(&pool, created.from.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn a_failing_leg_leaves_no_half_transfer(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let main = AccountRepository::find_by_user(&pool, user.id)
+            .await
+            .unwrap()
+            .remove(0);
+        let savings = AccountRepository::create(
+            &pool,
+            CreateAccount {
+                user_id: user.id,
+                name: "Savings".to_string(),
+                account_type: "savings".to_string(),
+                opening_balance: Decimal::ZERO,
+                currency: "EUR".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        // Make the database reject the receiving leg
+        sqlx::raw_sql(&format!(
+            r#"
+            CREATE FUNCTION reject_leg() RETURNS trigger LANGUAGE plpgsql AS $$
+            BEGIN
+                RAISE EXCEPTION 'rejected';
+            END
+            $$;
+            CREATE TRIGGER reject_leg BEFORE INSERT ON transactions
+            FOR EACH ROW WHEN (NEW.account_id = '{}') EXECUTE FUNCTION reject_leg();
+            "#,
+            savings.id
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let service = TransactionService::new(pool.clone());
+
+        let result = service
+            .create_transfer(transfer(user.id, main.id, savings.id))
+            .await;
+
+        assert!(result.is_err());
+        let remaining = service
+            .get_transactions(user.id, TransactionFilter::default())
+            .await
+            .unwrap();
+        assert!(remaining.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn split_lines_must_add_up_to_the_amount(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = TransactionService::new(pool.clone());
+        let created = service
+            .create_transaction_from_dto(purchase(user.id, None, None))
+            .await
+            .unwrap();
+        let line = |amount: i64| SplitLine {
+            category_id: None,
+            amount: Decimal::new(amount, 2),
+            note: None,
+        };
+
+        let short = service
+            .set_splits(user.id, created.id, None, vec![line(-600), line(-300)])
+            .await;
+        assert!(matches!(short, Err(AppError::Validation(_))));
+        let single = service
+            .set_splits(user.id, created.id, None, vec![line(-1000)])
+            .await;
+        assert!(matches!(single, Err(AppError::Validation(_))));
+
+        let splits = service
+            .set_splits(user.id, created.id, None, vec![line(-600), line(-400)])
+            .await
+            .unwrap();
+        assert_eq!(splits.len(), 2);
+
+        // The amount only changes together with the lines
+        let changed = service
+            .update_transaction(
+                user.id,
+                created.id,
+                UpdateTransaction {
+                    amount: Some(Decimal::new(-1200, 2)),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(changed, Err(AppError::Validation(_))));
+        let updated = service
+            .set_splits(
+                user.id,
+                created.id,
+                Some(Decimal::new(-1200, 2)),
+                vec![line(-800), line(-400)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.len(), 2);
+        let transaction = service.get_transaction(created.id).await.unwrap().unwrap();
+        assert_eq!(transaction.amount, Decimal::new(-1200, 2));
+    }
+
+    async fn category(pool: &PgPool, user_id: Uuid, name: &str) -> Uuid {
+        CategoryRepository::create(
+            pool,
+            CreateCategory {
+                user_id,
+                name: name.to_string(),
+                icon: None,
+                is_income: false,
+                parent_id: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[sqlx::test]
+    async fn updates_only_use_the_users_own_categories(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let other = create_user(&pool, "other@example.com").await;
+        let foreign = category(&pool, other.id, "Groceries").await;
+        let service = TransactionService::new(pool.clone());
+        let created = service
+            .create_transaction_from_dto(purchase(user.id, None, None))
+            .await
+            .unwrap();
+
+        let result = service
+            .update_transaction(
+                user.id,
+                created.id,
+                UpdateTransaction {
+                    category_id: Some(Some(foreign)),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = service
+            .update_transaction(
+                user.id,
+                created.id,
+                UpdateTransaction {
+                    category_id: Some(Some(Uuid::new_v4())),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let unchanged = service.get_transaction(created.id).await.unwrap().unwrap();
+        assert_eq!(unchanged.category_id, None);
+    }
+
+    #[sqlx::test]
+    async fn updates_clear_optional_fields(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let groceries = category(&pool, user.id, "Groceries").await;
+        let service = TransactionService::new(pool.clone());
+        let created = service
+            .create_transaction_from_dto(CreateTransaction {
+                category_id: Some(groceries),
+                description: Some("Weekly shop".to_string()),
+                ..purchase(user.id, None, None)
+            })
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_transaction(
+                user.id,
+                created.id,
+                UpdateTransaction {
+                    category_id: Some(None),
+                    store: Some(None),
+                    description: Some(None),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.category_id, None);
+        assert_eq!(updated.store, None);
+        assert_eq!(updated.description, None);
+        assert_eq!(updated.amount, created.amount);
+    }
+
+    #[sqlx::test]
+    async fn a_new_date_moves_the_transaction_to_its_month(pool: PgPool) {
+        let user = create_user(&pool, "user@example.com").await;
+        let service = TransactionService::new(pool.clone());
+        let created = service
+            .create_transaction_from_dto(purchase(user.id, None, None))
+            .await
+            .unwrap();
+
+        service
+            .update_transaction(
+                user.id,
+                created.id,
+                UpdateTransaction {
+                    transaction_date: Some(date("2026-04-02")),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let march = service
+            .get_user_transactions_by_month(user.id, 2026, 3)
+            .await
+            .unwrap();
+        let april = service
+            .get_user_transactions_by_month(user.id, 2026, 4)
+            .await
+            .unwrap();
+        assert!(march.is_empty());
+        assert_eq!(april.len(), 1);
+        assert_eq!(april[0].id, created.id);
     }
 }
@@ -0,0 +1,64 @@
+//! Helpers shared by the database and export tests
+
+use crate::db::repository::{AccountRepository, UserRepository};
+use crate::models::{CreateUser, Transaction, TransactionWithCategory, User};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Create a user with their default account, like a first login does
+pub async fn create_user(pool: &PgPool, email: &str) -> User {
+    let user = UserRepository::create(
+        pool,
+        CreateUser {
+            google_id: format!("google-{}", email),
+            email: email.to_string(),
+            f_name: "Test".to_string(),
+            l_name: "User".to_string(),
+            photo_url: None,
+        },
+    )
+    .await
+    .unwrap();
+    AccountRepository::ensure_default(pool, user.id)
+        .await
+        .unwrap();
+
+    user
+}
+
+/// An EUR transaction as exporters receive it (not stored anywhere)
+pub fn exported_transaction(
+    date: &str,
+    amount: &str,
+    store: Option<&str>,
+    description: Option<&str>,
+    category: Option<&str>,
+) -> TransactionWithCategory {
+    let amount: Decimal = amount.parse().unwrap();
+
+    TransactionWithCategory {
+        transaction: Transaction {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            category_id: None,
+            amount,
+            store: store.map(str::to_string),
+            description: description.map(str::to_string),
+            source: Some("manual".to_string()),
+            email_message_id: None,
+            external_id: None,
+            transaction_date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            value_date: None,
+            merchant_id: None,
+            account_id: Uuid::nil(),
+            transfer_id: None,
+            currency: "EUR".to_string(),
+            original_amount: amount,
+            fx_rate: Some(Decimal::ONE),
+            created_at: chrono::Utc::now(),
+        },
+        category_name: category.map(str::to_string),
+    }
+}